@@ -0,0 +1,4 @@
+//! Metadata CPIs shared by the programs minting tokens through a token authority PDA, which is
+//! the update authority of both the Metaplex metadata and the Token-2022 metadata extension.
+pub mod metaplex;
+pub mod token_extension;
@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use mpl_token_metadata::{
+    accounts::Metadata,
+    instructions::{UpdateV1Cpi, UpdateV1CpiAccounts, UpdateV1InstructionArgs},
+    types::{CollectionDetailsToggle, CollectionToggle, Data, RuleSetToggle, UsesToggle},
+};
+
+/// Accounts of a Metaplex `UpdateV1` on the metadata of a fungible mint.
+pub struct MetadataAccounts<'info> {
+    pub metadata_program: AccountInfo<'info>,
+    pub metadata: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+    pub sysvar_instructions: AccountInfo<'info>,
+}
+
+impl<'info> MetadataAccounts<'info> {
+    /// Returns the current metadata data with the given fields replaced.
+    pub fn updated_data(
+        &self,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<Data> {
+        let current = Metadata::from_bytes(&self.metadata.try_borrow_data()?)?;
+        // Metaplex pads the stored strings with null bytes, which we don't want to carry over.
+        Ok(Data {
+            name: name.unwrap_or_else(|| current.name.trim_end_matches('\0').to_string()),
+            symbol: symbol.unwrap_or_else(|| current.symbol.trim_end_matches('\0').to_string()),
+            uri: uri.unwrap_or_else(|| current.uri.trim_end_matches('\0').to_string()),
+            seller_fee_basis_points: current.seller_fee_basis_points,
+            creators: current.creators,
+        })
+    }
+
+    /// Invokes `UpdateV1` as `authority`, which must be the current update authority of the
+    /// metadata, either signing the transaction or derived from `signer_seeds`.
+    pub fn update(
+        &self,
+        authority: &AccountInfo<'info>,
+        new_update_authority: Option<Pubkey>,
+        data: Option<Data>,
+        signer_seeds: &[&[&[u8]]],
+    ) -> Result<()> {
+        let args = UpdateV1InstructionArgs {
+            new_update_authority,
+            data,
+            primary_sale_happened: None,
+            is_mutable: None,
+            collection: CollectionToggle::None,
+            collection_details: CollectionDetailsToggle::None,
+            uses: UsesToggle::None,
+            rule_set: RuleSetToggle::None,
+            authorization_data: None,
+        };
+
+        let cpi_update = UpdateV1Cpi::new(
+            &self.metadata_program,
+            UpdateV1CpiAccounts {
+                authority,
+                delegate_record: None,
+                token: None,
+                mint: &self.mint,
+                metadata: &self.metadata,
+                edition: None,
+                payer: &self.payer,
+                system_program: &self.system_program,
+                sysvar_instructions: &self.sysvar_instructions,
+                authorization_rules_program: None,
+                authorization_rules: None,
+            },
+            args,
+        );
+        Ok(cpi_update.invoke_signed(signer_seeds)?)
+    }
+}
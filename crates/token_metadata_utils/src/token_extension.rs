@@ -0,0 +1,128 @@
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+use anchor_spl::token_2022_extensions::{
+    spl_pod::optional_keys::OptionalNonZeroPubkey,
+    spl_token_metadata_interface::state::{Field, TokenMetadata},
+    token_metadata_initialize, token_metadata_update_authority, token_metadata_update_field,
+    TokenMetadataInitialize, TokenMetadataUpdateAuthority, TokenMetadataUpdateField,
+};
+use spl_token_2022::extension::{BaseStateWithExtensions, StateWithExtensions};
+
+/// Accounts of the Token-2022 metadata instructions on a mint whose metadata pointer is set to
+/// itself. The token authority is both its mint authority and its metadata update authority.
+pub struct TokenMetadataAccounts<'info> {
+    pub token_program: AccountInfo<'info>,
+    pub mint: AccountInfo<'info>,
+    pub token_authority: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+impl<'info> TokenMetadataAccounts<'info> {
+    pub fn initialize(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        let metadata = TokenMetadata {
+            name: name.clone(),
+            symbol: symbol.clone(),
+            uri: uri.clone(),
+            ..Default::default()
+        };
+        self.fund_mint_rent(self.mint.data_len() + metadata.tlv_size_of()?)?;
+
+        token_metadata_initialize(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TokenMetadataInitialize {
+                    program_id: self.token_program.clone(),
+                    metadata: self.mint.clone(),
+                    update_authority: self.token_authority.clone(),
+                    mint_authority: self.token_authority.clone(),
+                    mint: self.mint.clone(),
+                },
+                signer_seeds,
+            ),
+            name,
+            symbol,
+            uri,
+        )
+    }
+
+    pub fn update_field(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        field: Field,
+        value: String,
+    ) -> Result<()> {
+        let new_len = {
+            let data = self.mint.try_borrow_data()?;
+            let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+            let mut metadata = state.get_variable_len_extension::<TokenMetadata>()?;
+            let current_size = metadata.tlv_size_of()?;
+            metadata.update(field.clone(), value.clone());
+            data.len() - current_size + metadata.tlv_size_of()?
+        };
+        self.fund_mint_rent(new_len)?;
+
+        token_metadata_update_field(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TokenMetadataUpdateField {
+                    program_id: self.token_program.clone(),
+                    metadata: self.mint.clone(),
+                    update_authority: self.token_authority.clone(),
+                },
+                signer_seeds,
+            ),
+            field,
+            value,
+        )
+    }
+
+    pub fn update_authority(
+        &self,
+        signer_seeds: &[&[&[u8]]],
+        new_update_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        token_metadata_update_authority(
+            CpiContext::new_with_signer(
+                self.token_program.clone(),
+                TokenMetadataUpdateAuthority {
+                    program_id: self.token_program.clone(),
+                    metadata: self.mint.clone(),
+                    current_authority: self.token_authority.clone(),
+                    new_authority: self.token_authority.clone(),
+                },
+                signer_seeds,
+            ),
+            OptionalNonZeroPubkey::try_from(new_update_authority)?,
+        )
+    }
+
+    /// Token-2022 reallocates the mint when writing metadata, but expects the mint to already hold
+    /// enough lamports to stay rent exempt at the new size.
+    fn fund_mint_rent(&self, new_len: usize) -> Result<()> {
+        let missing = Rent::get()?
+            .minimum_balance(new_len)
+            .saturating_sub(self.mint.lamports());
+        if missing > 0 {
+            system_program::transfer(
+                CpiContext::new(
+                    self.system_program.clone(),
+                    Transfer {
+                        from: self.payer.clone(),
+                        to: self.mint.clone(),
+                    },
+                ),
+                missing,
+            )?;
+        }
+        Ok(())
+    }
+}
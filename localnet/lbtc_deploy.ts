@@ -221,9 +221,9 @@ describe("LBTC", () => {
 
   describe("Initialize and set roles", function () {
     it("initialize: successful", async () => {
-      const burnCommission = new BN(1);
+      const burnCommission = { flat: { amount: new BN(1) } };
       const dustFeeRate = new BN(1000);
-      const mintFee = new BN(1);
+      const mintFee = { flat: { amount: new BN(1) } };
       const programData = PublicKey.findProgramAddressSync(
         [program.programId.toBuffer()],
         new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
@@ -270,53 +270,49 @@ describe("LBTC", () => {
     });
 
     //Claimer is a role which can perform autoclaim
-    it("addClaimer: successful by admin", async () => {
+    it("grantAccountRole: claimer successful by admin", async () => {
       const tx = await program.methods
-        .addClaimer(claimer.publicKey)
-        .accounts({ payer: admin.publicKey, config: configPDA })
+        .grantAccountRole(claimer.publicKey, { claimer: {} })
+        .accounts({ payer: admin.publicKey })
         .signers([admin])
         .rpc();
       await provider.connection.confirmTransaction(tx);
-      const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.claimers[0].toBase58() == claimer.publicKey.toBase58());
     });
 
     //Pauser is a role that can only set contracts on pause
-    it("addPauser: successful by admin", async () => {
+    it("grantAccountRole: pauser successful by admin", async () => {
       const tx = await program.methods
-        .addPauser(pauser.publicKey)
-        .accounts({ payer: admin.publicKey, config: configPDA })
+        .grantAccountRole(pauser.publicKey, { pauser: {} })
+        .accounts({ payer: admin.publicKey })
         .signers([admin])
         .rpc();
       await provider.connection.confirmTransaction(tx);
-      const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.pausers[0].toBase58() == pauser.publicKey.toBase58());
     });
 
     //MintFee is a fee that charged for autoclaim and transfered to treasury
     it("setMintFee: successful by operator", async () => {
       const mintFee = new BN(10);
       const tx2 = await program.methods
-        .setMintFee(mintFee)
+        .setMintFee({ flat: { amount: mintFee } })
         .accounts({ payer: operator.publicKey, config: configPDA })
         .signers([operator])
         .rpc();
       await provider.connection.confirmTransaction(tx2);
       let cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.mintFee.bigInt()).to.be.eq(mintFee.bigInt());
+      expect(cfg.mintFee.flat.amount.bigInt()).to.be.eq(mintFee.bigInt());
     });
 
     //BurnCommission is a fee that charged at redeem
     it("setBurnCommission: successful by admin", async () => {
       const burnCommission = new BN(10);
       const tx = await program.methods
-        .setBurnCommission(burnCommission)
+        .setBurnCommission({ flat: { amount: burnCommission } })
         .accounts({ payer: admin.publicKey, config: configPDA })
         .signers([admin])
         .rpc();
       await provider.connection.confirmTransaction(tx);
       const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.burnCommission.eq(burnCommission));
+      expect(cfg.burnCommission.flat.amount.eq(burnCommission));
     });
 
     it("setDustFeeRate: successful by admin", async () => {
@@ -85,6 +85,8 @@ pub enum AssetRouterError {
     InvalidSessionPayload,
     #[msg("Invalid message path")]
     InvalidMessagePath,
+    #[msg("Config already migrated")]
+    ConfigAlreadyMigrated,
 }
 
 impl From<std::io::Error> for AssetRouterError {
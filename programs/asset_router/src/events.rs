@@ -1,5 +1,5 @@
 //! Defines all events for the Lombard Finance protocol.
-use crate::state::{AccountRole, TokenConfig, TokenRouteType};
+use crate::state::{AccountRole, MetadataField, TokenConfig, TokenRouteType};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -23,6 +23,11 @@ pub struct BasculeGmpEnabled {
     pub enabled: bool,
 }
 
+#[event]
+pub struct ComplianceHookSet {
+    pub compliance_hook: Option<Pubkey>,
+}
+
 #[event]
 pub struct MintFeeSet {
     pub mint_fee: u64,
@@ -86,3 +91,26 @@ pub struct TokenRouteUnset {
     pub to_token_address: [u8; 32],
     pub token_route_type: TokenRouteType,
 }
+
+#[event]
+pub struct MetadataUpdated {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct TokenMetadataFieldUpdated {
+    pub field: MetadataField,
+    pub value: String,
+}
+
+#[event]
+pub struct MetadataUpdateAuthorityChanged {
+    pub new_update_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct ProofOfReservesSet {
+    pub proof_of_reserves: Option<Pubkey>,
+}
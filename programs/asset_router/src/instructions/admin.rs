@@ -2,7 +2,10 @@
 use crate::{
     constants,
     errors::AssetRouterError,
-    events::{BasculeEnabled, BasculeGmpEnabled, OwnershipTransferInitiated, ProgramPaused},
+    events::{
+        BasculeEnabled, BasculeGmpEnabled, ComplianceHookSet, OwnershipTransferInitiated,
+        ProgramPaused, ProofOfReservesSet,
+    },
     state::Config,
 };
 use anchor_lang::prelude::*;
@@ -33,6 +36,18 @@ pub fn set_bascule_gmp(ctx: Context<Admin>, bascule_gmp: Option<Pubkey>) -> Resu
     Ok(())
 }
 
+pub fn set_compliance_hook(ctx: Context<Admin>, compliance_hook: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.compliance_hook = compliance_hook;
+    emit!(ComplianceHookSet { compliance_hook });
+    Ok(())
+}
+
+pub fn set_proof_of_reserves(ctx: Context<Admin>, proof_of_reserves: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.proof_of_reserves = proof_of_reserves;
+    emit!(ProofOfReservesSet { proof_of_reserves });
+    Ok(())
+}
+
 pub fn unpause(ctx: Context<Admin>) -> Result<()> {
     ctx.accounts.config.paused = false;
     emit!(ProgramPaused { paused: false });
@@ -6,7 +6,9 @@ use bascule_gmp::{
     cpi::{accounts::ValidateMint, validate_mint},
     state::MintMessage,
 };
+use compliance_hook::state::DenylistEntry;
 use mailbox::{constants::MESSAGE_SEED, state::MessageV1Info};
+use proof_of_reserves::utils::reserves;
 
 use crate::{
     constants::{BASCULE_VALIDATOR_SEED, BTC_STAKING_MODULE_ADDRESS, CHAIN_ID, CONFIG_SEED, MESSAGE_HANDLED_SEED},
@@ -85,12 +87,24 @@ pub struct GMPReceive<'info> {
     /// CHECK: bascule validates mint_payload PDA.
     #[account(mut)]
     pub bascule_gmp_mint_payload: Option<UncheckedAccount<'info>>,
+    /// Required when config.compliance_hook is Some, the recipient owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub recipient_denylist_entry: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
 }
 
 pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<()> {
     let message_info = &ctx.accounts.message_info;
 
-    let computed_payload_hash = sha256(&message_info.message.to_session_payload()).to_bytes();
+    let computed_payload_hash = sha256(&message_info.to_session_payload()).to_bytes();
     require!(
         computed_payload_hash == payload_hash,
         AssetRouterError::InvalidPayloadHash
@@ -122,6 +136,12 @@ pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<(
     );
     require!(mint_message.amount > 0, AssetRouterError::ZeroAmount);
 
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.recipient.owner,
+        ctx.accounts.recipient_denylist_entry.as_ref(),
+    )?;
+
     // When bascule_gmp is configured, validate mint (message must be reported when above threshold).
     if let Some(bascule_gmp_id) = ctx.accounts.config.bascule_gmp {
         let bascule_gmp_address = ctx
@@ -183,6 +203,15 @@ pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<(
         )?;
     }
 
+    reserves::check_reserves(
+        ctx.accounts.config.proof_of_reserves,
+        ctx.accounts.reserves_program.as_ref(),
+        ctx.accounts.reserves_config.as_ref(),
+        ctx.accounts.mint_reserves.as_ref(),
+        ctx.accounts.mint.to_account_info(),
+        mint_message.amount,
+    )?;
+
     utils::execute_mint(
         ctx.accounts.token_program.to_account_info(),
         ctx.accounts.recipient.to_account_info(),
@@ -0,0 +1,68 @@
+//! Migrates the config account from the legacy layout, growing it to make room for the compliance
+//! hook and proof of reserves settings, which both start out disabled.
+use crate::{
+    constants::CONFIG_SEED,
+    errors::AssetRouterError,
+    state::{Config, LegacyConfig},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: The seeds constraint ensures the correct address is passed. The account can't be
+    /// decoded as `Config` yet, so the legacy layout and the admin are checked in the handler.
+    #[account(mut, owner = crate::ID, seeds = [CONFIG_SEED], bump)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let legacy = {
+        let data = config_info.try_borrow_data()?;
+        require_eq!(
+            data.len(),
+            8 + LegacyConfig::INIT_SPACE,
+            AssetRouterError::ConfigAlreadyMigrated
+        );
+        require!(
+            data[..8] == *Config::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        legacy.admin,
+        ctx.accounts.payer.key(),
+        AssetRouterError::Unauthorized
+    );
+
+    let new_len = 8 + Config::INIT_SPACE;
+    let missing = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(config_info.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                ctx.accounts.system_program.to_account_info(),
+                Transfer {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: config_info.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    // Zero the old contents, as optional fields may have left stale bytes behind the legacy data.
+    config_info.realloc(new_len, false)?;
+    config_info.try_borrow_mut_data()?[8..].fill(0);
+
+    let config = Config::from(legacy);
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
@@ -7,7 +7,9 @@ use bascule::{
     cpi::{accounts::Validator, validate_withdrawal},
     to_deposit_id,
 };
+use compliance_hook::state::DenylistEntry;
 use consortium::{constants::VALIDATED_PAYLOAD_SEED, state::ValidatedPayload};
+use proof_of_reserves::utils::reserves;
 
 use crate::state::DepositPayloadSpent;
 use crate::utils::consortium_payloads::{DepositV1, DEPOSIT_V1_PAYLOAD_LEN};
@@ -81,6 +83,18 @@ pub struct MintFromPayload<'info> {
     /// CHECK: bascule validates it
     #[account(mut)]
     pub bascule_deposit: Option<UncheckedAccount<'info>>,
+    /// Required when config.compliance_hook is Some, the recipient owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub recipient_denylist_entry: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
 }
 
 pub fn mint_from_payload(
@@ -89,6 +103,11 @@ pub fn mint_from_payload(
     mint_payload_hash: [u8; 32],
 ) -> Result<()> {
     let config = &ctx.accounts.config;
+    DenylistEntry::ensure_not_denylisted(
+        config.compliance_hook,
+        &ctx.accounts.recipient.owner,
+        ctx.accounts.recipient_denylist_entry.as_ref(),
+    )?;
 
     require!(
         mint_payload_hash == sha256(&mint_payload).to_bytes(),
@@ -176,6 +195,15 @@ pub fn mint_from_payload(
         payload_hash: mint_payload_hash,
     });
 
+    reserves::check_reserves(
+        config.proof_of_reserves,
+        ctx.accounts.reserves_program.as_ref(),
+        ctx.accounts.reserves_config.as_ref(),
+        ctx.accounts.mint_reserves.as_ref(),
+        ctx.accounts.mint.to_account_info(),
+        deposit_payload.amount,
+    )?;
+
     utils::execute_mint(
         ctx.accounts.token_program.to_account_info(),
         ctx.accounts.recipient.to_account_info(),
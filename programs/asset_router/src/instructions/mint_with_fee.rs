@@ -8,10 +8,12 @@ use bascule::{
     cpi::{accounts::Validator, validate_withdrawal},
     to_deposit_id,
 };
+use compliance_hook::state::DenylistEntry;
 use consortium::{
     constants::VALIDATED_PAYLOAD_SEED,
     state::{SessionPayload, ValidatedPayload},
 };
+use proof_of_reserves::utils::reserves;
 
 use crate::constants::{
     ACCOUNT_ROLES_SEED, BASCULE_VALIDATOR_SEED, CHAIN_ID, CONFIG_SEED, DEPOSIT_PAYLOAD_SPENT_SEED,
@@ -114,6 +116,18 @@ pub struct MintWithFee<'info> {
     /// CHECK: When bascule is enabled, validated in handler.
     #[account(mut)]
     pub bascule_deposit: Option<UncheckedAccount<'info>>,
+    /// Required when config.compliance_hook is Some, the recipient owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub recipient_denylist_entry: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
 }
 
 pub fn mint_with_fee(
@@ -123,6 +137,11 @@ pub fn mint_with_fee(
     fee_signature: [u8; 64],
 ) -> Result<()> {
     let config = &ctx.accounts.config;
+    DenylistEntry::ensure_not_denylisted(
+        config.compliance_hook,
+        &ctx.accounts.recipient.owner,
+        ctx.accounts.recipient_denylist_entry.as_ref(),
+    )?;
 
     let session_payload_data = ctx.accounts.session_payload.try_borrow_data()?;
     let session_payload = SessionPayload::try_deserialize(&mut session_payload_data.iter().as_slice())?;
@@ -223,6 +242,15 @@ pub fn mint_with_fee(
     require!(fee < amount, AssetRouterError::FeeGTEAmount);
     amount -= fee;
 
+    reserves::check_reserves(
+        config.proof_of_reserves,
+        ctx.accounts.reserves_program.as_ref(),
+        ctx.accounts.reserves_config.as_ref(),
+        ctx.accounts.mint_reserves.as_ref(),
+        ctx.accounts.mint.to_account_info(),
+        fee + amount,
+    )?;
+
     // mint fee to treasury
     utils::execute_mint(
         ctx.accounts.token_program.to_account_info(),
@@ -10,6 +10,8 @@ pub mod grant_account_role;
 pub use grant_account_role::*;
 pub mod initialize;
 pub use initialize::*;
+pub mod migrate_config;
+pub use migrate_config::*;
 pub mod mint_from_payload;
 pub use mint_from_payload::*;
 pub mod mint_with_fee;
@@ -36,3 +38,7 @@ pub mod set_token_route;
 pub use set_token_route::*;
 pub mod unset_token_route;
 pub use unset_token_route::*;
+pub mod token_metadata;
+pub use token_metadata::*;
+pub mod update_metadata;
+pub use update_metadata::*;
@@ -7,6 +7,7 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use compliance_hook::state::DenylistEntry;
 use mailbox::{
     cpi::{accounts::SendMessage, send_message},
     program::Mailbox, state::OutboundMessagePath,
@@ -66,7 +67,11 @@ pub struct Redeem<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub mailbox_config: UncheckedAccount<'info>,
+    /// CHECK: The event authority of the mailbox program, which emits its events through a self-CPI
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = mailbox.key())]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
     #[account(
+        mut,
         constraint = outbound_message_path.destination_chain_id == config.ledger_lchain_id @ AssetRouterError::InvalidMessagePath,
     )]
     pub outbound_message_path: Account<'info, OutboundMessagePath>,
@@ -74,16 +79,31 @@ pub struct Redeem<'info> {
     #[account(mut)]
     pub outbound_message: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
-    pub sender_config: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub outbound_message_record: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub sender_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program
+    pub sender_fee_schedule: Option<UncheckedAccount<'info>>,
+    /// Provided to pay the mailbox fee from the payer token account instead of lamports.
+    /// CHECK: This will be verified by the mailbox program
+    pub mailbox_fee_token: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub mailbox_treasury_token_account: Option<UncheckedAccount<'info>>,
 
     pub system_program: Program<'info, System>,
+    /// Required when config.compliance_hook is Some, the payer's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub payer_denylist_entry: Option<UncheckedAccount<'info>>,
 }
 
-pub fn redeem(
-    ctx: Context<Redeem>,
+pub fn redeem<'info>(
+    ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
     to_lchain_id: [u8; 32],
     _to_token_address: [u8; 32],
     recipient: [u8; 32],
@@ -91,22 +111,24 @@ pub fn redeem(
 ) -> Result<()> {
 
     require!(amount > 0, AssetRouterError::ZeroAmount);
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.payer.key(),
+        ctx.accounts.payer_denylist_entry.as_ref(),
+    )?;
 
     let fee = ctx.accounts.token_config.redeem_fee;
     require!(amount > fee, AssetRouterError::FeeGTEAmount);
 
-    anchor_spl::token_interface::transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token_interface::TransferChecked {
-                from: ctx.accounts.payer_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-            },
-        ),
+    // Remaining accounts carry the transfer hook accounts when the mint has one configured.
+    utils::execute_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.payer_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.treasury_token_account.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.remaining_accounts,
         fee,
-        constants::BTC_DECIMALS,
     )?;
 
     utils::execute_burn(
@@ -129,6 +151,7 @@ pub fn redeem(
         constants::MESSAGING_AUTHORITY_SEED,
         &[ctx.bumps.messaging_authority],
     ]];
+    let pay_fee_in_token = ctx.accounts.mailbox_fee_token.is_some();
     let cpi_context = CpiContext::new_with_signer(
         ctx.accounts.mailbox.to_account_info(),
         SendMessage {
@@ -136,24 +159,47 @@ pub fn redeem(
             sender_authority: ctx.accounts.messaging_authority.to_account_info(),
             config: ctx.accounts.mailbox_config.to_account_info(),
             outbound_message_path: ctx.accounts.outbound_message_path.to_account_info(),
+            message_buffer: None,
             outbound_message: ctx.accounts.outbound_message.to_account_info(),
-            sender_config: ctx
+            outbound_message_record: ctx.accounts.outbound_message_record.to_account_info(),
+            sender_config: ctx.accounts.sender_config.to_account_info(),
+            treasury: ctx.accounts.treasury.clone().map(|t| t.to_account_info()),
+            sender_fee_schedule: ctx
                 .accounts
-                .sender_config
+                .sender_fee_schedule
                 .clone()
-                .map(|c| c.to_account_info()),
-            treasury: ctx.accounts.treasury.clone().map(|t| t.to_account_info()),
+                .map(|s| s.to_account_info()),
+            fee_token: ctx
+                .accounts
+                .mailbox_fee_token
+                .clone()
+                .map(|f| f.to_account_info()),
+            fee_mint: pay_fee_in_token.then(|| ctx.accounts.mint.to_account_info()),
+            fee_payer_token_account: pay_fee_in_token
+                .then(|| ctx.accounts.payer_token_account.to_account_info()),
+            treasury_token_account: ctx
+                .accounts
+                .mailbox_treasury_token_account
+                .clone()
+                .map(|t| t.to_account_info()),
+            fee_token_program: pay_fee_in_token
+                .then(|| ctx.accounts.token_program.to_account_info()),
             system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
+            program: ctx.accounts.mailbox.to_account_info(),
         },
         signer_seeds,
-    );
+    )
+    .with_remaining_accounts(ctx.remaining_accounts.to_vec());
 
     send_message(
         cpi_context,
         redeem_msg.to_gmp_body(),
         ctx.accounts.token_config.ledger_redeem_handler,
         None,
-        0
+        0,
+        None,
+        false
     )?;
 
     Ok(())
@@ -10,6 +10,7 @@ use crate::{
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use compliance_hook::state::DenylistEntry;
 use mailbox::{
     cpi::{accounts::SendMessage, send_message},
     program::Mailbox, state::OutboundMessagePath,
@@ -68,7 +69,11 @@ pub struct RedeemForBtc<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub mailbox_config: UncheckedAccount<'info>,
+    /// CHECK: The event authority of the mailbox program, which emits its events through a self-CPI
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = mailbox.key())]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
     #[account(
+        mut,
         constraint = outbound_message_path.destination_chain_id == config.ledger_lchain_id @ AssetRouterError::InvalidMessagePath,
     )]
     pub outbound_message_path: Account<'info, OutboundMessagePath>,
@@ -76,20 +81,34 @@ pub struct RedeemForBtc<'info> {
     #[account(mut)]
     pub outbound_message: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
-    pub sender_config: Option<UncheckedAccount<'info>>,
+    #[account(mut)]
+    pub outbound_message_record: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub sender_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program
+    pub sender_fee_schedule: Option<UncheckedAccount<'info>>,
 
     pub system_program: Program<'info, System>,
+    /// Required when config.compliance_hook is Some, the payer's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub payer_denylist_entry: Option<UncheckedAccount<'info>>,
 }
 
-pub fn redeem_for_btc(
-    ctx: Context<RedeemForBtc>,
+pub fn redeem_for_btc<'info>(
+    ctx: Context<'_, '_, '_, 'info, RedeemForBtc<'info>>,
     script_pubkey: Vec<u8>,
     amount: u64,
 ) -> Result<()> {
     require!(amount > 0, AssetRouterError::ZeroAmount);
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.payer.key(),
+        ctx.accounts.payer_denylist_entry.as_ref(),
+    )?;
 
     let is_native = ctx.accounts.mint.key() == ctx.accounts.config.native_mint;
 
@@ -110,18 +129,15 @@ pub fn redeem_for_btc(
     // check script pubkey is supported
     let _ = bitcoin_utils::get_output_type(&script_pubkey)?;
 
-    anchor_spl::token_interface::transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token_interface::TransferChecked {
-                from: ctx.accounts.payer_token_account.to_account_info(),
-                to: ctx.accounts.treasury_token_account.to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-            },
-        ),
+    // Remaining accounts carry the transfer hook accounts when the mint has one configured.
+    utils::execute_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.payer_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.treasury_token_account.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.remaining_accounts,
         fee,
-        constants::BTC_DECIMALS,
     )?;
 
     utils::execute_burn(
@@ -160,14 +176,24 @@ pub fn redeem_for_btc(
             sender_authority: ctx.accounts.messaging_authority.to_account_info(),
             config: ctx.accounts.mailbox_config.to_account_info(),
             outbound_message_path: ctx.accounts.outbound_message_path.to_account_info(),
+            message_buffer: None,
             outbound_message: ctx.accounts.outbound_message.to_account_info(),
-            sender_config: ctx
+            outbound_message_record: ctx.accounts.outbound_message_record.to_account_info(),
+            sender_config: ctx.accounts.sender_config.to_account_info(),
+            treasury: ctx.accounts.treasury.clone().map(|t| t.to_account_info()),
+            sender_fee_schedule: ctx
                 .accounts
-                .sender_config
+                .sender_fee_schedule
                 .clone()
-                .map(|c| c.to_account_info()),
-            treasury: ctx.accounts.treasury.clone().map(|t| t.to_account_info()),
+                .map(|s| s.to_account_info()),
+            fee_token: None,
+            fee_mint: None,
+            fee_payer_token_account: None,
+            treasury_token_account: None,
+            fee_token_program: None,
             system_program: ctx.accounts.system_program.to_account_info(),
+            event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
+            program: ctx.accounts.mailbox.to_account_info(),
         },
         signer_seeds,
     );
@@ -177,7 +203,9 @@ pub fn redeem_for_btc(
         gmp_body,
         ctx.accounts.token_config.ledger_redeem_handler,
         None,
-        0
+        0,
+        None,
+        false
     )?;
 
     Ok(())
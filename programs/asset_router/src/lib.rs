@@ -9,7 +9,7 @@ pub(crate) mod utils;
 
 use anchor_lang::prelude::*;
 use instructions::*;
-use state::{AccountRole, Config, TokenConfig, TokenRouteType};
+use state::{AccountRole, Config, MetadataField, TokenConfig, TokenRouteType};
 use utils::consortium_payloads::DEPOSIT_V1_PAYLOAD_LEN;
 use utils::fee::FEE_PAYLOAD_LEN;
 
@@ -33,6 +33,10 @@ pub mod asset_router {
         instructions::initialize(ctx, config)
     }
 
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config(ctx)
+    }
+
     pub fn mint_from_payload(
         ctx: Context<MintFromPayload>,
         mint_payload: [u8; DEPOSIT_V1_PAYLOAD_LEN],
@@ -60,8 +64,8 @@ pub mod asset_router {
         instructions::deposit(ctx, to_lchain_id, to_token_address, recipient, amount)
     }
 
-    pub fn redeem(
-        ctx: Context<Redeem>,
+    pub fn redeem<'info>(
+        ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
         to_lchain_id: [u8; 32],
         to_token_address: [u8; 32],
         recipient: [u8; 32],
@@ -70,8 +74,8 @@ pub mod asset_router {
         instructions::redeem(ctx, to_lchain_id, to_token_address, recipient, amount)
     }
 
-    pub fn redeem_for_btc(
-        ctx: Context<RedeemForBtc>,
+    pub fn redeem_for_btc<'info>(
+        ctx: Context<'_, '_, '_, 'info, RedeemForBtc<'info>>,
         script_pubkey: Vec<u8>,
         amount: u64,
     ) -> Result<()> {
@@ -98,6 +102,17 @@ pub mod asset_router {
         instructions::set_bascule_gmp(ctx, bascule_gmp)
     }
 
+    pub fn set_compliance_hook(ctx: Context<Admin>, compliance_hook: Option<Pubkey>) -> Result<()> {
+        instructions::set_compliance_hook(ctx, compliance_hook)
+    }
+
+    pub fn set_proof_of_reserves(
+        ctx: Context<Admin>,
+        proof_of_reserves: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_proof_of_reserves(ctx, proof_of_reserves)
+    }
+
     pub fn set_mint_fee(ctx: Context<SetMintFee>, mint_fee: u64) -> Result<()> {
         instructions::set_mint_fee(ctx, mint_fee)
     }
@@ -175,4 +190,48 @@ pub mod asset_router {
     pub fn revoke_account_roles(ctx: Context<RevokeAccountRoles>, account: Pubkey) -> Result<()> {
         instructions::revoke_account_roles(ctx, account)
     }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        instructions::update_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn set_metadata_update_authority(
+        ctx: Context<UpdateMetadata>,
+        new_update_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_metadata_update_authority(ctx, new_update_authority)
+    }
+
+    pub fn claim_metadata_update_authority(ctx: Context<UpdateMetadata>) -> Result<()> {
+        instructions::claim_metadata_update_authority(ctx)
+    }
+
+    pub fn initialize_token_metadata(
+        ctx: Context<ManageTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize_token_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn update_token_metadata_field(
+        ctx: Context<ManageTokenMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        instructions::update_token_metadata_field(ctx, field, value)
+    }
+
+    pub fn set_token_metadata_update_authority(
+        ctx: Context<ManageTokenMetadata>,
+        new_update_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_token_metadata_update_authority(ctx, new_update_authority)
+    }
 }
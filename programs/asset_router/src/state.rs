@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
 
 #[account]
 #[derive(InitSpace)]
@@ -29,6 +30,48 @@ pub struct Config {
     pub ledger_lchain_id: [u8; 32],
     // The Lombard Chain ID of the Bitcoin blockchain
     pub bitcoin_lchain_id: [u8; 32],
+
+    /// When Some, mint and redeem paths reject accounts denylisted by this compliance hook.
+    pub compliance_hook: Option<Pubkey>,
+    /// When Some, mints are checked against the reserves attested to this program.
+    pub proof_of_reserves: Option<Pubkey>,
+}
+
+/// The layout of [`Config`] before the compliance hook and proof of reserves were added. Only used
+/// by `migrate_config` to read the existing account.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub treasury: Pubkey,
+    pub paused: bool,
+    pub native_mint: Pubkey,
+    pub consortium: Pubkey,
+    pub mailbox: Pubkey,
+    pub bascule: Option<Pubkey>,
+    pub bascule_gmp: Option<Pubkey>,
+    pub ledger_lchain_id: [u8; 32],
+    pub bitcoin_lchain_id: [u8; 32],
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Self {
+            admin: legacy.admin,
+            pending_admin: legacy.pending_admin,
+            treasury: legacy.treasury,
+            paused: legacy.paused,
+            native_mint: legacy.native_mint,
+            consortium: legacy.consortium,
+            mailbox: legacy.mailbox,
+            bascule: legacy.bascule,
+            bascule_gmp: legacy.bascule_gmp,
+            ledger_lchain_id: legacy.ledger_lchain_id,
+            bitcoin_lchain_id: legacy.bitcoin_lchain_id,
+            compliance_hook: None,
+            proof_of_reserves: None,
+        }
+    }
 }
 
 #[account]
@@ -87,3 +130,23 @@ pub struct MessageHandled {}
 #[account]
 #[derive(InitSpace)]
 pub struct MessagingAuthority {}
+
+/// A field of the Token-2022 metadata extension, mirroring the token metadata interface `Field`.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    Key(String),
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+            MetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
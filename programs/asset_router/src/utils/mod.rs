@@ -33,6 +33,30 @@ pub fn execute_mint<'info>(
     )?)
 }
 
+/// Transfers tokens with `transfer_checked`, forwarding any transfer hook accounts in
+/// `additional_accounts` to the token program.
+pub fn execute_transfer<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+) -> Result<()> {
+    Ok(spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        additional_accounts,
+        amount,
+        crate::constants::BTC_DECIMALS,
+        &[],
+    )?)
+}
+
 pub fn execute_burn<'info>(
     token_program: AccountInfo<'info>,
     from: AccountInfo<'info>,
@@ -47,6 +47,28 @@ pub enum BridgeError {
     WrongTokenOwner,
     #[msg("Invalid payload hash")]
     InvalidPayloadHash,
+    #[msg("Missing token vault account")]
+    MissingTokenVault,
+    #[msg("Invalid token vault")]
+    InvalidTokenVault,
+    #[msg("Invalid vault token account")]
+    InvalidVaultTokenAccount,
+    #[msg("Insufficient liquidity in the token vault")]
+    InsufficientLiquidity,
+    #[msg("Token is not in lock and release mode")]
+    NotLockReleaseToken,
+    #[msg("Amount cannot be converted between local and remote decimals")]
+    InvalidAmountConversion,
+    #[msg("Amount has dust below the precision of the remote token")]
+    AmountHasDust,
+    #[msg("Account already migrated")]
+    AccountAlreadyMigrated,
+    #[msg("Tokens with a transfer fee cannot be locked and released")]
+    TransferFeeNotSupported,
+    #[msg("Locked amount overflow")]
+    LockedAmountOverflow,
+    #[msg("Token vault still holds locked tokens")]
+    TokenVaultNotEmpty,
 }
 
 impl From<std::io::Error> for BridgeError {
@@ -2,7 +2,7 @@
 use anchor_lang::prelude::*;
 use base_token_pool::rate_limiter::RateLimitConfig;
 
-use crate::state::AccountRole;
+use crate::state::{AccountRole, TokenMode};
 
 #[event]
 pub struct DestinationBridgeSet {
@@ -68,6 +68,7 @@ pub struct SenderConfigUnset {
 #[event]
 pub struct LocalTokenConfigSet {
     pub mint: Pubkey,
+    pub mode: TokenMode,
 }
 
 #[event]
@@ -81,6 +82,7 @@ pub struct RemoteTokenConfigSet {
     pub chain_id: [u8; 32],
     pub token: [u8; 32], 
     pub direction: u8,
+    pub decimals: u8,
 }
 
 #[event]
@@ -105,4 +107,27 @@ pub struct BridgeRateLimitConfigured {
     pub mint: Pubkey,
     pub chain_id: [u8; 32],
     pub inbound_rate_limit: RateLimitConfig,
+    /// Set when the outbound rate limit was configured, the inbound one being left as is
+    pub outbound_rate_limit: Option<RateLimitConfig>,
+}
+
+#[event]
+pub struct ProofOfReservesSet {
+    pub proof_of_reserves: Option<Pubkey>,
+}
+
+#[event]
+pub struct LiquidityAdded {
+    pub mint: Pubkey,
+    pub rebalancer: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
+}
+
+#[event]
+pub struct LiquidityRemoved {
+    pub mint: Pubkey,
+    pub rebalancer: Pubkey,
+    pub amount: u64,
+    pub locked_amount: u64,
 }
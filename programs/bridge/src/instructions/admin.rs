@@ -2,7 +2,7 @@ use anchor_lang::prelude::*;
 use crate::{
     constants::CONFIG_SEED,
     errors::BridgeError,
-    events::{OwnershipTransferInitiated, ProgramPaused},
+    events::{OwnershipTransferInitiated, ProgramPaused, ProofOfReservesSet},
     state::Config,
 };
 
@@ -29,6 +29,12 @@ pub fn transfer_ownership(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()>
     Ok(())
 }
 
+pub fn set_proof_of_reserves(ctx: Context<Admin>, proof_of_reserves: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.proof_of_reserves = proof_of_reserves;
+    emit!(ProofOfReservesSet { proof_of_reserves });
+    Ok(())
+}
+
 // pub fn update_config(
 //     ctx: Context<Admin>,
 //     default_max_payload_size: Option<u32>,
@@ -5,8 +5,8 @@ use solana_address::bytes_are_curve_point;
 use crate::{
     constants::{self, CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED, OPTIONAL_MESSAGE_SIZE, OUTBOUND_DIRECTION, REMOTE_BRIDGE_CONFIG_SEED, REMOTE_TOKEN_CONFIG_SEED, SENDER_CONFIG_SEED}, 
     errors::BridgeError, 
-    state::{Config, LocalTokenConfig,RemoteBridgeConfig, RemoteTokenConfig,SenderConfig}, 
-    utils::{gmp_messages::{BridgeToken as BridgeTokenMsg, OutboundResponse}, token_actions}
+    state::{Config, LocalTokenConfig,RemoteBridgeConfig, RemoteTokenConfig,SenderConfig, TokenMode, TokenVault}, 
+    utils::{decimals, gmp_messages::{BridgeToken as BridgeTokenMsg, OutboundResponse}, token_actions}
 };
 
 use mailbox::{
@@ -58,6 +58,7 @@ pub struct Deposit<'info> {
     )]
     pub local_token_config: Account<'info, LocalTokenConfig>,
     #[account(
+        mut,
         seeds = [REMOTE_TOKEN_CONFIG_SEED, mint.key().as_ref(), &outbound_message_path.destination_chain_id],
         constraint = remote_token_config.direction & OUTBOUND_DIRECTION != 0 @ BridgeError::OutboundDirectionDisabled,
         bump
@@ -73,23 +74,47 @@ pub struct Deposit<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub mailbox_config: UncheckedAccount<'info>,
+    /// CHECK: The event authority of the mailbox program, which emits its events through a self-CPI
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = mailbox.key())]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
 
     pub outbound_message_path: Account<'info, OutboundMessagePath>,
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub outbound_message: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
-    #[account()]
+    #[account(mut)]
+    pub outbound_message_record: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
     pub mailbox_sender_config: UncheckedAccount<'info>,
 
     #[account(mut)]
     pub treasury: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program
+    pub mailbox_sender_fee_schedule: Option<UncheckedAccount<'info>>,
+    /// Provided to pay the mailbox fee in the bridged token instead of lamports.
+    /// CHECK: This will be verified by the mailbox program
+    pub mailbox_fee_token: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program, owned by the fee payer
+    #[account(mut)]
+    pub fee_payer_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub mailbox_treasury_token_account: Option<UncheckedAccount<'info>>,
 
     pub system_program: Program<'info, System>,
+
+    /// Required for lock and release tokens, the vault accounting for the locked tokens.
+    #[account(mut, constraint = token_vault.mint == mint.key() @ BridgeError::InvalidTokenVault)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+    /// Required for lock and release tokens, the token account of the vault.
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 }
 
-pub fn deposit(
-    ctx: Context<Deposit>,
+pub fn deposit<'info>(
+    ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
     sender: [u8; 32],
     recipient: [u8; 32],
     caller: Option<[u8; 32]>,
@@ -98,13 +123,54 @@ pub fn deposit(
 ) -> Result<OutboundResponse> {
 
     require!(amount > 0, BridgeError::ZeroAmount);
-    token_actions::execute_burn(
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.sender_token_account.to_account_info(), // Use payer or another TokenAccount here
+    let remote_amount = decimals::to_remote_amount(
         amount,
-        ctx.accounts.mint.to_account_info(),
-        ctx.accounts.sender.to_account_info(),
+        ctx.accounts.mint.decimals,
+        ctx.accounts.remote_token_config.decimals,
     )?;
+    ctx.accounts
+        .remote_token_config
+        .outbound_rate_limit
+        .consume::<Clock>(amount)?;
+    match ctx.accounts.local_token_config.mode {
+        TokenMode::BurnMint => token_actions::execute_burn(
+            ctx.accounts.token_program.to_account_info(),
+            ctx.accounts.sender_token_account.to_account_info(), // Use payer or another TokenAccount here
+            amount,
+            ctx.accounts.mint.to_account_info(),
+            ctx.accounts.sender.to_account_info(),
+        )?,
+        TokenMode::LockRelease => {
+            let (Some(token_vault), Some(vault_token_account)) =
+                (ctx.accounts.token_vault.as_mut(), ctx.accounts.vault_token_account.as_ref())
+            else {
+                return err!(BridgeError::MissingTokenVault);
+            };
+            token_actions::check_vault_token_account(
+                ctx.accounts.token_program.key(),
+                ctx.accounts.mint.key(),
+                token_vault.key(),
+                vault_token_account.key(),
+            )?;
+            token_actions::check_no_transfer_fee(&ctx.accounts.mint.to_account_info())?;
+            // the transfer hook accounts of the token are shared with the fee paid in it
+            token_actions::execute_transfer(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.sender_token_account.to_account_info(),
+                vault_token_account.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.sender.to_account_info(),
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.mint.decimals,
+                &[],
+            )?;
+            token_vault.locked_amount = token_vault
+                .locked_amount
+                .checked_add(amount)
+                .ok_or(BridgeError::LockedAmountOverflow)?;
+        }
+    }
 
     let dest_token_addr = ctx.accounts.remote_token_config.token;
 
@@ -112,11 +178,12 @@ pub fn deposit(
         destination_token_address: dest_token_addr,
         sender: sender,
         recipient: recipient,
-        amount: amount,
+        amount: remote_amount,
         optional_message: message,
     };
     let signer_seeds: &[&[&[u8]]] = &[&[constants::CONFIG_SEED, &[ctx.bumps.config]]];
     let msg_body = message.to_gmp_body();
+    let pay_fee_in_token = ctx.accounts.mailbox_fee_token.is_some();
 
     let result = send_message(
         CpiContext::new_with_signer(
@@ -128,17 +195,46 @@ pub fn deposit(
                 sender_authority: ctx.accounts.config.to_account_info(),
                 config: ctx.accounts.mailbox_config.to_account_info(),
                 outbound_message_path: ctx.accounts.outbound_message_path.to_account_info(),
+                message_buffer: None,
                 outbound_message: ctx.accounts.outbound_message.to_account_info(),
+                outbound_message_record: ctx.accounts.outbound_message_record.to_account_info(),
                 treasury: match &ctx.accounts.treasury {
                     Some(a) => Some(a.to_account_info()),
                     None => None,
                 },
-                sender_config: Some(ctx.accounts.mailbox_sender_config.to_account_info()),
+                sender_config: ctx.accounts.mailbox_sender_config.to_account_info(),
+                sender_fee_schedule: ctx
+                    .accounts
+                    .mailbox_sender_fee_schedule
+                    .as_ref()
+                    .map(|s| s.to_account_info()),
+                fee_token: ctx
+                    .accounts
+                    .mailbox_fee_token
+                    .as_ref()
+                    .map(|f| f.to_account_info()),
+                fee_mint: pay_fee_in_token.then(|| ctx.accounts.mint.to_account_info()),
+                fee_payer_token_account: ctx
+                    .accounts
+                    .fee_payer_token_account
+                    .as_ref()
+                    .map(|a| a.to_account_info()),
+                treasury_token_account: ctx
+                    .accounts
+                    .mailbox_treasury_token_account
+                    .as_ref()
+                    .map(|t| t.to_account_info()),
+                fee_token_program: pay_fee_in_token
+                    .then(|| ctx.accounts.token_program.to_account_info()),
                 system_program: ctx.accounts.system_program.to_account_info(),
+                event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
+                program: ctx.accounts.mailbox.to_account_info(),
             },
             signer_seeds,
-        ),
-        msg_body, ctx.accounts.remote_bridge_config.bridge, caller, FEE_ADJUSTMET_BASE - ctx.accounts.sender_config.fee_discount,
+        )
+        // remaining accounts carry the transfer hook accounts of the token, if any
+        .with_remaining_accounts(ctx.remaining_accounts.to_vec()),
+        msg_body, ctx.accounts.remote_bridge_config.bridge, caller, FEE_ADJUSTMET_BASE - ctx.accounts.sender_config.fee_discount, None, false,
     )?;
 
     let send_result = result.get();
@@ -6,17 +6,21 @@ use mailbox::{
     constants::{INBOUND_MESSAGE_PATH_SEED, MESSAGE_SEED},
     state::{InboundMessagePath, MessageV1Info},
 };
+use proof_of_reserves::utils::reserves;
 
 use crate::{
     constants::{
         CONFIG_SEED, INBOUND_DIRECTION, LOCAL_TOKEN_CONFIG_SEED, MESSAGE_HANDLED_SEED,
-        REMOTE_BRIDGE_CONFIG_SEED, REMOTE_TOKEN_CONFIG_SEED,
+        REMOTE_BRIDGE_CONFIG_SEED, REMOTE_TOKEN_CONFIG_SEED, TOKEN_VAULT_SEED,
     },
     errors::BridgeError,
-    state::{Config, LocalTokenConfig, MessageHandled, RemoteBridgeConfig, RemoteTokenConfig},
+    state::{
+        Config, LocalTokenConfig, MessageHandled, RemoteBridgeConfig, RemoteTokenConfig, TokenMode,
+        TokenVault,
+    },
     utils::{
         gmp_messages::{InboundResponse, Mint},
-        token_actions,
+        decimals, token_actions,
     },
 };
 
@@ -100,12 +104,30 @@ pub struct GMPReceive<'info> {
     )]
     pub inbound_message_path: Account<'info, InboundMessagePath>,
     pub system_program: Program<'info, System>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
+    /// Required for lock and release tokens, the vault the tokens are released from.
+    #[account(mut, constraint = token_vault.mint == mint.key() @ BridgeError::InvalidTokenVault)]
+    pub token_vault: Option<Account<'info, TokenVault>>,
+    /// Required for lock and release tokens, the token account of the vault.
+    #[account(mut)]
+    pub vault_token_account: Option<InterfaceAccount<'info, TokenAccount>>,
 }
 
-pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<InboundResponse> {
+pub fn gmp_receive<'info>(
+    ctx: Context<'_, '_, '_, 'info, GMPReceive<'info>>,
+    payload_hash: [u8; 32],
+) -> Result<InboundResponse> {
     let message_info = &ctx.accounts.message_info;
 
-    let computed_payload_hash = sha256(&message_info.message.to_session_payload()).to_bytes();
+    let computed_payload_hash = sha256(&message_info.to_session_payload()).to_bytes();
     require!(
         computed_payload_hash == payload_hash,
         BridgeError::InvalidPayloadHash
@@ -129,24 +151,76 @@ pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<I
                 && ctx.accounts.recipient.owner.key().to_bytes() == mint_message.recipient),
         BridgeError::RecipientMismatch
     );
-    require!(mint_message.amount > 0, BridgeError::ZeroAmount);
+    let amount = decimals::to_local_amount(
+        mint_message.amount,
+        ctx.accounts.remote_token_config.decimals,
+        ctx.accounts.mint.decimals,
+    )?;
+    require!(amount > 0, BridgeError::ZeroAmount);
     ctx.accounts
         .remote_token_config
         .inbound_rate_limit
-        .consume::<Clock>(mint_message.amount)?;
+        .consume::<Clock>(amount)?;
 
-    token_actions::execute_mint(
-        ctx.accounts.token_program.to_account_info(),
-        ctx.accounts.recipient.to_account_info(),
-        mint_message.amount,
-        ctx.accounts.mint.to_account_info(),
-        ctx.accounts.mint_authority.to_account_info(),
-        ctx.accounts.token_authority.to_account_info(),
-        ctx.bumps.token_authority,
-    )?;
+    match ctx.accounts.local_token_config.mode {
+        TokenMode::BurnMint => {
+            reserves::check_reserves(
+                ctx.accounts.config.proof_of_reserves,
+                ctx.accounts.reserves_program.as_ref(),
+                ctx.accounts.reserves_config.as_ref(),
+                ctx.accounts.mint_reserves.as_ref(),
+                ctx.accounts.mint.to_account_info(),
+                amount,
+            )?;
+
+            token_actions::execute_mint(
+                ctx.accounts.token_program.to_account_info(),
+                ctx.accounts.recipient.to_account_info(),
+                amount,
+                ctx.accounts.mint.to_account_info(),
+                ctx.accounts.mint_authority.to_account_info(),
+                ctx.accounts.token_authority.to_account_info(),
+                ctx.bumps.token_authority,
+            )?;
+        }
+        // released tokens were locked before, so they do not change the supply the reserves back
+        TokenMode::LockRelease => {
+            let (Some(token_vault), Some(vault_token_account)) =
+                (ctx.accounts.token_vault.as_mut(), ctx.accounts.vault_token_account.as_ref())
+            else {
+                return err!(BridgeError::MissingTokenVault);
+            };
+            token_actions::check_vault_token_account(
+                ctx.accounts.token_program.key(),
+                ctx.accounts.mint.key(),
+                token_vault.key(),
+                vault_token_account.key(),
+            )?;
+            require!(
+                token_vault.locked_amount >= amount,
+                BridgeError::InsufficientLiquidity
+            );
+            token_actions::check_no_transfer_fee(&ctx.accounts.mint.to_account_info())?;
+            let mint = ctx.accounts.mint.key();
+            let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_VAULT_SEED, mint.as_ref(), &[token_vault.bump]]];
+            token_actions::execute_transfer(
+                ctx.accounts.token_program.to_account_info(),
+                vault_token_account.to_account_info(),
+                ctx.accounts.recipient.to_account_info(),
+                ctx.accounts.mint.to_account_info(),
+                token_vault.to_account_info(),
+                // remaining accounts carry the transfer hook accounts of the token, if any
+                ctx.remaining_accounts,
+                amount,
+                ctx.accounts.mint.decimals,
+                signer_seeds,
+            )?;
+            token_vault.locked_amount -= amount;
+        }
+    }
 
     Ok(InboundResponse {
-        amount: mint_message.amount,
+        amount,
         message: mint_message.message,
     })
 }
@@ -46,5 +46,6 @@ pub fn initialize(
     config.pending_admin = Pubkey::default();
     config.paused = false;
     config.mailbox = mailbox;
+    config.proof_of_reserves = None;
     Ok(())
 }
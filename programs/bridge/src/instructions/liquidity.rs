@@ -0,0 +1,174 @@
+use anchor_lang::prelude::*;
+use anchor_spl::associated_token::AssociatedToken;
+use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+
+use crate::{
+    constants::{ACCOUNT_ROLES_SEED, CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED, TOKEN_VAULT_SEED},
+    errors::BridgeError,
+    events::{LiquidityAdded, LiquidityRemoved},
+    state::{AccountRole, AccountRoles, Config, LocalTokenConfig, TokenMode, TokenVault},
+    utils::token_actions,
+};
+
+#[derive(Accounts)]
+pub struct AddLiquidity<'info> {
+    #[account(mut)]
+    pub rebalancer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = account_roles.has_role(AccountRole::Rebalancer) @ BridgeError::Unauthorized,
+        seeds = [ACCOUNT_ROLES_SEED, rebalancer.key().as_ref()],
+        bump
+    )]
+    pub account_roles: Account<'info, AccountRoles>,
+    #[account(
+        seeds = [LOCAL_TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        constraint = local_token_config.mode == TokenMode::LockRelease @ BridgeError::NotLockReleaseToken,
+        bump = local_token_config.bump
+    )]
+    pub local_token_config: Account<'info, LocalTokenConfig>,
+    #[account(address = local_token_config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        init_if_needed,
+        payer = rebalancer,
+        space = 8 + TokenVault::INIT_SPACE,
+        seeds = [TOKEN_VAULT_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        init_if_needed,
+        payer = rebalancer,
+        associated_token::mint = mint,
+        associated_token::authority = token_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = rebalancer,
+        token::token_program = token_program,
+    )]
+    pub rebalancer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub associated_token_program: Program<'info, AssociatedToken>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+pub struct RemoveLiquidity<'info> {
+    #[account(mut)]
+    pub rebalancer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        constraint = account_roles.has_role(AccountRole::Rebalancer) @ BridgeError::Unauthorized,
+        seeds = [ACCOUNT_ROLES_SEED, rebalancer.key().as_ref()],
+        bump
+    )]
+    pub account_roles: Account<'info, AccountRoles>,
+    #[account(
+        seeds = [LOCAL_TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        constraint = local_token_config.mode == TokenMode::LockRelease @ BridgeError::NotLockReleaseToken,
+        bump = local_token_config.bump
+    )]
+    pub local_token_config: Account<'info, LocalTokenConfig>,
+    #[account(address = local_token_config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(
+        mut,
+        seeds = [TOKEN_VAULT_SEED, mint.key().as_ref()],
+        bump = token_vault.bump
+    )]
+    pub token_vault: Account<'info, TokenVault>,
+    #[account(
+        mut,
+        associated_token::mint = mint,
+        associated_token::authority = token_vault,
+        associated_token::token_program = token_program,
+    )]
+    pub vault_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        mut,
+        token::mint = mint,
+        token::authority = rebalancer,
+        token::token_program = token_program,
+    )]
+    pub rebalancer_token_account: InterfaceAccount<'info, TokenAccount>,
+    pub token_program: Interface<'info, TokenInterface>,
+}
+
+/// Adds tokens of the rebalancer to the vault of a lock-and-release token, to be released
+/// to the recipients of inbound transfers.
+pub fn add_liquidity<'info>(
+    ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, BridgeError::ZeroAmount);
+    token_actions::check_no_transfer_fee(&ctx.accounts.mint.to_account_info())?;
+    token_actions::execute_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.rebalancer_token_account.to_account_info(),
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.rebalancer.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+        &[],
+    )?;
+
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.bump = ctx.bumps.token_vault;
+    token_vault.mint = ctx.accounts.mint.key();
+    token_vault.locked_amount = token_vault
+        .locked_amount
+        .checked_add(amount)
+        .ok_or(BridgeError::LockedAmountOverflow)?;
+    emit!(LiquidityAdded {
+        mint: token_vault.mint,
+        rebalancer: ctx.accounts.rebalancer.key(),
+        amount,
+        locked_amount: token_vault.locked_amount,
+    });
+    Ok(())
+}
+
+/// Removes tokens from the vault of a lock-and-release token to the rebalancer, to be moved
+/// to the chains where inbound transfers need them.
+pub fn remove_liquidity<'info>(
+    ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+    amount: u64,
+) -> Result<()> {
+    require!(amount > 0, BridgeError::ZeroAmount);
+    require!(
+        ctx.accounts.token_vault.locked_amount >= amount,
+        BridgeError::InsufficientLiquidity
+    );
+    let mint = ctx.accounts.mint.key();
+    let signer_seeds: &[&[&[u8]]] = &[&[TOKEN_VAULT_SEED, mint.as_ref(), &[ctx.accounts.token_vault.bump]]];
+    token_actions::execute_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.vault_token_account.to_account_info(),
+        ctx.accounts.rebalancer_token_account.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.token_vault.to_account_info(),
+        ctx.remaining_accounts,
+        amount,
+        ctx.accounts.mint.decimals,
+        signer_seeds,
+    )?;
+
+    let token_vault = &mut ctx.accounts.token_vault;
+    token_vault.locked_amount -= amount;
+    emit!(LiquidityRemoved {
+        mint,
+        rebalancer: ctx.accounts.rebalancer.key(),
+        amount,
+        locked_amount: token_vault.locked_amount,
+    });
+    Ok(())
+}
@@ -0,0 +1,139 @@
+//! Migrates accounts created before fields were appended to their layout.
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED, REMOTE_TOKEN_CONFIG_SEED},
+    errors::BridgeError,
+    state::{Config, LocalTokenConfig, RemoteTokenConfig},
+};
+
+#[derive(Accounts)]
+pub struct MigrateLocalTokenConfig<'info> {
+    #[account(mut, address = config.admin @ BridgeError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [LOCAL_TOKEN_CONFIG_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub local_token_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Legacy local token configs could only burn and mint, which is the mode the zeroed appended
+/// field decodes to.
+pub fn migrate_local_token_config(ctx: Context<MigrateLocalTokenConfig>) -> Result<()> {
+    let local_token_config = ctx.accounts.local_token_config.to_account_info();
+    check_legacy_layout(
+        &local_token_config,
+        LocalTokenConfig::DISCRIMINATOR,
+        LocalTokenConfig::LEGACY_SPACE,
+    )?;
+    grow_account(
+        &local_token_config,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + LocalTokenConfig::INIT_SPACE,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(chain_id: [u8; 32])]
+pub struct MigrateRemoteTokenConfig<'info> {
+    #[account(mut, address = config.admin @ BridgeError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [REMOTE_TOKEN_CONFIG_SEED, mint.key().as_ref(), &chain_id],
+        bump
+    )]
+    pub remote_token_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// The outbound rate limit starts out disabled. Legacy configs moved amounts unscaled, so the
+/// decimals of the remote token are set explicitly to those of the mint.
+pub fn migrate_remote_token_config(
+    ctx: Context<MigrateRemoteTokenConfig>,
+    _chain_id: [u8; 32],
+) -> Result<()> {
+    let remote_token_config = ctx.accounts.remote_token_config.to_account_info();
+    check_legacy_layout(
+        &remote_token_config,
+        RemoteTokenConfig::DISCRIMINATOR,
+        RemoteTokenConfig::LEGACY_SPACE,
+    )?;
+    grow_account(
+        &remote_token_config,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + RemoteTokenConfig::INIT_SPACE,
+    )?;
+
+    let mut migrated =
+        RemoteTokenConfig::try_deserialize(&mut &remote_token_config.try_borrow_data()?[..])?;
+    migrated.decimals = ctx.accounts.mint.decimals;
+    migrated.try_serialize(&mut &mut remote_token_config.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+fn check_legacy_layout(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    legacy_space: usize,
+) -> Result<()> {
+    let data = account.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require_eq!(
+        data.len(),
+        8 + legacy_space,
+        BridgeError::AccountAlreadyMigrated
+    );
+    Ok(())
+}
+
+/// Reallocates the account to its current size, zeroing the appended space, and tops up its rent.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let missing = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
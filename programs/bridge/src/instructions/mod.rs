@@ -9,6 +9,10 @@ pub use deposit::*;
 pub mod gmp_receive;
 pub use gmp_receive::*;pub mod grant_account_role;
 pub use grant_account_role::*;
+pub mod liquidity;
+pub use liquidity::*;
+pub mod migrate_accounts;
+pub use migrate_accounts::*;
 pub mod pause;
 pub use pause::*;
 pub mod revoke_account_roles;
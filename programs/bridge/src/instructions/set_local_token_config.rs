@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED};
+use crate::constants::{CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED, TOKEN_VAULT_SEED};
 use crate::{
     errors::BridgeError,
     events::LocalTokenConfigSet,
-    state::{Config, LocalTokenConfig},
+    state::{Config, LocalTokenConfig, TokenMode},
+    utils::token_actions,
 };
 
 #[derive(Accounts)]
@@ -22,17 +23,27 @@ pub struct SetLocalTokenConfig<'info> {
         bump
     )]
     pub local_token_config: Account<'info, LocalTokenConfig>,
+    /// CHECK: The seeds constraint ensures the correct address is passed. The vault may not exist,
+    /// it is only read when the mode changes.
+    #[account(seeds = [TOKEN_VAULT_SEED, &mint.to_bytes()], bump)]
+    pub token_vault: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
 pub fn set_local_token_config(
     ctx: Context<SetLocalTokenConfig>,
     mint: Pubkey, 
+    mode: TokenMode,
 ) -> Result<()> {
+    if ctx.accounts.local_token_config.mode != mode {
+        token_actions::check_vault_empty(&ctx.accounts.token_vault)?;
+    }
     ctx.accounts.local_token_config.bump = ctx.bumps.local_token_config;
     ctx.accounts.local_token_config.mint = mint;
+    ctx.accounts.local_token_config.mode = mode;
     emit!(LocalTokenConfigSet {
         mint,
+        mode,
     });
     Ok(())
 }
@@ -35,6 +35,25 @@ pub fn set_rate_limit(
         mint,
         chain_id,
         inbound_rate_limit,
+        outbound_rate_limit: None,
+    });
+    Ok(())
+}
+
+/// Sets the rate limit of the amount deposited towards the remote chain, leaving the inbound one as is.
+pub fn set_outbound_rate_limit(
+    ctx: Context<SetRateLimit>,
+    mint: Pubkey,
+    chain_id: [u8; 32],
+    outbound_rate_limit: RateLimitConfig,
+) -> Result<()> {
+    let remote_token_config = &mut ctx.accounts.remote_token_config;
+    remote_token_config.outbound_rate_limit.set_token_bucket_config(outbound_rate_limit.clone())?;
+    emit!(BridgeRateLimitConfigured {
+        mint,
+        chain_id,
+        inbound_rate_limit: remote_token_config.inbound_rate_limit_config()?,
+        outbound_rate_limit: Some(outbound_rate_limit),
     });
     Ok(())
 }
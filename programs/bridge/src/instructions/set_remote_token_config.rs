@@ -31,16 +31,19 @@ pub fn set_remote_token_config(
     chain_id: [u8; 32],
     token: [u8; 32], 
     direction: u8,
+    decimals: u8,
 ) -> Result<()> {
     ctx.accounts.remote_token_config.bump = ctx.bumps.remote_token_config;
     ctx.accounts.remote_token_config.chain_id = chain_id;
     ctx.accounts.remote_token_config.token = token;
     ctx.accounts.remote_token_config.direction = direction;
+    ctx.accounts.remote_token_config.decimals = decimals;
     emit!(RemoteTokenConfigSet {
         mint,
         chain_id,
         token,
-        direction
+        direction,
+        decimals
     });
     Ok(())
 }
@@ -1,10 +1,11 @@
 use anchor_lang::prelude::*;
 
-use crate::constants::{CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED};
+use crate::constants::{CONFIG_SEED, LOCAL_TOKEN_CONFIG_SEED, TOKEN_VAULT_SEED};
 use crate::{
     errors::BridgeError,
     events::LocalTokenConfigUnset,
     state::{Config, LocalTokenConfig},
+    utils::token_actions,
 };
 
 #[derive(Accounts)]
@@ -21,10 +22,15 @@ pub struct UnsetLocalTokenConfig<'info> {
         bump = local_token_config.bump
     )]
     pub local_token_config: Account<'info, LocalTokenConfig>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the vault may not exist.
+    #[account(seeds = [TOKEN_VAULT_SEED, &mint.to_bytes()], bump)]
+    pub token_vault: UncheckedAccount<'info>,
     pub system_program: Program<'info, System>,
 }
 
-pub fn unset_local_token_config(_ctx: Context<UnsetLocalTokenConfig>, mint: Pubkey) -> Result<()> {
+/// The vault must hold no locked tokens, as a config set again for the mint could change its mode.
+pub fn unset_local_token_config(ctx: Context<UnsetLocalTokenConfig>, mint: Pubkey) -> Result<()> {
+    token_actions::check_vault_empty(&ctx.accounts.token_vault)?;
     emit!(LocalTokenConfigUnset { mint });
     Ok(())
 }
@@ -10,7 +10,7 @@ pub mod utils;
 
 use constants::OPTIONAL_MESSAGE_SIZE;
 use instructions::*;
-use state::{AccountRole, };
+use state::{AccountRole, TokenMode};
 use utils::gmp_messages::{InboundResponse, OutboundResponse};
 
 use base_token_pool::rate_limiter::RateLimitConfig;
@@ -51,8 +51,8 @@ pub mod bridge {
         instructions::revoke_account_roles(ctx, account)
     }
 
-    pub fn deposit(
-        ctx: Context<Deposit>,
+    pub fn deposit<'info>(
+        ctx: Context<'_, '_, '_, 'info, Deposit<'info>>,
         sender: [u8; 32],
         recipient: [u8; 32],
         caller: Option<[u8; 32]>,
@@ -62,7 +62,10 @@ pub mod bridge {
         instructions::deposit(ctx, sender, recipient, caller, amount, message)
     }
 
-    pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<InboundResponse> {
+    pub fn gmp_receive<'info>(
+        ctx: Context<'_, '_, '_, 'info, GMPReceive<'info>>,
+        payload_hash: [u8; 32],
+    ) -> Result<InboundResponse> {
         instructions::gmp_receive(ctx, payload_hash)
     }
 
@@ -82,6 +85,13 @@ pub mod bridge {
         instructions::unpause(ctx)
     }
 
+    pub fn set_proof_of_reserves(
+        ctx: Context<Admin>,
+        proof_of_reserves: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_proof_of_reserves(ctx, proof_of_reserves)
+    }
+
     pub fn set_sender_config(
         ctx: Context<SetSenderConfig>,
         sender_program: Pubkey,
@@ -98,22 +108,38 @@ pub mod bridge {
     pub fn set_local_token_config(
     ctx: Context<SetLocalTokenConfig>,
     mint: Pubkey, 
+    mode: TokenMode,
     ) -> Result<()> {
-        instructions::set_local_token_config(ctx, mint)
+        instructions::set_local_token_config(ctx, mint, mode)
     }
 
     pub fn unset_local_token_config(ctx: Context<UnsetLocalTokenConfig>, mint: Pubkey) -> Result<()> {
         instructions::unset_local_token_config(ctx, mint)
     }
 
+    pub fn add_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, AddLiquidity<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::add_liquidity(ctx, amount)
+    }
+
+    pub fn remove_liquidity<'info>(
+        ctx: Context<'_, '_, '_, 'info, RemoveLiquidity<'info>>,
+        amount: u64,
+    ) -> Result<()> {
+        instructions::remove_liquidity(ctx, amount)
+    }
+
     pub fn set_remote_token_config(
     ctx: Context<SetRemoteTokenConfig>,
     mint: Pubkey,
     chain_id: [u8; 32],
     token: [u8; 32], 
     direction: u8,
+    decimals: u8,
     ) -> Result<()> {
-        instructions::set_remote_token_config(ctx, mint, chain_id, token, direction)
+        instructions::set_remote_token_config(ctx, mint, chain_id, token, direction, decimals)
     }
     
     pub fn unset_remote_token_config(ctx: Context<UnsetRemoteTokenConfig>, mint: Pubkey, chain_id: [u8; 32]) -> Result<()> {
@@ -140,4 +166,24 @@ pub mod bridge {
     ) -> Result<()> {
         instructions::set_rate_limit(ctx, mint, chain_id, inbound_rate_limit)
     }
+
+    pub fn set_outbound_rate_limit(
+    ctx: Context<SetRateLimit>,
+    mint: Pubkey,
+    chain_id: [u8; 32],
+    outbound_rate_limit: RateLimitConfig,
+    ) -> Result<()> {
+        instructions::set_outbound_rate_limit(ctx, mint, chain_id, outbound_rate_limit)
+    }
+
+    pub fn migrate_local_token_config(ctx: Context<MigrateLocalTokenConfig>) -> Result<()> {
+        instructions::migrate_local_token_config(ctx)
+    }
+
+    pub fn migrate_remote_token_config(
+    ctx: Context<MigrateRemoteTokenConfig>,
+    chain_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_remote_token_config(ctx, chain_id)
+    }
 }
\ No newline at end of file
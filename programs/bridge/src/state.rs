@@ -1,6 +1,6 @@
 use anchor_lang::prelude::*;
 
-use base_token_pool::rate_limiter::RateLimitTokenBucket;
+use base_token_pool::rate_limiter::{RateLimitConfig, RateLimitTokenBucket};
 // todo: optimize by saving bumps for accounts used more often
 
 #[account]
@@ -15,12 +15,16 @@ pub struct Config {
 
     // GMP Mailbox
     pub mailbox: Pubkey,
+
+    /// When Some, mints are checked against the reserves attested to this program.
+    pub proof_of_reserves: Option<Pubkey>,
 }
 
 #[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
 pub enum AccountRole {
     Pauser,
-    None // a dummy case as it looks like anchor cannot serialize/deserialize if there is only one case
+    None, // a dummy case as it looks like anchor cannot serialize/deserialize if there is only one case
+    Rebalancer,
 }
 
 #[account]
@@ -60,11 +64,37 @@ pub struct RemoteBridgeConfig {
     pub bridge: [u8; 32],
 }
 
+/// How the bridge moves a local token: burning on deposit and minting on receive, which requires
+/// the mint authority, or locking into and releasing from a vault for tokens it cannot mint.
+#[derive(Clone, Copy, Default, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
+pub enum TokenMode {
+    #[default]
+    BurnMint,
+    LockRelease,
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct LocalTokenConfig {
     pub bump: u8,
     pub mint: Pubkey, 
+    pub mode: TokenMode,
+}
+
+impl LocalTokenConfig {
+    /// The space of the account before the mode was appended.
+    pub const LEGACY_SPACE: usize = 1 + 32;
+}
+
+/// The vault of a lock-and-release token. Its associated token account holds the tokens,
+/// created along with the vault by the first liquidity added by a rebalancer.
+#[account]
+#[derive(InitSpace)]
+pub struct TokenVault {
+    pub bump: u8,
+    pub mint: Pubkey,
+    // Tokens locked by deposits or added by rebalancers, and not yet released or removed
+    pub locked_amount: u64,
 }
 
 #[account]
@@ -75,4 +105,20 @@ pub struct RemoteTokenConfig {
     pub token: [u8; 32], 
     pub direction: u8,
     pub inbound_rate_limit: RateLimitTokenBucket,
+    // Caps the amount deposited towards the remote chain
+    pub outbound_rate_limit: RateLimitTokenBucket,
+    // Decimals of the remote token, amounts are scaled between them and the local mint decimals
+    pub decimals: u8,
+}
+
+impl RemoteTokenConfig {
+    /// The space of the account before the outbound rate limit and the decimals were appended.
+    pub const LEGACY_SPACE: usize = 1 + 32 + 32 + 1 + RateLimitTokenBucket::INIT_SPACE;
+
+    /// The config of the inbound rate limit. The bucket keeps its config private, so it is read
+    /// from the serialized bucket, where it follows the tokens and the last update timestamp.
+    pub fn inbound_rate_limit_config(&self) -> Result<RateLimitConfig> {
+        let bucket = self.inbound_rate_limit.try_to_vec()?;
+        Ok(RateLimitConfig::deserialize(&mut &bucket[8 + 8..])?)
+    }
 }
@@ -0,0 +1,70 @@
+use crate::errors::BridgeError;
+
+// Amounts travel in the decimals of the token on the destination chain, as a uint256 of which
+// the bridge uses the lower 128 bits: enough for any u64 amount scaled up by 19 decimals.
+
+fn pow10(exponent: u8) -> Result<u128, BridgeError> {
+    10u128
+        .checked_pow(exponent as u32)
+        .ok_or(BridgeError::InvalidAmountConversion)
+}
+
+/// Scales a local amount to the decimals of the remote token. Rejects amounts with a remainder
+/// below the precision of the remote token, which would be taken from the sender without being
+/// bridged.
+pub fn to_remote_amount(amount: u64, local_decimals: u8, remote_decimals: u8) -> Result<u128, BridgeError> {
+    let amount = amount as u128;
+    if remote_decimals >= local_decimals {
+        return amount
+            .checked_mul(pow10(remote_decimals - local_decimals)?)
+            .ok_or(BridgeError::InvalidAmountConversion);
+    }
+    let factor = pow10(local_decimals - remote_decimals)?;
+    let (remote_amount, dust) = (amount / factor, amount % factor);
+    if dust != 0 {
+        return Err(BridgeError::AmountHasDust);
+    }
+    Ok(remote_amount)
+}
+
+/// Scales a remote amount to the decimals of the local token. A remainder below the precision
+/// of the local token is truncated, as it has already left the remote chain.
+pub fn to_local_amount(amount: u128, remote_decimals: u8, local_decimals: u8) -> Result<u64, BridgeError> {
+    let amount = if local_decimals >= remote_decimals {
+        amount
+            .checked_mul(pow10(local_decimals - remote_decimals)?)
+            .ok_or(BridgeError::InvalidAmountConversion)?
+    } else {
+        // amounts are below 10^39, any larger divisor truncates them to zero
+        pow10(remote_decimals - local_decimals).map_or(0, |factor| amount / factor)
+    };
+    u64::try_from(amount).map_err(|_| BridgeError::InvalidAmountConversion)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_same_decimals() {
+        assert_eq!(to_remote_amount(12345, 8, 8).unwrap(), 12345);
+        assert_eq!(to_local_amount(12345, 8, 8).unwrap(), 12345);
+    }
+
+    #[test]
+    fn test_remote_with_more_decimals() {
+        assert_eq!(to_remote_amount(u64::MAX, 8, 18).unwrap(), u64::MAX as u128 * 10u128.pow(10));
+        assert_eq!(to_local_amount(123_456_789_012, 18, 8).unwrap(), 12);
+        assert_eq!(to_local_amount(u64::MAX as u128 * 10u128.pow(10), 18, 8).unwrap(), u64::MAX);
+        assert_eq!(to_local_amount(u128::MAX, 60, 0).unwrap(), 0);
+        assert!(matches!(to_remote_amount(1, 0, 39), Err(BridgeError::InvalidAmountConversion)));
+    }
+
+    #[test]
+    fn test_remote_with_less_decimals() {
+        assert_eq!(to_remote_amount(1_200, 8, 6).unwrap(), 12);
+        assert!(matches!(to_remote_amount(1_201, 8, 6), Err(BridgeError::AmountHasDust)));
+        assert_eq!(to_local_amount(12, 6, 8).unwrap(), 1_200);
+        assert!(matches!(to_local_amount(u64::MAX as u128, 6, 8), Err(BridgeError::InvalidAmountConversion)));
+    }
+}
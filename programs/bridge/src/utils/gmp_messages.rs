@@ -15,7 +15,8 @@ pub struct BridgeToken {
     pub destination_token_address: [u8; 32],
     pub sender: [u8; 32],
     pub recipient: [u8; 32],
-    pub amount: u64,
+    // in the decimals of the remote token
+    pub amount: u128,
     pub optional_message: Option<[u8; OPTIONAL_MESSAGE_SIZE]>,
 }
 
@@ -34,7 +35,7 @@ impl BridgeToken {
         message.extend_from_slice(&self.sender);
         message.extend_from_slice(&self.recipient);
         let mut amount_bytes = [0u8; 32];
-        amount_bytes[24..].copy_from_slice(&self.amount.to_be_bytes());
+        amount_bytes[16..].copy_from_slice(&self.amount.to_be_bytes());
         message.extend_from_slice(&amount_bytes);
         match self.optional_message {
             Some(msg) => {
@@ -52,7 +53,8 @@ pub struct Mint {
     pub token_address: [u8; 32],
     pub sender: [u8; 32],
     pub recipient: [u8; 32],
-    pub amount: u64,
+    // in the decimals of the token on the source chain
+    pub amount: u128,
     pub message: Option<[u8; OPTIONAL_MESSAGE_SIZE]>,
 }
 
@@ -85,7 +87,10 @@ impl Mint {
 
         let mut amount_bytes = [0u8; 32];
         reader.read_exact(&mut amount_bytes)?;
-        mint.amount = u64::from_be_bytes(amount_bytes[24..32].try_into().unwrap());
+        if amount_bytes[..16].iter().any(|b| *b != 0) {
+            return Err(BridgeError::InvalidAmountConversion);
+        }
+        mint.amount = u128::from_be_bytes(amount_bytes[16..32].try_into().unwrap());
         if message.len() > MINT_MESSAGE_MIN_LENGTH {
             let mut msg_bytes = [0u8; OPTIONAL_MESSAGE_SIZE];
             let remaining = cmp::min(message.len() - MINT_MESSAGE_MIN_LENGTH, OPTIONAL_MESSAGE_SIZE);
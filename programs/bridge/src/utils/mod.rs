@@ -1,2 +1,3 @@
 pub mod token_actions;
+pub mod decimals;
 pub mod gmp_messages;
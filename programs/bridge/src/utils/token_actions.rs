@@ -1,5 +1,8 @@
 use anchor_lang::prelude::*;
 use anchor_spl::{associated_token, token_interface};
+use spl_token_2022::extension::{
+    transfer_fee::TransferFeeConfig, BaseStateWithExtensions, StateWithExtensions,
+};
 
 pub fn execute_mint<'info>(
     token_program: AccountInfo<'info>,
@@ -46,6 +49,75 @@ pub fn execute_burn<'info>(
     )
 }
 
+/// Transfers tokens between token accounts with `transfer_checked`, forwarding any transfer hook
+/// accounts in `additional_accounts` to the token program. The authority signs through
+/// `signer_seeds` when it is a PDA of the bridge.
+#[allow(clippy::too_many_arguments)]
+pub fn execute_transfer<'info>(
+    token_program: AccountInfo<'info>,
+    from: AccountInfo<'info>,
+    to: AccountInfo<'info>,
+    mint: AccountInfo<'info>,
+    authority: AccountInfo<'info>,
+    additional_accounts: &[AccountInfo<'info>],
+    amount: u64,
+    decimals: u8,
+    signer_seeds: &[&[&[u8]]],
+) -> Result<()> {
+    Ok(spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from,
+        mint,
+        to,
+        authority,
+        additional_accounts,
+        amount,
+        decimals,
+        signer_seeds,
+    )?)
+}
+
+/// Checks the mint has no transfer fee extension. The vault accounts for the amounts moved in
+/// and out of it, which a fee withheld by the token program would make it overstate.
+pub fn check_no_transfer_fee(mint: &AccountInfo) -> Result<()> {
+    let data = mint.try_borrow_data()?;
+    let state = StateWithExtensions::<spl_token_2022::state::Mint>::unpack(&data)?;
+    require!(
+        state.get_extension::<TransferFeeConfig>().is_err(),
+        crate::errors::BridgeError::TransferFeeNotSupported
+    );
+    Ok(())
+}
+
+/// Checks the token vault, when it was created, holds no locked tokens. Switching the mode of a
+/// token away from lock and release would otherwise leave them out of reach of the rebalancers.
+pub fn check_vault_empty(token_vault: &AccountInfo) -> Result<()> {
+    if token_vault.data_is_empty() {
+        return Ok(());
+    }
+    let token_vault = crate::state::TokenVault::try_deserialize(&mut &token_vault.try_borrow_data()?[..])?;
+    require!(
+        token_vault.locked_amount == 0,
+        crate::errors::BridgeError::TokenVaultNotEmpty
+    );
+    Ok(())
+}
+
+/// Checks the vault token account is the associated token account of the token vault.
+pub fn check_vault_token_account(
+    token_program: Pubkey,
+    mint: Pubkey,
+    token_vault: Pubkey,
+    vault_token_account: Pubkey,
+) -> Result<()> {
+    require_keys_eq!(
+        vault_token_account,
+        get_token_account(token_program, mint, token_vault)?,
+        crate::errors::BridgeError::InvalidVaultTokenAccount
+    );
+    Ok(())
+}
+
 pub fn get_token_account<'info>(
     token_program: Pubkey,
     mint: Pubkey,
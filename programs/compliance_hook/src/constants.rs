@@ -0,0 +1,7 @@
+pub const CONFIG_SEED: &[u8] = b"compliance_hook_config";
+pub const ACCOUNT_ROLES_SEED: &[u8] = b"account_roles";
+pub const DENYLIST_SEED: &[u8] = b"denylist";
+pub const EXTRA_ACCOUNT_METAS_SEED: &[u8] = b"extra-account-metas";
+
+/// Offset of the owner field in an SPL token account.
+pub const TOKEN_ACCOUNT_OWNER_OFFSET: u8 = 32;
@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ComplianceHookError {
+    #[msg("Unauthorized function call")]
+    Unauthorized,
+    #[msg("Account role already granted")]
+    AccountRoleAlreadyGranted,
+    #[msg("Sender is denylisted")]
+    SenderDenylisted,
+    #[msg("Receiver is denylisted")]
+    ReceiverDenylisted,
+    #[msg("Invalid denylist entry account")]
+    InvalidDenylistEntry,
+    #[msg("Invalid extra account metas")]
+    InvalidExtraAccountMetas,
+    #[msg("Missing denylist entry account")]
+    MissingDenylistEntry,
+    #[msg("Account is denylisted")]
+    AccountDenylisted,
+}
@@ -0,0 +1,36 @@
+use anchor_lang::prelude::*;
+
+use crate::state::AccountRole;
+
+#[event]
+pub struct AccountRoleGranted {
+    pub account: Pubkey,
+    pub account_role: AccountRole,
+}
+
+#[event]
+pub struct AccountRolesRevoked {
+    pub account: Pubkey,
+}
+
+#[event]
+pub struct OwnershipTransferInitiated {
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct AccountDenylisted {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct AccountRemovedFromDenylist {
+    pub account: Pubkey,
+    pub authority: Pubkey,
+}
+
+#[event]
+pub struct ExtraAccountMetasInitialized {
+    pub mint: Pubkey,
+}
@@ -0,0 +1,89 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{ACCOUNT_ROLES_SEED, CONFIG_SEED, DENYLIST_SEED},
+    errors::ComplianceHookError,
+    events::{AccountDenylisted, AccountRemovedFromDenylist},
+    state::{AccountRole, AccountRoles, Config, DenylistEntry},
+};
+
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct AddToDenylist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// Required unless the authority is the admin.
+    #[account(seeds = [ACCOUNT_ROLES_SEED, authority.key().as_ref()], bump)]
+    pub account_roles: Option<Account<'info, AccountRoles>>,
+    #[account(
+        init,
+        payer = authority,
+        space = 8 + DenylistEntry::INIT_SPACE,
+        seeds = [DENYLIST_SEED, account.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+    pub system_program: Program<'info, System>,
+}
+
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct RemoveFromDenylist<'info> {
+    #[account(mut)]
+    pub authority: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// Required unless the authority is the admin.
+    #[account(seeds = [ACCOUNT_ROLES_SEED, authority.key().as_ref()], bump)]
+    pub account_roles: Option<Account<'info, AccountRoles>>,
+    #[account(
+        mut,
+        close = authority,
+        seeds = [DENYLIST_SEED, account.as_ref()],
+        bump
+    )]
+    pub denylist_entry: Account<'info, DenylistEntry>,
+}
+
+/// The denylist is managed by the admin and by accounts holding the Compliance role.
+fn check_compliance_authority(
+    authority: &Pubkey,
+    config: &Config,
+    account_roles: Option<&Account<AccountRoles>>,
+) -> Result<()> {
+    let is_compliance = account_roles.is_some_and(|roles| roles.has_role(AccountRole::Compliance));
+    require!(
+        *authority == config.admin || is_compliance,
+        ComplianceHookError::Unauthorized
+    );
+    Ok(())
+}
+
+pub fn add_to_denylist(ctx: Context<AddToDenylist>, account: Pubkey) -> Result<()> {
+    check_compliance_authority(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.config,
+        ctx.accounts.account_roles.as_ref(),
+    )?;
+    ctx.accounts.denylist_entry.account = account;
+    emit!(AccountDenylisted {
+        account,
+        authority: ctx.accounts.authority.key(),
+    });
+    Ok(())
+}
+
+pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>, account: Pubkey) -> Result<()> {
+    check_compliance_authority(
+        &ctx.accounts.authority.key(),
+        &ctx.accounts.config,
+        ctx.accounts.account_roles.as_ref(),
+    )?;
+    emit!(AccountRemovedFromDenylist {
+        account,
+        authority: ctx.accounts.authority.key(),
+    });
+    Ok(())
+}
@@ -0,0 +1,41 @@
+//! Transfer hook invoked by Token-2022 on every transfer of the hooked mint.
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    constants::{DENYLIST_SEED, EXTRA_ACCOUNT_METAS_SEED},
+    errors::ComplianceHookError,
+    state::DenylistEntry,
+};
+
+#[derive(Accounts)]
+pub struct Execute<'info> {
+    #[account(token::mint = mint)]
+    pub source_token: InterfaceAccount<'info, TokenAccount>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub destination_token: InterfaceAccount<'info, TokenAccount>,
+    /// CHECK: The transfer authority, validated by Token-2022.
+    pub owner: UncheckedAccount<'info>,
+    /// CHECK: The seeds constraint ensures the correct address is passed.
+    #[account(seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()], bump)]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    /// CHECK: Denylist entry of the source owner, may be uninitialized.
+    #[account(seeds = [DENYLIST_SEED, source_token.owner.as_ref()], bump)]
+    pub sender_denylist_entry: UncheckedAccount<'info>,
+    /// CHECK: Denylist entry of the destination owner, may be uninitialized.
+    #[account(seeds = [DENYLIST_SEED, destination_token.owner.as_ref()], bump)]
+    pub receiver_denylist_entry: UncheckedAccount<'info>,
+}
+
+pub fn execute(ctx: Context<Execute>, _amount: u64) -> Result<()> {
+    require!(
+        !DenylistEntry::is_denylisted(&ctx.accounts.sender_denylist_entry, &crate::ID),
+        ComplianceHookError::SenderDenylisted
+    );
+    require!(
+        !DenylistEntry::is_denylisted(&ctx.accounts.receiver_denylist_entry, &crate::ID),
+        ComplianceHookError::ReceiverDenylisted
+    );
+    Ok(())
+}
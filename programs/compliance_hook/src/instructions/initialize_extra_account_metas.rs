@@ -0,0 +1,77 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use spl_tlv_account_resolution::{
+    account::ExtraAccountMeta, seeds::Seed, state::ExtraAccountMetaList,
+};
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+use crate::{
+    constants::{CONFIG_SEED, DENYLIST_SEED, EXTRA_ACCOUNT_METAS_SEED, TOKEN_ACCOUNT_OWNER_OFFSET},
+    errors::ComplianceHookError,
+    events::ExtraAccountMetasInitialized,
+    state::Config,
+};
+
+/// Index of the source token account in the transfer hook execute instruction.
+const SOURCE_TOKEN_ACCOUNT_INDEX: u8 = 0;
+/// Index of the destination token account in the transfer hook execute instruction.
+const DESTINATION_TOKEN_ACCOUNT_INDEX: u8 = 2;
+
+#[derive(Accounts)]
+pub struct InitializeExtraAccountMetas<'info> {
+    #[account(mut, address = config.admin @ ComplianceHookError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: Initialized in the instruction body, the seeds constraint ensures the correct address.
+    #[account(
+        init,
+        payer = admin,
+        space = extra_account_metas()?.1,
+        seeds = [EXTRA_ACCOUNT_METAS_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub extra_account_meta_list: UncheckedAccount<'info>,
+    pub token_program: Interface<'info, TokenInterface>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Returns the extra accounts resolved by Token-2022 for every transfer: the denylist entries of
+/// the source and destination token account owners, along with the space they require.
+fn extra_account_metas() -> Result<(Vec<ExtraAccountMeta>, usize)> {
+    let owner_entry = |account_index: u8| {
+        ExtraAccountMeta::new_with_seeds(
+            &[
+                Seed::Literal {
+                    bytes: DENYLIST_SEED.to_vec(),
+                },
+                Seed::AccountData {
+                    account_index,
+                    data_index: TOKEN_ACCOUNT_OWNER_OFFSET,
+                    length: 32,
+                },
+            ],
+            false,
+            false,
+        )
+    };
+    let metas = vec![
+        owner_entry(SOURCE_TOKEN_ACCOUNT_INDEX)?,
+        owner_entry(DESTINATION_TOKEN_ACCOUNT_INDEX)?,
+    ];
+    let space = ExtraAccountMetaList::size_of(metas.len())?;
+    Ok((metas, space))
+}
+
+pub fn initialize_extra_account_metas(ctx: Context<InitializeExtraAccountMetas>) -> Result<()> {
+    let (metas, _) = extra_account_metas()?;
+    let mut data = ctx.accounts.extra_account_meta_list.try_borrow_mut_data()?;
+    ExtraAccountMetaList::init::<ExecuteInstruction>(&mut data, &metas)
+        .map_err(|_| ComplianceHookError::InvalidExtraAccountMetas)?;
+    emit!(ExtraAccountMetasInitialized {
+        mint: ctx.accounts.mint.key(),
+    });
+    Ok(())
+}
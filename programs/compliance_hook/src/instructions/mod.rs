@@ -0,0 +1,16 @@
+pub mod accept_ownership;
+pub use accept_ownership::*;
+pub mod admin;
+pub use admin::*;
+pub mod denylist;
+pub use denylist::*;
+pub mod execute;
+pub use execute::*;
+pub mod grant_account_role;
+pub use grant_account_role::*;
+pub mod initialize;
+pub use initialize::*;
+pub mod initialize_extra_account_metas;
+pub use initialize_extra_account_metas::*;
+pub mod revoke_account_roles;
+pub use revoke_account_roles::*;
@@ -0,0 +1,76 @@
+use anchor_lang::prelude::*;
+use spl_discriminator::SplDiscriminate;
+use spl_transfer_hook_interface::instruction::ExecuteInstruction;
+
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod security;
+pub mod state;
+
+use instructions::*;
+use state::AccountRole;
+
+#[cfg(feature = "mainnet")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "gastald")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "staging")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "bft")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(not(any(
+    feature = "mainnet",
+    feature = "gastald",
+    feature = "staging",
+    feature = "bft"
+)))]
+declare_id!("apPXyBjDiJ4y1j6v68EheLm4SYqiMJ5vtN5hb4Vjstv");
+
+#[program]
+pub mod compliance_hook {
+
+    use super::*;
+
+    pub fn initialize(ctx: Context<Initialize>, admin: Pubkey) -> Result<()> {
+        instructions::initialize(ctx, admin)
+    }
+
+    pub fn grant_account_role(
+        ctx: Context<GrantAccountRole>,
+        account: Pubkey,
+        account_role: AccountRole,
+    ) -> Result<()> {
+        instructions::grant_account_role(ctx, account, account_role)
+    }
+
+    pub fn revoke_account_roles(ctx: Context<RevokeAccountRoles>, account: Pubkey) -> Result<()> {
+        instructions::revoke_account_roles(ctx, account)
+    }
+
+    pub fn transfer_ownership(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_ownership(ctx, new_admin)
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        instructions::accept_ownership(ctx)
+    }
+
+    pub fn add_to_denylist(ctx: Context<AddToDenylist>, account: Pubkey) -> Result<()> {
+        instructions::add_to_denylist(ctx, account)
+    }
+
+    pub fn remove_from_denylist(ctx: Context<RemoveFromDenylist>, account: Pubkey) -> Result<()> {
+        instructions::remove_from_denylist(ctx, account)
+    }
+
+    pub fn initialize_extra_account_metas(ctx: Context<InitializeExtraAccountMetas>) -> Result<()> {
+        instructions::initialize_extra_account_metas(ctx)
+    }
+
+    #[instruction(discriminator = ExecuteInstruction::SPL_DISCRIMINATOR_SLICE)]
+    pub fn execute(ctx: Context<Execute>, amount: u64) -> Result<()> {
+        instructions::execute(ctx, amount)
+    }
+}
@@ -0,0 +1,78 @@
+use anchor_lang::prelude::*;
+
+use crate::{constants::DENYLIST_SEED, errors::ComplianceHookError};
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+}
+
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
+pub enum AccountRole {
+    Compliance,
+    None, // placeholder, anchor fails to (de)serialize single-variant enums
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AccountRoles {
+    #[max_len(3)]
+    pub roles: Vec<AccountRole>,
+}
+
+impl AccountRoles {
+    pub fn add_role(&mut self, role: AccountRole) {
+        if !self.roles.contains(&role) {
+            self.roles.push(role);
+        }
+    }
+
+    pub fn has_role(&self, role: AccountRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
+/// Marks an account as denylisted. The account is denylisted for as long as the PDA exists.
+#[account]
+#[derive(InitSpace)]
+pub struct DenylistEntry {
+    pub account: Pubkey,
+}
+
+impl DenylistEntry {
+    /// Returns the denylist entry PDA for `account` under the given compliance hook program.
+    pub fn address(program_id: &Pubkey, account: &Pubkey) -> Pubkey {
+        Pubkey::find_program_address(&[DENYLIST_SEED, account.as_ref()], program_id).0
+    }
+
+    /// Returns true if `entry` is an initialized denylist entry owned by `program_id`.
+    pub fn is_denylisted(entry: &AccountInfo, program_id: &Pubkey) -> bool {
+        entry.owner == program_id && !entry.data_is_empty()
+    }
+
+    /// Used by the programs minting and moving the token. When a compliance hook is configured,
+    /// ensures `account` is not denylisted. `entry` must then be the denylist entry PDA of
+    /// `account` under the compliance hook program, whether it is initialized or not.
+    pub fn ensure_not_denylisted(
+        compliance_hook: Option<Pubkey>,
+        account: &Pubkey,
+        entry: Option<&UncheckedAccount>,
+    ) -> Result<()> {
+        let Some(compliance_hook) = compliance_hook else {
+            return Ok(());
+        };
+        let entry = entry.ok_or(ComplianceHookError::MissingDenylistEntry)?;
+        require_keys_eq!(
+            entry.key(),
+            Self::address(&compliance_hook, account),
+            ComplianceHookError::InvalidDenylistEntry
+        );
+        require!(
+            !Self::is_denylisted(entry, &compliance_hook),
+            ComplianceHookError::AccountDenylisted
+        );
+        Ok(())
+    }
+}
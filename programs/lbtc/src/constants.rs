@@ -21,6 +21,7 @@ pub const NEW_VALSET_ACTION: u32 = 0x4aab1d6f;
 
 pub const TOKEN_AUTHORITY_SEED: &[u8] = b"token_authority";
 pub const CONFIG_SEED: &[u8] = b"lbtc_config";
+pub const ACCOUNT_ROLES_SEED: &[u8] = b"account_roles";
 pub const MIN_VALIDATOR_SET_SIZE: usize = 1;
 pub const MAX_VALIDATOR_SET_SIZE: usize = 102;
 pub const MINT_PAYLOAD_LEN: usize = 164;
@@ -31,3 +32,5 @@ pub const METADATA_SEED: [u8; 32] = [
 pub const VALIDATOR_PUBKEY_SIZE: usize = 64;
 pub const LBTC_DECIMALS: u8 = 8;
 pub const MAX_FEE: u64 = 100000;
+pub const DEFAULT_MAX_FEE_BPS: u16 = 100;
+pub const BASIS_POINTS_BASE: u64 = 10000;
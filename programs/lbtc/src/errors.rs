@@ -67,18 +67,18 @@ pub enum LBTCError {
     SignaturesIndicesMismatch,
     #[msg("Selected fee is too high")]
     FeeTooHigh,
-    #[msg("Claimer already exists")]
-    ClaimerExists,
-    #[msg("Pauser already exists")]
-    PauserExists,
-    #[msg("Claimer not found")]
-    ClaimerNotFound,
-    #[msg("Pauser not found")]
-    PauserNotFound,
+    #[msg("Account role already granted")]
+    AccountRoleAlreadyGranted,
+    #[msg("Config account already migrated")]
+    ConfigAlreadyMigrated,
+    #[msg("Missing or invalid account roles account")]
+    InvalidAccountRolesAccount,
     #[msg("Metadata finalized already")]
     MetadataFinalizedAlready,
     #[msg("Metadata not finalized")]
     MetadataNotFinalized,
     #[msg("Payload mismatch")]
     PayloadMismatch,
+    #[msg("Invalid fee model")]
+    InvalidFeeModel,
 }
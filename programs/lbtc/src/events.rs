@@ -1,5 +1,8 @@
 //! Defines all events for the Lombard Finance protocol.
-use crate::constants::{MINT_PAYLOAD_LEN, VALIDATOR_PUBKEY_SIZE};
+use crate::{
+    constants::{MINT_PAYLOAD_LEN, VALIDATOR_PUBKEY_SIZE},
+    state::{AccountRole, FeeBounds, FeeModel, MetadataField},
+};
 use anchor_lang::prelude::*;
 
 #[event]
@@ -14,12 +17,17 @@ pub struct BasculeEnabled {
 
 #[event]
 pub struct MintFeeSet {
-    pub mint_fee: u64,
+    pub mint_fee: FeeModel,
 }
 
 #[event]
 pub struct BurnCommissionSet {
-    pub burn_commission: u64,
+    pub burn_commission: FeeModel,
+}
+
+#[event]
+pub struct FeeBoundsSet {
+    pub fee_bounds: FeeBounds,
 }
 
 #[event]
@@ -38,23 +46,20 @@ pub struct TreasuryChanged {
 }
 
 #[event]
-pub struct ClaimerAdded {
-    pub claimer: Pubkey,
-}
-
-#[event]
-pub struct ClaimerRemoved {
-    pub claimer: Pubkey,
+pub struct AccountRoleGranted {
+    pub account: Pubkey,
+    pub account_role: AccountRole,
 }
 
 #[event]
-pub struct PauserAdded {
-    pub pauser: Pubkey,
+pub struct AccountRolesRevoked {
+    pub account: Pubkey,
 }
 
 #[event]
-pub struct PauserRemoved {
-    pub pauser: Pubkey,
+pub struct ConfigMigrated {
+    pub claimers: Vec<Pubkey>,
+    pub pausers: Vec<Pubkey>,
 }
 
 #[event]
@@ -77,6 +82,24 @@ pub struct UnstakeRequest {
     pub amount: u64,
 }
 
+#[event]
+pub struct RedeemFeeCharged {
+    pub from: Pubkey,
+    pub amount: u64,
+    pub fee: u64,
+    pub fee_model: FeeModel,
+}
+
+#[event]
+pub struct MintFeeCharged {
+    pub recipient: Pubkey,
+    pub payload_hash: [u8; 32],
+    pub amount: u64,
+    pub max_fee: u64,
+    pub fee: u64,
+    pub fee_model: FeeModel,
+}
+
 #[event]
 pub struct MintProofConsumed {
     pub recipient: Pubkey,
@@ -129,3 +152,31 @@ pub struct OwnershipTransferred {
 pub struct MintAuthorityUpdated {
     pub new_auth: Pubkey,
 }
+
+#[event]
+pub struct MetadataUpdated {
+    pub name: String,
+    pub symbol: String,
+    pub uri: String,
+}
+
+#[event]
+pub struct TokenMetadataFieldUpdated {
+    pub field: MetadataField,
+    pub value: String,
+}
+
+#[event]
+pub struct MetadataUpdateAuthorityChanged {
+    pub new_update_authority: Option<Pubkey>,
+}
+
+#[event]
+pub struct ComplianceHookSet {
+    pub compliance_hook: Option<Pubkey>,
+}
+
+#[event]
+pub struct ProofOfReservesSet {
+    pub proof_of_reserves: Option<Pubkey>,
+}
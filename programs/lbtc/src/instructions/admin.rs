@@ -1,12 +1,12 @@
 //! Collection of admin-privileged functionality.
 use crate::{
     constants,
-    errors::LBTCError,
     events::{
-        BasculeEnabled, BurnCommissionSet, ClaimerAdded, ClaimerRemoved, DustFeeRateSet,
-        OperatorSet, OwnershipTransferInitiated, PauserAdded, PauserRemoved, WithdrawalsEnabled,
+        BasculeEnabled, BurnCommissionSet, ComplianceHookSet, DustFeeRateSet, FeeBoundsSet, OperatorSet,
+        OwnershipTransferInitiated, ProofOfReservesSet, WithdrawalsEnabled,
     },
-    state::Config,
+    state::{Config, FeeBounds, FeeModel},
+    utils::fees,
 };
 use anchor_lang::prelude::*;
 
@@ -48,8 +48,20 @@ pub fn disable_bascule(ctx: Context<Admin>) -> Result<()> {
     Ok(())
 }
 
-pub fn set_burn_commission(ctx: Context<Admin>, commission: u64) -> Result<()> {
-    require!(commission <= constants::MAX_FEE, LBTCError::FeeTooHigh);
+pub fn set_compliance_hook(ctx: Context<Admin>, compliance_hook: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.compliance_hook = compliance_hook;
+    emit!(ComplianceHookSet { compliance_hook });
+    Ok(())
+}
+
+pub fn set_proof_of_reserves(ctx: Context<Admin>, proof_of_reserves: Option<Pubkey>) -> Result<()> {
+    ctx.accounts.config.proof_of_reserves = proof_of_reserves;
+    emit!(ProofOfReservesSet { proof_of_reserves });
+    Ok(())
+}
+
+pub fn set_burn_commission(ctx: Context<Admin>, commission: FeeModel) -> Result<()> {
+    fees::validate_fee_model(&commission, &ctx.accounts.config.fee_bounds)?;
     ctx.accounts.config.burn_commission = commission;
     emit!(BurnCommissionSet {
         burn_commission: commission
@@ -69,58 +81,13 @@ pub fn set_dust_fee_rate(ctx: Context<Admin>, rate: u64) -> Result<()> {
     Ok(())
 }
 
-pub fn add_claimer(ctx: Context<Admin>, claimer: Pubkey) -> Result<()> {
-    require!(
-        !ctx.accounts.config.claimers.iter().any(|c| *c == claimer),
-        LBTCError::ClaimerExists
-    );
-    ctx.accounts.config.claimers.push(claimer);
-    emit!(ClaimerAdded { claimer });
+/// Sets the bounds for the burn commission and mint fee. The currently configured fees must
+/// remain within the new bounds.
+pub fn set_fee_bounds(ctx: Context<Admin>, fee_bounds: FeeBounds) -> Result<()> {
+    fees::validate_fee_bounds(&fee_bounds)?;
+    fees::validate_fee_model(&ctx.accounts.config.burn_commission, &fee_bounds)?;
+    fees::validate_fee_model(&ctx.accounts.config.mint_fee, &fee_bounds)?;
+    ctx.accounts.config.fee_bounds = fee_bounds;
+    emit!(FeeBoundsSet { fee_bounds });
     Ok(())
 }
-
-pub fn remove_claimer(ctx: Context<Admin>, claimer: Pubkey) -> Result<()> {
-    require!(
-        remove_from_vector(&mut ctx.accounts.config.claimers, claimer),
-        LBTCError::ClaimerNotFound
-    );
-    emit!(ClaimerRemoved { claimer });
-    Ok(())
-}
-
-pub fn add_pauser(ctx: Context<Admin>, pauser: Pubkey) -> Result<()> {
-    require!(
-        !ctx.accounts.config.pausers.iter().any(|p| *p == pauser),
-        LBTCError::PauserExists
-    );
-    ctx.accounts.config.pausers.push(pauser);
-    emit!(PauserAdded { pauser });
-    Ok(())
-}
-
-pub fn remove_pauser(ctx: Context<Admin>, pauser: Pubkey) -> Result<()> {
-    require!(
-        remove_from_vector(&mut ctx.accounts.config.pausers, pauser),
-        LBTCError::PauserNotFound
-    );
-    emit!(PauserRemoved { pauser });
-    Ok(())
-}
-
-fn remove_from_vector(v: &mut Vec<Pubkey>, to_remove: Pubkey) -> bool {
-    let mut found = false;
-    let mut index = 0;
-    for (i, p) in v.iter().enumerate() {
-        if *p == to_remove {
-            found = true;
-            index = i;
-            break;
-        }
-    }
-
-    if found {
-        v.swap_remove(index);
-    }
-
-    found
-}
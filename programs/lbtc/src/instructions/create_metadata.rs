@@ -1,4 +1,5 @@
-//! Allows admin to set token metadata.
+//! Allows admin to set token metadata. The token authority PDA becomes the update authority, so
+//! later changes go through `update_metadata`.
 use crate::{constants, state::Config};
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenInterface};
@@ -55,6 +56,7 @@ pub fn create_metadata(ctx: Context<CreateMetadata>) -> Result<()> {
     let metadata = ctx.accounts.metadata_pda.to_account_info();
     let mint = ctx.accounts.mint.to_account_info();
     let authority = ctx.accounts.mint_authority.to_account_info();
+    let token_authority = ctx.accounts.token_authority.to_account_info();
     let payer = ctx.accounts.payer.to_account_info();
     let spl_token_program = ctx.accounts.token_program.to_account_info();
     let system_program = ctx.accounts.system_program.to_account_info();
@@ -68,7 +70,7 @@ pub fn create_metadata(ctx: Context<CreateMetadata>) -> Result<()> {
             mint: (&mint, false),
             authority: &authority,
             payer: &payer,
-            update_authority: (&payer, true),
+            update_authority: (&token_authority, true),
             spl_token_program: Some(&spl_token_program),
             system_program: &system_program,
             sysvar_instructions: &sysvar_instructions,
@@ -1,5 +1,9 @@
 //! Initializes the LBTC program, setting all initial values.
-use crate::{constants, errors::LBTCError, state::Config};
+use crate::{
+    constants,
+    state::{Config, FeeBounds, FeeModel},
+    utils::fees,
+};
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::bpf_loader_upgradeable;
 use anchor_spl::token_interface::{Mint, TokenAccount};
@@ -38,17 +42,19 @@ pub struct Initialize<'info> {
 pub fn initialize(
     ctx: Context<Initialize>,
     admin: Pubkey,
-    burn_commission: u64,
+    burn_commission: FeeModel,
     dust_fee_rate: u64,
-    mint_fee: u64,
+    mint_fee: FeeModel,
 ) -> Result<()> {
-    require!(burn_commission <= constants::MAX_FEE, LBTCError::FeeTooHigh);
-    require!(mint_fee <= constants::MAX_FEE, LBTCError::FeeTooHigh);
+    let fee_bounds = FeeBounds::default();
+    fees::validate_fee_model(&burn_commission, &fee_bounds)?;
+    fees::validate_fee_model(&mint_fee, &fee_bounds)?;
     ctx.accounts.config.admin = admin;
     ctx.accounts.config.mint = ctx.accounts.mint.key();
     ctx.accounts.config.treasury = ctx.accounts.treasury.key();
     ctx.accounts.config.burn_commission = burn_commission;
     ctx.accounts.config.dust_fee_rate = dust_fee_rate;
     ctx.accounts.config.mint_fee = mint_fee;
+    ctx.accounts.config.fee_bounds = fee_bounds;
     Ok(())
 }
@@ -0,0 +1,113 @@
+//! Migrates the config account from the legacy layout, moving claimers and pausers into
+//! per-account roles PDAs and shrinking the config account.
+//!
+//! The roles PDAs must be passed as remaining accounts, one per distinct account holding a legacy
+//! role, ordered by first appearance in the claimers list followed by the pausers list.
+use crate::{
+    constants::{ACCOUNT_ROLES_SEED, CONFIG_SEED},
+    errors::LBTCError,
+    events::ConfigMigrated,
+    state::{AccountRole, AccountRoles, Config, LegacyConfig},
+};
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, CreateAccount},
+};
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    /// CHECK: The seeds constraint ensures the correct address is passed. The account can't be
+    /// decoded as `Config` yet, so the legacy layout and the admin are checked in the handler.
+    #[account(mut, owner = crate::ID, seeds = [CONFIG_SEED], bump)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_config<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, MigrateConfig<'info>>,
+) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    let legacy = {
+        let data = config_info.try_borrow_data()?;
+        require_eq!(
+            data.len(),
+            8 + LegacyConfig::INIT_SPACE,
+            LBTCError::ConfigAlreadyMigrated
+        );
+        require!(
+            data[..8] == *Config::DISCRIMINATOR,
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        LegacyConfig::deserialize(&mut &data[8..])?
+    };
+    require_keys_eq!(
+        legacy.admin,
+        ctx.accounts.payer.key(),
+        LBTCError::Unauthorized
+    );
+
+    // An account can be both a claimer and a pauser, so roles are grouped per account first.
+    let mut holders: Vec<(Pubkey, AccountRoles)> = vec![];
+    for (accounts, role) in [
+        (&legacy.claimers, AccountRole::Claimer),
+        (&legacy.pausers, AccountRole::Pauser),
+    ] {
+        for account in accounts {
+            match holders.iter_mut().find(|(a, _)| a == account) {
+                Some((_, roles)) => roles.add_role(role),
+                None => holders.push((*account, AccountRoles { roles: vec![role] })),
+            }
+        }
+    }
+
+    require_eq!(
+        ctx.remaining_accounts.len(),
+        holders.len(),
+        LBTCError::InvalidAccountRolesAccount
+    );
+    let space = 8 + AccountRoles::INIT_SPACE;
+    let lamports = Rent::get()?.minimum_balance(space);
+    for ((account, roles), roles_info) in holders.iter().zip(ctx.remaining_accounts) {
+        let (expected, bump) =
+            Pubkey::find_program_address(&[ACCOUNT_ROLES_SEED, account.as_ref()], ctx.program_id);
+        require_keys_eq!(
+            roles_info.key(),
+            expected,
+            LBTCError::InvalidAccountRolesAccount
+        );
+        system_program::create_account(
+            CpiContext::new_with_signer(
+                ctx.accounts.system_program.to_account_info(),
+                CreateAccount {
+                    from: ctx.accounts.payer.to_account_info(),
+                    to: roles_info.clone(),
+                },
+                &[&[ACCOUNT_ROLES_SEED, account.as_ref(), &[bump]]],
+            ),
+            lamports,
+            space as u64,
+            ctx.program_id,
+        )?;
+        roles.try_serialize(&mut &mut roles_info.try_borrow_mut_data()?[..])?;
+    }
+
+    emit!(ConfigMigrated {
+        claimers: legacy.claimers.clone(),
+        pausers: legacy.pausers.clone(),
+    });
+
+    let config = Config::from(legacy);
+    config.try_serialize(&mut &mut config_info.try_borrow_mut_data()?[..])?;
+
+    // Shrink the account and return the freed rent to the admin.
+    let new_len = 8 + Config::INIT_SPACE;
+    config_info.realloc(new_len, false)?;
+    let excess = config_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(new_len));
+    **config_info.try_borrow_mut_lamports()? -= excess;
+    **ctx.accounts.payer.try_borrow_mut_lamports()? += excess;
+    Ok(())
+}
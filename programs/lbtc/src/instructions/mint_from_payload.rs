@@ -13,6 +13,8 @@ use bascule::{
     program::Bascule,
     state::{BasculeData, BASCULE_SEED},
 };
+use compliance_hook::state::DenylistEntry;
+use proof_of_reserves::utils::reserves;
 
 #[derive(Accounts)]
 #[instruction(mint_payload_hash: [u8; 32])]
@@ -44,11 +46,28 @@ pub struct MintFromPayload<'info> {
     #[account(mut)]
     pub deposit: Option<UncheckedAccount<'info>>,
     pub system_program: Option<Program<'info, System>>,
+    /// Required when config.compliance_hook is Some, the recipient owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub recipient_denylist_entry: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
 }
 
 pub fn mint_from_payload(ctx: Context<MintFromPayload>, mint_payload_hash: [u8; 32]) -> Result<()> {
     require!(!ctx.accounts.config.paused, LBTCError::Paused);
     require!(!ctx.accounts.payload.minted, LBTCError::MintPayloadUsed);
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.recipient.owner,
+        ctx.accounts.recipient_denylist_entry.as_ref(),
+    )?;
     let amount = validation::post_validate_mint(
         &ctx.accounts.payer,
         &ctx.accounts.config,
@@ -67,6 +86,15 @@ pub fn mint_from_payload(ctx: Context<MintFromPayload>, mint_payload_hash: [u8;
         recipient: ctx.accounts.recipient.key(),
         payload_hash: mint_payload_hash,
     });
+    reserves::check_reserves(
+        ctx.accounts.config.proof_of_reserves,
+        ctx.accounts.reserves_program.as_ref(),
+        ctx.accounts.reserves_config.as_ref(),
+        ctx.accounts.mint_reserves.as_ref(),
+        ctx.accounts.mint.to_account_info(),
+        amount,
+    )?;
+
     utils::execute_mint(
         ctx.accounts.token_program.to_account_info(),
         ctx.accounts.recipient.to_account_info(),
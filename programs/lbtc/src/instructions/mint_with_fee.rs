@@ -1,11 +1,11 @@
 //! Minting functionality from a notarized payload where Lombard pays for the transaction fee, in
 //! return for a small rebate in LBTC.
 use crate::{
-    constants::{CONFIG_SEED, FEE_PAYLOAD_LEN},
+    constants::{ACCOUNT_ROLES_SEED, CONFIG_SEED, FEE_PAYLOAD_LEN},
     errors::LBTCError,
-    events::MintProofConsumed,
-    state::{Config, MintPayload},
-    utils::{self, validation},
+    events::{MintFeeCharged, MintProofConsumed},
+    state::{AccountRole, AccountRoles, Config, MintPayload},
+    utils::{self, fees, validation},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
@@ -14,6 +14,8 @@ use bascule::{
     program::Bascule,
     state::{BasculeData, BASCULE_SEED},
 };
+use compliance_hook::state::DenylistEntry;
+use proof_of_reserves::utils::reserves;
 
 #[derive(Accounts)]
 #[instruction(mint_payload_hash: [u8; 32])]
@@ -22,6 +24,12 @@ pub struct MintWithFee<'info> {
     pub payer: Signer<'info>,
     #[account(seeds = [CONFIG_SEED], bump)]
     pub config: Account<'info, Config>,
+    #[account(
+        constraint = account_roles.has_role(AccountRole::Claimer) @ LBTCError::Unauthorized,
+        seeds = [ACCOUNT_ROLES_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub account_roles: Account<'info, AccountRoles>,
     pub token_program: Interface<'info, TokenInterface>,
     /// CHECK: This will be verified by the token authority on recipient.
     pub recipient_auth: UncheckedAccount<'info>,
@@ -50,6 +58,18 @@ pub struct MintWithFee<'info> {
     #[account(mut)]
     pub deposit: Option<UncheckedAccount<'info>>,
     pub system_program: Option<Program<'info, System>>,
+    /// Required when config.compliance_hook is Some, the recipient owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub recipient_denylist_entry: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, must be the proof of reserves program.
+    /// CHECK: instruction body constrains it to have correct configured address.
+    pub reserves_program: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the proof of reserves config PDA.
+    /// CHECK: proof of reserves validates it
+    pub reserves_config: Option<UncheckedAccount<'info>>,
+    /// When config.proof_of_reserves is Some, the reserves PDA of the mint.
+    /// CHECK: proof of reserves validates it
+    pub mint_reserves: Option<UncheckedAccount<'info>>,
 }
 
 pub fn mint_with_fee(
@@ -59,15 +79,12 @@ pub fn mint_with_fee(
     fee_signature: [u8; 64],
 ) -> Result<()> {
     require!(!ctx.accounts.config.paused, LBTCError::Paused);
-    require!(
-        ctx.accounts
-            .config
-            .claimers
-            .iter()
-            .any(|&claimer| claimer == ctx.accounts.payer.key()),
-        LBTCError::Unauthorized
-    );
     require!(!ctx.accounts.payload.minted, LBTCError::MintPayloadUsed);
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.recipient.owner,
+        ctx.accounts.recipient_denylist_entry.as_ref(),
+    )?;
 
     let amount = validation::post_validate_mint(
         &ctx.accounts.payer,
@@ -82,13 +99,15 @@ pub fn mint_with_fee(
         &ctx.accounts.system_program,
     )?;
 
-    let fee = validation::validate_fee(
-        &ctx.accounts.config,
+    let fee_model = ctx.accounts.config.mint_fee;
+    let max_fee = validation::validate_fee(
         *ctx.program_id,
         &ctx.accounts.recipient_auth.to_account_info(),
         fee_payload,
         fee_signature,
     )?;
+    // The user approves a maximum fee, of which we only charge what the fee model asks for.
+    let fee = fees::compute_fee(&fee_model, amount)?.min(max_fee);
     require!(fee < amount, LBTCError::FeeGTEAmount);
 
     ctx.accounts.payload.minted = true;
@@ -96,6 +115,23 @@ pub fn mint_with_fee(
         recipient: ctx.accounts.recipient.key(),
         payload_hash: mint_payload_hash,
     });
+    emit!(MintFeeCharged {
+        recipient: ctx.accounts.recipient.key(),
+        payload_hash: mint_payload_hash,
+        amount,
+        max_fee,
+        fee,
+        fee_model,
+    });
+    reserves::check_reserves(
+        ctx.accounts.config.proof_of_reserves,
+        ctx.accounts.reserves_program.as_ref(),
+        ctx.accounts.reserves_config.as_ref(),
+        ctx.accounts.mint_reserves.as_ref(),
+        ctx.accounts.mint.to_account_info(),
+        amount,
+    )?;
+
     utils::execute_mint(
         ctx.accounts.token_program.to_account_info(),
         ctx.accounts.treasury.to_account_info(),
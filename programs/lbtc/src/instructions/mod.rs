@@ -10,6 +10,10 @@ pub mod create_metadata_for_valset_payload;
 pub mod initialize;
 pub use create_metadata_for_valset_payload::*;
 pub use initialize::*;
+pub mod grant_account_role;
+pub use grant_account_role::*;
+pub mod migrate_config;
+pub use migrate_config::*;
 pub mod mint_from_payload;
 pub use mint_from_payload::*;
 pub mod mint_with_fee;
@@ -32,7 +36,13 @@ pub mod change_auth;
 pub use change_auth::*;
 pub mod redeem;
 pub use redeem::*;
+pub mod revoke_account_roles;
+pub use revoke_account_roles::*;
 pub mod set_initial_valset;
 pub use set_initial_valset::*;
 pub mod set_next_valset;
 pub use set_next_valset::*;
+pub mod token_metadata;
+pub use token_metadata::*;
+pub mod update_metadata;
+pub use update_metadata::*;
@@ -1,5 +1,10 @@
 //! Collection of operator-privileged functionality.
-use crate::{constants, errors::LBTCError, events::MintFeeSet, state::Config};
+use crate::{
+    constants,
+    events::MintFeeSet,
+    state::{Config, FeeModel},
+    utils::fees,
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -10,8 +15,8 @@ pub struct Operator<'info> {
     pub config: Account<'info, Config>,
 }
 
-pub fn set_mint_fee(ctx: Context<Operator>, mint_fee: u64) -> Result<()> {
-    require!(mint_fee <= constants::MAX_FEE, LBTCError::FeeTooHigh);
+pub fn set_mint_fee(ctx: Context<Operator>, mint_fee: FeeModel) -> Result<()> {
+    fees::validate_fee_model(&mint_fee, &ctx.accounts.config.fee_bounds)?;
     ctx.accounts.config.mint_fee = mint_fee;
     emit!(MintFeeSet { mint_fee });
     Ok(())
@@ -1,5 +1,10 @@
 //! Pauses the program.
-use crate::{constants::CONFIG_SEED, errors::LBTCError, events::PauseEnabled, state::Config};
+use crate::{
+    constants::{ACCOUNT_ROLES_SEED, CONFIG_SEED},
+    errors::LBTCError,
+    events::PauseEnabled,
+    state::{AccountRole, AccountRoles, Config},
+};
 use anchor_lang::prelude::*;
 
 #[derive(Accounts)]
@@ -7,17 +12,15 @@ pub struct Pause<'info> {
     pub payer: Signer<'info>,
     #[account(mut, seeds = [CONFIG_SEED], bump)]
     pub config: Account<'info, Config>,
+    #[account(
+        constraint = account_roles.has_role(AccountRole::Pauser) @ LBTCError::Unauthorized,
+        seeds = [ACCOUNT_ROLES_SEED, payer.key().as_ref()],
+        bump
+    )]
+    pub account_roles: Account<'info, AccountRoles>,
 }
 
 pub fn pause(ctx: Context<Pause>) -> Result<()> {
-    require!(
-        ctx.accounts
-            .config
-            .pausers
-            .iter()
-            .any(|pauser| *pauser == ctx.accounts.payer.key()),
-        LBTCError::Unauthorized
-    );
     require!(!ctx.accounts.config.paused, LBTCError::Paused);
     ctx.accounts.config.paused = true;
     emit!(PauseEnabled { enabled: true });
@@ -25,14 +28,6 @@ pub fn pause(ctx: Context<Pause>) -> Result<()> {
 }
 
 pub fn unpause(ctx: Context<Pause>) -> Result<()> {
-    require!(
-        ctx.accounts
-            .config
-            .pausers
-            .iter()
-            .any(|pauser| *pauser == ctx.accounts.payer.key()),
-        LBTCError::Unauthorized
-    );
     require!(ctx.accounts.config.paused, LBTCError::NotPaused);
     ctx.accounts.config.paused = false;
     emit!(PauseEnabled { enabled: false });
@@ -2,12 +2,13 @@
 use crate::{
     constants,
     errors::LBTCError,
-    events::UnstakeRequest,
+    events::{RedeemFeeCharged, UnstakeRequest},
     state::{Config, UnstakeInfo},
-    utils::{self, bitcoin_utils},
+    utils::{self, bitcoin_utils, fees},
 };
 use anchor_lang::prelude::*;
 use anchor_spl::token_interface::{Mint, TokenAccount, TokenInterface};
+use compliance_hook::state::DenylistEntry;
 
 #[derive(Accounts)]
 pub struct Redeem<'info> {
@@ -36,16 +37,29 @@ pub struct Redeem<'info> {
     )]
     pub unstake_info: Account<'info, UnstakeInfo>,
     pub system_program: Program<'info, System>,
+    /// Required when config.compliance_hook is Some, the holder owner's denylist entry.
+    /// CHECK: Address is verified against the compliance hook in the instruction body.
+    pub holder_denylist_entry: Option<UncheckedAccount<'info>>,
 }
 
-pub fn redeem(ctx: Context<Redeem>, script_pubkey: Vec<u8>, amount: u64) -> Result<()> {
+pub fn redeem<'info>(
+    ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
+    script_pubkey: Vec<u8>,
+    amount: u64,
+) -> Result<()> {
     require!(!ctx.accounts.config.paused, LBTCError::Paused);
     require!(
         ctx.accounts.config.withdrawals_enabled,
         LBTCError::WithdrawalsDisabled
     );
+    DenylistEntry::ensure_not_denylisted(
+        ctx.accounts.config.compliance_hook,
+        &ctx.accounts.holder.owner,
+        ctx.accounts.holder_denylist_entry.as_ref(),
+    )?;
 
-    let fee = ctx.accounts.config.burn_commission;
+    let fee_model = ctx.accounts.config.burn_commission;
+    let fee = fees::compute_fee(&fee_model, amount)?;
     let dust_limit = bitcoin_utils::get_dust_limit_for_output(
         &script_pubkey,
         ctx.accounts.config.dust_fee_rate,
@@ -53,18 +67,15 @@ pub fn redeem(ctx: Context<Redeem>, script_pubkey: Vec<u8>, amount: u64) -> Resu
     require!(amount > fee, LBTCError::FeeGTEAmount);
     require!(amount - fee > dust_limit, LBTCError::AmountBelowDustLimit);
 
-    anchor_spl::token_interface::transfer_checked(
-        CpiContext::new(
-            ctx.accounts.token_program.to_account_info(),
-            anchor_spl::token_interface::TransferChecked {
-                from: ctx.accounts.holder.to_account_info(),
-                to: ctx.accounts.treasury.to_account_info(),
-                authority: ctx.accounts.payer.to_account_info(),
-                mint: ctx.accounts.mint.to_account_info(),
-            },
-        ),
+    // Remaining accounts carry the transfer hook accounts when the mint has one configured.
+    utils::execute_transfer(
+        ctx.accounts.token_program.to_account_info(),
+        ctx.accounts.holder.to_account_info(),
+        ctx.accounts.mint.to_account_info(),
+        ctx.accounts.treasury.to_account_info(),
+        ctx.accounts.payer.to_account_info(),
+        ctx.remaining_accounts,
         fee,
-        constants::LBTC_DECIMALS,
     )?;
 
     utils::execute_burn(
@@ -80,6 +91,12 @@ pub fn redeem(ctx: Context<Redeem>, script_pubkey: Vec<u8>, amount: u64) -> Resu
     ctx.accounts.unstake_info.amount = amount - fee;
     ctx.accounts.config.unstake_counter += 1;
 
+    emit!(RedeemFeeCharged {
+        from: ctx.accounts.holder.key(),
+        amount,
+        fee,
+        fee_model,
+    });
     emit!(UnstakeRequest {
         from: ctx.accounts.holder.key(),
         script_pubkey,
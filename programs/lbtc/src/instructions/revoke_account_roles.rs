@@ -0,0 +1,28 @@
+//! Revokes all roles of an account by closing its roles PDA.
+use crate::{
+    constants,
+    events::AccountRolesRevoked,
+    state::{AccountRoles, Config},
+};
+use anchor_lang::prelude::*;
+
+#[derive(Accounts)]
+#[instruction(account: Pubkey)]
+pub struct RevokeAccountRoles<'info> {
+    #[account(mut, address = config.admin)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [constants::CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = payer,
+        seeds = [constants::ACCOUNT_ROLES_SEED, account.as_ref()],
+        bump
+    )]
+    pub account_roles: Account<'info, AccountRoles>,
+}
+
+pub fn revoke_account_roles(_ctx: Context<RevokeAccountRoles>, account: Pubkey) -> Result<()> {
+    emit!(AccountRolesRevoked { account });
+    Ok(())
+}
@@ -0,0 +1,92 @@
+//! Allows admin to manage token metadata stored in the Token-2022 metadata extension of the mint,
+//! as an alternative to Metaplex metadata. The mint must have its metadata pointer set to itself,
+//! and the token authority PDA acts as both mint authority and metadata update authority.
+use crate::{
+    constants,
+    errors::LBTCError,
+    events::{MetadataUpdateAuthorityChanged, MetadataUpdated, TokenMetadataFieldUpdated},
+    state::{Config, MetadataField},
+};
+use anchor_lang::prelude::*;
+use anchor_spl::{token_2022::Token2022, token_interface::Mint};
+use token_metadata_utils::token_extension::TokenMetadataAccounts;
+
+#[derive(Accounts)]
+pub struct ManageTokenMetadata<'info> {
+    #[account(mut, address = config.admin @ LBTCError::Unauthorized)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [constants::CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(mut, address = config.mint, mint::token_program = token_program)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The seeds constraint ensures the correct address is passed.
+    #[account(seeds = [constants::TOKEN_AUTHORITY_SEED], bump)]
+    pub token_authority: UncheckedAccount<'info>,
+    pub token_program: Program<'info, Token2022>,
+    pub system_program: Program<'info, System>,
+}
+
+impl<'info> ManageTokenMetadata<'info> {
+    fn metadata_accounts(&self) -> TokenMetadataAccounts<'info> {
+        TokenMetadataAccounts {
+            token_program: self.token_program.to_account_info(),
+            mint: self.mint.to_account_info(),
+            token_authority: self.token_authority.to_account_info(),
+            payer: self.payer.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+        }
+    }
+}
+
+pub fn initialize_token_metadata(
+    ctx: Context<ManageTokenMetadata>,
+    name: String,
+    symbol: String,
+    uri: String,
+) -> Result<()> {
+    ctx.accounts.metadata_accounts().initialize(
+        &[&[
+            constants::TOKEN_AUTHORITY_SEED,
+            &[ctx.bumps.token_authority],
+        ]],
+        name.clone(),
+        symbol.clone(),
+        uri.clone(),
+    )?;
+    emit!(MetadataUpdated { name, symbol, uri });
+    Ok(())
+}
+
+pub fn update_token_metadata_field(
+    ctx: Context<ManageTokenMetadata>,
+    field: MetadataField,
+    value: String,
+) -> Result<()> {
+    ctx.accounts.metadata_accounts().update_field(
+        &[&[
+            constants::TOKEN_AUTHORITY_SEED,
+            &[ctx.bumps.token_authority],
+        ]],
+        field.clone().into(),
+        value.clone(),
+    )?;
+    emit!(TokenMetadataFieldUpdated { field, value });
+    Ok(())
+}
+
+pub fn set_token_metadata_update_authority(
+    ctx: Context<ManageTokenMetadata>,
+    new_update_authority: Option<Pubkey>,
+) -> Result<()> {
+    ctx.accounts.metadata_accounts().update_authority(
+        &[&[
+            constants::TOKEN_AUTHORITY_SEED,
+            &[ctx.bumps.token_authority],
+        ]],
+        new_update_authority,
+    )?;
+    emit!(MetadataUpdateAuthorityChanged {
+        new_update_authority
+    });
+    Ok(())
+}
@@ -0,0 +1,113 @@
+//! Allows admin to update the Metaplex token metadata and hand over its update authority. The
+//! token authority PDA must be the current update authority of the metadata account, which
+//! `claim_metadata_update_authority` arranges for metadata still held by the admin.
+use crate::{
+    constants,
+    errors::LBTCError,
+    events::{MetadataUpdateAuthorityChanged, MetadataUpdated},
+    state::Config,
+};
+use anchor_lang::{prelude::*, solana_program::sysvar};
+use anchor_spl::token_interface::Mint;
+use mpl_token_metadata::accounts::Metadata;
+use token_metadata_utils::metaplex::MetadataAccounts;
+
+#[derive(Accounts)]
+pub struct UpdateMetadata<'info> {
+    #[account(mut, address = config.admin @ LBTCError::Unauthorized)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [constants::CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: The address constraint ensures the correct program is passed.
+    #[account(address = mpl_token_metadata::ID)]
+    pub metadata_program: UncheckedAccount<'info>,
+    /// CHECK: The seeds constraint ensures the correct address is passed.
+    #[account(
+        mut,
+        seeds = [Metadata::PREFIX, mpl_token_metadata::ID.as_ref(), mint.key().as_ref()],
+        seeds::program = mpl_token_metadata::ID,
+        bump
+    )]
+    pub metadata_pda: UncheckedAccount<'info>,
+    #[account(address = config.mint)]
+    pub mint: InterfaceAccount<'info, Mint>,
+    /// CHECK: The seeds constraint ensures the correct address is passed.
+    #[account(seeds = [constants::TOKEN_AUTHORITY_SEED], bump)]
+    pub token_authority: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+    /// CHECK: The address constraint ensures the correct sysvar is passed.
+    #[account(address = sysvar::instructions::ID)]
+    pub sysvar_instructions: UncheckedAccount<'info>,
+}
+
+impl<'info> UpdateMetadata<'info> {
+    fn metadata_accounts(&self) -> MetadataAccounts<'info> {
+        MetadataAccounts {
+            metadata_program: self.metadata_program.to_account_info(),
+            metadata: self.metadata_pda.to_account_info(),
+            mint: self.mint.to_account_info(),
+            payer: self.payer.to_account_info(),
+            system_program: self.system_program.to_account_info(),
+            sysvar_instructions: self.sysvar_instructions.to_account_info(),
+        }
+    }
+}
+
+pub fn update_metadata(
+    ctx: Context<UpdateMetadata>,
+    name: Option<String>,
+    symbol: Option<String>,
+    uri: Option<String>,
+) -> Result<()> {
+    let data = ctx
+        .accounts
+        .metadata_accounts()
+        .updated_data(name, symbol, uri)?;
+    emit!(MetadataUpdated {
+        name: data.name.clone(),
+        symbol: data.symbol.clone(),
+        uri: data.uri.clone(),
+    });
+    ctx.accounts.metadata_accounts().update(
+        &ctx.accounts.token_authority.to_account_info(),
+        None,
+        Some(data),
+        &[&[
+            constants::TOKEN_AUTHORITY_SEED,
+            &[ctx.bumps.token_authority],
+        ]],
+    )
+}
+
+pub fn set_metadata_update_authority(
+    ctx: Context<UpdateMetadata>,
+    new_update_authority: Pubkey,
+) -> Result<()> {
+    emit!(MetadataUpdateAuthorityChanged {
+        new_update_authority: Some(new_update_authority),
+    });
+    ctx.accounts.metadata_accounts().update(
+        &ctx.accounts.token_authority.to_account_info(),
+        Some(new_update_authority),
+        None,
+        &[&[
+            constants::TOKEN_AUTHORITY_SEED,
+            &[ctx.bumps.token_authority],
+        ]],
+    )
+}
+
+/// Moves the update authority from the admin, who is the update authority of metadata created
+/// before the token authority PDA took over, to the token authority PDA.
+pub fn claim_metadata_update_authority(ctx: Context<UpdateMetadata>) -> Result<()> {
+    let token_authority = ctx.accounts.token_authority.key();
+    emit!(MetadataUpdateAuthorityChanged {
+        new_update_authority: Some(token_authority),
+    });
+    ctx.accounts.metadata_accounts().update(
+        &ctx.accounts.payer.to_account_info(),
+        Some(token_authority),
+        None,
+        &[],
+    )
+}
@@ -11,6 +11,7 @@ use anchor_lang::prelude::*;
 use constants::VALIDATOR_PUBKEY_SIZE;
 use constants::{FEE_PAYLOAD_LEN, MINT_PAYLOAD_LEN};
 use instructions::*;
+use state::{AccountRole, FeeBounds, FeeModel, MetadataField};
 
 declare_id!("LomP48F7bLbKyMRHHsDVt7wuHaUQvQnVVspjcbfuAek");
 
@@ -21,9 +22,9 @@ pub mod lbtc {
     pub fn initialize(
         ctx: Context<Initialize>,
         admin: Pubkey,
-        burn_commission: u64,
+        burn_commission: FeeModel,
         dust_fee_rate: u64,
-        mint_fee: u64,
+        mint_fee: FeeModel,
     ) -> Result<()> {
         instructions::initialize(ctx, admin, burn_commission, dust_fee_rate, mint_fee)
     }
@@ -52,7 +53,11 @@ pub mod lbtc {
         instructions::mint_from_payload(ctx, mint_payload_hash)
     }
 
-    pub fn redeem(ctx: Context<Redeem>, script_pubkey: Vec<u8>, amount: u64) -> Result<()> {
+    pub fn redeem<'info>(
+        ctx: Context<'_, '_, '_, 'info, Redeem<'info>>,
+        script_pubkey: Vec<u8>,
+        amount: u64,
+    ) -> Result<()> {
         instructions::redeem(ctx, script_pubkey, amount)
     }
 
@@ -129,14 +134,29 @@ pub mod lbtc {
         instructions::disable_bascule(ctx)
     }
 
-    pub fn set_mint_fee(ctx: Context<Operator>, mint_fee: u64) -> Result<()> {
+    pub fn set_mint_fee(ctx: Context<Operator>, mint_fee: FeeModel) -> Result<()> {
         instructions::set_mint_fee(ctx, mint_fee)
     }
 
-    pub fn set_burn_commission(ctx: Context<Admin>, commission: u64) -> Result<()> {
+    pub fn set_compliance_hook(ctx: Context<Admin>, compliance_hook: Option<Pubkey>) -> Result<()> {
+        instructions::set_compliance_hook(ctx, compliance_hook)
+    }
+
+    pub fn set_proof_of_reserves(
+        ctx: Context<Admin>,
+        proof_of_reserves: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_proof_of_reserves(ctx, proof_of_reserves)
+    }
+
+    pub fn set_burn_commission(ctx: Context<Admin>, commission: FeeModel) -> Result<()> {
         instructions::set_burn_commission(ctx, commission)
     }
 
+    pub fn set_fee_bounds(ctx: Context<Admin>, fee_bounds: FeeBounds) -> Result<()> {
+        instructions::set_fee_bounds(ctx, fee_bounds)
+    }
+
     pub fn set_operator(ctx: Context<Admin>, operator: Pubkey) -> Result<()> {
         instructions::set_operator(ctx, operator)
     }
@@ -149,20 +169,22 @@ pub mod lbtc {
         instructions::set_treasury(ctx)
     }
 
-    pub fn add_claimer(ctx: Context<Admin>, claimer: Pubkey) -> Result<()> {
-        instructions::add_claimer(ctx, claimer)
-    }
-
-    pub fn remove_claimer(ctx: Context<Admin>, claimer: Pubkey) -> Result<()> {
-        instructions::remove_claimer(ctx, claimer)
+    pub fn grant_account_role(
+        ctx: Context<GrantAccountRole>,
+        account: Pubkey,
+        account_role: AccountRole,
+    ) -> Result<()> {
+        instructions::grant_account_role(ctx, account, account_role)
     }
 
-    pub fn add_pauser(ctx: Context<Admin>, pauser: Pubkey) -> Result<()> {
-        instructions::add_pauser(ctx, pauser)
+    pub fn revoke_account_roles(ctx: Context<RevokeAccountRoles>, account: Pubkey) -> Result<()> {
+        instructions::revoke_account_roles(ctx, account)
     }
 
-    pub fn remove_pauser(ctx: Context<Admin>, pauser: Pubkey) -> Result<()> {
-        instructions::remove_pauser(ctx, pauser)
+    pub fn migrate_config<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, MigrateConfig<'info>>,
+    ) -> Result<()> {
+        instructions::migrate_config(ctx)
     }
 
     pub fn change_mint_auth(ctx: Context<ChangeAuth>, new_auth: Pubkey) -> Result<()> {
@@ -180,4 +202,48 @@ pub mod lbtc {
     pub fn create_metadata(ctx: Context<CreateMetadata>) -> Result<()> {
         instructions::create_metadata(ctx)
     }
+
+    pub fn update_metadata(
+        ctx: Context<UpdateMetadata>,
+        name: Option<String>,
+        symbol: Option<String>,
+        uri: Option<String>,
+    ) -> Result<()> {
+        instructions::update_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn set_metadata_update_authority(
+        ctx: Context<UpdateMetadata>,
+        new_update_authority: Pubkey,
+    ) -> Result<()> {
+        instructions::set_metadata_update_authority(ctx, new_update_authority)
+    }
+
+    pub fn claim_metadata_update_authority(ctx: Context<UpdateMetadata>) -> Result<()> {
+        instructions::claim_metadata_update_authority(ctx)
+    }
+
+    pub fn initialize_token_metadata(
+        ctx: Context<ManageTokenMetadata>,
+        name: String,
+        symbol: String,
+        uri: String,
+    ) -> Result<()> {
+        instructions::initialize_token_metadata(ctx, name, symbol, uri)
+    }
+
+    pub fn update_token_metadata_field(
+        ctx: Context<ManageTokenMetadata>,
+        field: MetadataField,
+        value: String,
+    ) -> Result<()> {
+        instructions::update_token_metadata_field(ctx, field, value)
+    }
+
+    pub fn set_token_metadata_update_authority(
+        ctx: Context<ManageTokenMetadata>,
+        new_update_authority: Option<Pubkey>,
+    ) -> Result<()> {
+        instructions::set_token_metadata_update_authority(ctx, new_update_authority)
+    }
 }
@@ -1,8 +1,12 @@
 use crate::{
-    constants::{MAX_VALIDATOR_SET_SIZE, MINT_PAYLOAD_LEN, VALIDATOR_PUBKEY_SIZE},
+    constants::{
+        DEFAULT_MAX_FEE_BPS, MAX_FEE, MAX_VALIDATOR_SET_SIZE, MINT_PAYLOAD_LEN,
+        VALIDATOR_PUBKEY_SIZE,
+    },
     utils::bitcoin_utils::P2TR_P2WSH_LEN,
 };
 use anchor_lang::prelude::*;
+use anchor_spl::token_2022_extensions::spl_token_metadata_interface::state::Field;
 
 #[account]
 #[derive(InitSpace)]
@@ -12,27 +16,26 @@ pub struct Config {
     pub pending_admin: Pubkey,
     pub operator: Pubkey,
     pub treasury: Pubkey,
-    // NOTE: Since we have this as a contiguous block of memory, we can initially add more than 10
-    // claimers (provided that the pausers vector is smaller than 10 elements at the time), so this
-    // attribute is not a hard limit that's unique to the vector, but rather an instruction to
-    // anchor about how much storage we allocate for this account.
-    #[max_len(10)]
-    pub claimers: Vec<Pubkey>,
-    #[max_len(10)]
-    pub pausers: Vec<Pubkey>,
 
     // Mint/redeem fields
     pub mint: Pubkey,
-    pub burn_commission: u64,
+    pub burn_commission: FeeModel,
     pub withdrawals_enabled: bool,
     pub dust_fee_rate: u64,
     pub bascule_enabled: bool,
+    /// When Some, mint and redeem reject accounts denylisted by this compliance hook program.
+    pub compliance_hook: Option<Pubkey>,
+    /// When Some, mints are checked against the reserves attested to this program.
+    pub proof_of_reserves: Option<Pubkey>,
 
     // Global pause
     pub paused: bool,
 
     // Automint fields
-    pub mint_fee: u64,
+    pub mint_fee: FeeModel,
+
+    // Limits for the fee models above
+    pub fee_bounds: FeeBounds,
 
     // Consortium fields
     pub epoch: u64,
@@ -46,6 +49,116 @@ pub struct Config {
     pub unstake_counter: u64,
 }
 
+/// How a fee is derived from the amount being minted or redeemed.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
+pub enum FeeModel {
+    /// A fixed fee, in LBTC base units.
+    Flat { amount: u64 },
+    /// A fee proportional to the amount, in basis points.
+    BasisPoints { bps: u16 },
+    /// A proportional fee clamped between `min` and `max`, in LBTC base units.
+    CappedBasisPoints { bps: u16, min: u64, max: u64 },
+}
+
+/// Admin-set limits that any configured [`FeeModel`] must respect.
+#[derive(Clone, Copy, Debug, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
+pub struct FeeBounds {
+    /// The maximum flat fee, which also bounds the cap of a capped fee.
+    pub max_flat_fee: u64,
+    /// The maximum proportional fee, in basis points.
+    pub max_fee_bps: u16,
+}
+
+impl Default for FeeBounds {
+    fn default() -> Self {
+        Self {
+            max_flat_fee: MAX_FEE,
+            max_fee_bps: DEFAULT_MAX_FEE_BPS,
+        }
+    }
+}
+
+/// The layout of [`Config`] before claimers and pausers were moved into [`AccountRoles`] PDAs.
+/// Only used by `migrate_config` to read the existing account.
+#[derive(AnchorSerialize, AnchorDeserialize, InitSpace)]
+pub struct LegacyConfig {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    pub operator: Pubkey,
+    pub treasury: Pubkey,
+    #[max_len(10)]
+    pub claimers: Vec<Pubkey>,
+    #[max_len(10)]
+    pub pausers: Vec<Pubkey>,
+    pub mint: Pubkey,
+    pub burn_commission: u64,
+    pub withdrawals_enabled: bool,
+    pub dust_fee_rate: u64,
+    pub bascule_enabled: bool,
+    pub paused: bool,
+    pub mint_fee: u64,
+    pub epoch: u64,
+    #[max_len(MAX_VALIDATOR_SET_SIZE)]
+    pub validators: Vec<[u8; VALIDATOR_PUBKEY_SIZE]>,
+    #[max_len(MAX_VALIDATOR_SET_SIZE)]
+    pub weights: Vec<u64>,
+    pub weight_threshold: u64,
+    pub unstake_counter: u64,
+}
+
+impl From<LegacyConfig> for Config {
+    fn from(legacy: LegacyConfig) -> Self {
+        Self {
+            admin: legacy.admin,
+            pending_admin: legacy.pending_admin,
+            operator: legacy.operator,
+            treasury: legacy.treasury,
+            mint: legacy.mint,
+            burn_commission: FeeModel::Flat {
+                amount: legacy.burn_commission,
+            },
+            withdrawals_enabled: legacy.withdrawals_enabled,
+            dust_fee_rate: legacy.dust_fee_rate,
+            bascule_enabled: legacy.bascule_enabled,
+            compliance_hook: None,
+            proof_of_reserves: None,
+            paused: legacy.paused,
+            mint_fee: FeeModel::Flat {
+                amount: legacy.mint_fee,
+            },
+            fee_bounds: FeeBounds::default(),
+            epoch: legacy.epoch,
+            validators: legacy.validators,
+            weights: legacy.weights,
+            weight_threshold: legacy.weight_threshold,
+            unstake_counter: legacy.unstake_counter,
+        }
+    }
+}
+
+#[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
+pub enum AccountRole {
+    Claimer,
+    Pauser,
+}
+
+#[account]
+#[derive(InitSpace)]
+pub struct AccountRoles {
+    #[max_len(3)] // to have some room for future roles
+    pub roles: Vec<AccountRole>,
+}
+
+impl AccountRoles {
+    pub fn add_role(&mut self, role: AccountRole) {
+        self.roles.push(role);
+    }
+
+    pub fn has_role(&self, role: AccountRole) -> bool {
+        self.roles.contains(&role)
+    }
+}
+
 #[account]
 #[derive(InitSpace)]
 pub struct MintPayload {
@@ -87,3 +200,23 @@ pub struct UnstakeInfo {
     pub script_pubkey: Vec<u8>,
     pub amount: u64,
 }
+
+/// A field of the Token-2022 metadata extension, mirroring the token metadata interface `Field`.
+#[derive(Clone, AnchorSerialize, AnchorDeserialize)]
+pub enum MetadataField {
+    Name,
+    Symbol,
+    Uri,
+    Key(String),
+}
+
+impl From<MetadataField> for Field {
+    fn from(field: MetadataField) -> Self {
+        match field {
+            MetadataField::Name => Field::Name,
+            MetadataField::Symbol => Field::Symbol,
+            MetadataField::Uri => Field::Uri,
+            MetadataField::Key(key) => Field::Key(key),
+        }
+    }
+}
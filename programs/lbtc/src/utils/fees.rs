@@ -0,0 +1,138 @@
+//! This module implements the fee models used for redeems and minting with a fee.
+use crate::{
+    constants::BASIS_POINTS_BASE,
+    errors::LBTCError,
+    state::{FeeBounds, FeeModel},
+};
+use anchor_lang::prelude::*;
+
+/// Computes the fee charged on `amount` under the given fee model. Proportional fees are rounded
+/// up, so that no non-zero amount can be moved for free under a non-zero rate.
+pub fn compute_fee(fee_model: &FeeModel, amount: u64) -> Result<u64> {
+    match *fee_model {
+        FeeModel::Flat { amount: fee } => Ok(fee),
+        FeeModel::BasisPoints { bps } => proportional_fee(amount, bps),
+        FeeModel::CappedBasisPoints { bps, min, max } => {
+            Ok(proportional_fee(amount, bps)?.clamp(min, max))
+        }
+    }
+}
+
+/// Checks that a fee model stays within the admin-set bounds.
+pub fn validate_fee_model(fee_model: &FeeModel, bounds: &FeeBounds) -> Result<()> {
+    match *fee_model {
+        FeeModel::Flat { amount } => {
+            require!(amount <= bounds.max_flat_fee, LBTCError::FeeTooHigh);
+        }
+        FeeModel::BasisPoints { bps } => {
+            require!(bps <= bounds.max_fee_bps, LBTCError::FeeTooHigh);
+        }
+        FeeModel::CappedBasisPoints { bps, min, max } => {
+            require!(bps <= bounds.max_fee_bps, LBTCError::FeeTooHigh);
+            require!(max <= bounds.max_flat_fee, LBTCError::FeeTooHigh);
+            require!(min <= max, LBTCError::InvalidFeeModel);
+        }
+    }
+    Ok(())
+}
+
+/// Checks that the fee bounds themselves are sane.
+pub fn validate_fee_bounds(bounds: &FeeBounds) -> Result<()> {
+    require!(
+        u64::from(bounds.max_fee_bps) < BASIS_POINTS_BASE,
+        LBTCError::FeeTooHigh
+    );
+    Ok(())
+}
+
+fn proportional_fee(amount: u64, bps: u16) -> Result<u64> {
+    let fee = (amount as u128 * bps as u128).div_ceil(BASIS_POINTS_BASE as u128);
+    u64::try_from(fee).map_err(|_| error!(LBTCError::FeeTooHigh))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const BOUNDS: FeeBounds = FeeBounds {
+        max_flat_fee: 100_000,
+        max_fee_bps: 100,
+    };
+
+    #[test]
+    fn test_flat_fee() {
+        let model = FeeModel::Flat { amount: 1_000 };
+        assert_eq!(compute_fee(&model, 0).unwrap(), 1_000);
+        assert_eq!(compute_fee(&model, 100_000_000).unwrap(), 1_000);
+    }
+
+    #[test]
+    fn test_basis_points_fee() {
+        let model = FeeModel::BasisPoints { bps: 25 };
+        assert_eq!(compute_fee(&model, 100_000_000).unwrap(), 250_000);
+        // 1 * 25 / 10000 rounds up to a single unit.
+        assert_eq!(compute_fee(&model, 1).unwrap(), 1);
+        assert_eq!(compute_fee(&model, 0).unwrap(), 0);
+        assert_eq!(
+            compute_fee(&model, u64::MAX).unwrap(),
+            u64::MAX / 400 + 1
+        );
+    }
+
+    #[test]
+    fn test_capped_basis_points_fee() {
+        let model = FeeModel::CappedBasisPoints {
+            bps: 10,
+            min: 500,
+            max: 50_000,
+        };
+        assert_eq!(compute_fee(&model, 1_000).unwrap(), 500);
+        assert_eq!(compute_fee(&model, 10_000_000).unwrap(), 10_000);
+        assert_eq!(compute_fee(&model, 1_000_000_000).unwrap(), 50_000);
+    }
+
+    #[test]
+    fn test_validate_fee_model() {
+        assert!(validate_fee_model(&FeeModel::Flat { amount: 100_000 }, &BOUNDS).is_ok());
+        assert!(validate_fee_model(&FeeModel::Flat { amount: 100_001 }, &BOUNDS).is_err());
+        assert!(validate_fee_model(&FeeModel::BasisPoints { bps: 100 }, &BOUNDS).is_ok());
+        assert!(validate_fee_model(&FeeModel::BasisPoints { bps: 101 }, &BOUNDS).is_err());
+        assert!(validate_fee_model(
+            &FeeModel::CappedBasisPoints {
+                bps: 50,
+                min: 10,
+                max: 100_000
+            },
+            &BOUNDS
+        )
+        .is_ok());
+        assert!(validate_fee_model(
+            &FeeModel::CappedBasisPoints {
+                bps: 50,
+                min: 10,
+                max: 100_001
+            },
+            &BOUNDS
+        )
+        .is_err());
+        assert!(validate_fee_model(
+            &FeeModel::CappedBasisPoints {
+                bps: 50,
+                min: 11,
+                max: 10
+            },
+            &BOUNDS
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_validate_fee_bounds() {
+        assert!(validate_fee_bounds(&BOUNDS).is_ok());
+        assert!(validate_fee_bounds(&FeeBounds {
+            max_flat_fee: 0,
+            max_fee_bps: 10_000
+        })
+        .is_err());
+    }
+}
@@ -89,8 +89,8 @@ pub fn post_validate_mint<'info>(
     Ok(mint_action.amount)
 }
 
+/// Validates a user-signed fee approval, returning the maximum fee the user agreed to pay.
 pub fn validate_fee<'info>(
-    config: &Account<'info, Config>,
     program_id: Pubkey,
     recipient_auth: &AccountInfo<'info>,
     fee_payload: [u8; constants::FEE_PAYLOAD_LEN],
@@ -111,13 +111,6 @@ pub fn validate_fee<'info>(
         LBTCError::InvalidVerifyingcontract
     );
 
-    // Select correct fee
-    let fee = if fee_action.fee > config.mint_fee {
-        config.mint_fee
-    } else {
-        fee_action.fee
-    };
-
     // Check expiry
     let clock = Clock::get()?;
     if clock.unix_timestamp as u64 > fee_action.expiry {
@@ -130,7 +123,7 @@ pub fn validate_fee<'info>(
     if verify_signature(&recipient_auth.key(), &fee_signature, &fee_payload)
         .map_err(|_| LBTCError::InvalidFeeSignature)?
     {
-        Ok(fee)
+        Ok(fee_action.fee)
     } else {
         err!(LBTCError::InvalidFeeSignature)
     }
@@ -14,7 +14,7 @@ use std::{
 
 use mailbox::{
     self,
-    state::Config,
+    state::{Config, MessageV1Info},
 };
 
 use crate::{
@@ -40,6 +40,7 @@ pub mod release_or_mint {
         RetrieveStateConfig,
         RetrieveChainConfig,
         BuildDynamicAccounts,
+        RetrieveDeliverer,
     }
 
     impl Display for OfframpDeriveStage {
@@ -48,6 +49,7 @@ pub mod release_or_mint {
                 OfframpDeriveStage::RetrieveStateConfig => f.write_str("RetrieveStateConfig"),
                 OfframpDeriveStage::RetrieveChainConfig => f.write_str("RetrieveChainConfig"),
                 OfframpDeriveStage::BuildDynamicAccounts => f.write_str("BuildDynamicAccounts"),
+                OfframpDeriveStage::RetrieveDeliverer => f.write_str("RetrieveDeliverer"),
             }
         }
     }
@@ -60,6 +62,7 @@ pub mod release_or_mint {
                 "Start" | "RetrieveStateConfig" => Ok(Self::RetrieveStateConfig),
                 "RetrieveChainConfig" => Ok(Self::RetrieveChainConfig),
                 "BuildDynamicAccounts" => Ok(Self::BuildDynamicAccounts),
+                "RetrieveDeliverer" => Ok(Self::RetrieveDeliverer),
                 _ => Err(CcipTokenPoolError::InvalidDerivationStage),
             }
         }
@@ -123,6 +126,9 @@ pub mod release_or_mint {
         let payload_hash = sha256(&payload).to_bytes();
 
         Ok(DeriveAccountsResponse {
+            ask_again_with: vec![
+                get_pda(&[b"message", &payload_hash], &mailbox::ID).readonly(),
+            ],
             accounts_to_save: vec![
                 // remote_bridge_config
                 get_pda(&[b"remote_bridge_config", chain_id.as_ref()], &bridge::ID)
@@ -139,6 +145,25 @@ pub mod release_or_mint {
                 get_pda(&[b"message_handled", &payload_hash], &bridge::ID).writable(),
             ],
             current_stage: OfframpDeriveStage::BuildDynamicAccounts.to_string(),
+            next_stage: OfframpDeriveStage::RetrieveDeliverer.to_string(),
+            ..Default::default()
+        })
+    }
+
+    pub fn retrieve_deliverer<'info>(
+        ctx: Context<'_, '_, 'info, 'info, Empty>,
+    ) -> Result<DeriveAccountsResponse> {
+        let message_info = Account::<'info, MessageV1Info>::try_from(&ctx.remaining_accounts[0])?;
+
+        Ok(DeriveAccountsResponse {
+            accounts_to_save: vec![
+                // deliverer, refunded by the mailbox once the message is handled
+                message_info.deliverer.writable(),
+                // mailbox_event_authority
+                find(&[b"__event_authority"], mailbox::ID),
+            ],
+            current_stage: OfframpDeriveStage::RetrieveDeliverer.to_string(),
+            next_stage: "".to_string(),
             ..Default::default()
         })
     }
@@ -280,10 +305,10 @@ pub mod lock_or_burn {
                     .readonly(),
                 // mailbox_sender_config
                 get_pda(&[b"sender_config", &bridge::ID.as_ref()], &mailbox::ID)
-                    .readonly(),
+                    .writable(),
                 // outbound_message_path
                 get_pda(&[b"outbound_message_path", chain_id.as_ref()], &mailbox::ID)
-                    .readonly(),
+                    .writable(),
             ],
             current_stage: OnrampDeriveStage::BuildDynamicAccounts1.to_string(),
             next_stage: OnrampDeriveStage::BuildDynamicAccounts2.to_string(),
@@ -303,6 +328,10 @@ pub mod lock_or_burn {
                 mailbox_config.treasury.writable(),
                 // outbound_message
                 get_pda(&[b"outbound_message", &mailbox_config.global_nonce.to_be_bytes()], &mailbox::ID).writable(),
+                // outbound_message_record
+                get_pda(&[b"outbound_message_record", &mailbox_config.global_nonce.to_be_bytes()], &mailbox::ID).writable(),
+                // mailbox_event_authority
+                find(&[b"__event_authority"], mailbox::ID),
             ],
             current_stage: OnrampDeriveStage::BuildDynamicAccounts2.to_string(),
             next_stage: "".to_string(),
@@ -126,16 +126,17 @@ pub struct TokenOnramp<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account()]
     pub remote_bridge_config: Account<'info, RemoteBridgeConfig>,
-    /// CHECK: This will be verified by the mailbox program
-    #[account()]
+    /// CHECK: This will be verified by the bridge program, which consumes its outbound rate limit
+    #[account(mut)]
     pub remote_token_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
     #[account()]
     pub bridge_sender_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
-    #[account()]
+    #[account(mut)]
     pub mailbox_sender_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
     pub outbound_message_path: UncheckedAccount<'info>,
     pub treasury: Option<UncheckedAccount<'info>>,
     #[account(
@@ -144,6 +145,12 @@ pub struct TokenOnramp<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub outbound_message: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub outbound_message_record: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the bridge program
+    #[account()]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
 }
 
 pub fn lock_or_burn_tokens(
@@ -205,8 +212,10 @@ fn bridge_deposit_for_burn_with_caller(
             mint: ctx.accounts.mint.to_account_info(), 
             mailbox: ctx.accounts.mailbox.to_account_info(), 
             mailbox_config: ctx.accounts.mailbox_config.to_account_info(), 
+            mailbox_event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
             outbound_message_path: ctx.accounts.outbound_message_path.to_account_info(), 
             outbound_message: ctx.accounts.outbound_message.to_account_info(), 
+            outbound_message_record: ctx.accounts.outbound_message_record.to_account_info(),
             mailbox_sender_config: ctx.accounts.mailbox_sender_config.to_account_info(),
             sender_config: ctx.accounts.bridge_sender_config.to_account_info(),
             remote_bridge_config: ctx.accounts.remote_bridge_config.to_account_info(),
@@ -216,7 +225,13 @@ fn bridge_deposit_for_burn_with_caller(
                 Some(a) => Some(a.to_account_info()),
                 None => None,
             },
+            mailbox_sender_fee_schedule: None,
+            mailbox_fee_token: None,
+            fee_payer_token_account: None,
+            mailbox_treasury_token_account: None,
             system_program: ctx.accounts.system_program.to_account_info(),
+            token_vault: None,
+            vault_token_account: None,
         },
         pool_signer_seeds,
     );
@@ -153,7 +153,7 @@ pub struct TokenOfframp<'info> {
     #[account()]
     pub mailbox: Program<'info, Mailbox>,
     /// CHECK: This will be verified by the mailbox program
-    #[account()]
+    #[account(mut)]
     pub mailbox_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
     #[account()]
@@ -169,7 +169,7 @@ pub struct TokenOfframp<'info> {
     #[account()]
     pub remote_token_config: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
-    #[account()]
+    #[account(mut)]
     pub inbound_message_path: UncheckedAccount<'info>,
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
@@ -177,6 +177,12 @@ pub struct TokenOfframp<'info> {
     /// CHECK: This will be verified by the mailbox program
     #[account(mut)]
     pub message_handled: UncheckedAccount<'info>,
+    /// CHECK: This will be verified by the mailbox program
+    #[account(mut)]
+    pub deliverer: UncheckedAccount<'info>,
+    /// CHECK: The event authority of the mailbox program, which emits its events through a self-CPI
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = mailbox.key())]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
 }
 
 pub fn release_or_mint_tokens<'info>(
@@ -271,6 +277,16 @@ fn mailbox_receive_message<'info>(
             config: ctx.accounts.mailbox_config.to_account_info(),
             message_info: ctx.accounts.message_info.to_account_info(),
             recipient_program: ctx.accounts.bridge.to_account_info(),
+            deliverer: ctx.accounts.deliverer.to_account_info(),
+            inbound_message_path: Some(ctx.accounts.inbound_message_path.to_account_info()),
+            ack_outbound_message_path: None,
+            ack_outbound_message: None,
+            ack_outbound_message_record: None,
+            system_program: None,
+            ack_callback: None,
+            executor_allowlist: None,
+            event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
+            program: ctx.accounts.mailbox.to_account_info(),
         },
         signer_seeds,
     ).with_remaining_accounts(
@@ -308,5 +324,8 @@ pub fn derive_accounts_release_or_mint_tokens<'info>(
         derive_accounts::release_or_mint::OfframpDeriveStage::BuildDynamicAccounts => {
             derive_accounts::release_or_mint::build_dynamic_accounts(ctx, &release_or_mint)
         }
+        derive_accounts::release_or_mint::OfframpDeriveStage::RetrieveDeliverer => {
+            derive_accounts::release_or_mint::retrieve_deliverer(ctx)
+        }
     }
 }
\ No newline at end of file
@@ -14,12 +14,25 @@ pub const SELF_CHAIN_ID: [u8; 32] = [
 
 pub const CONFIG_SEED: &[u8] = b"mailbox_config";
 pub const SENDER_CONFIG_SEED: &[u8] = b"sender_config";
+pub const SENDER_FEE_SCHEDULE_SEED: &[u8] = b"sender_fee_schedule";
+pub const FEE_TOKEN_SEED: &[u8] = b"fee_token";
 pub const OUTBOUND_MESSAGE_PATH_SEED: &[u8] = b"outbound_message_path";
 pub const INBOUND_MESSAGE_PATH_SEED: &[u8] = b"inbound_message_path";
 pub const MESSAGE_SEED: &[u8] = b"message";
 pub const OUTBOUND_MESSAGE: &[u8] = b"outbound_message";
+pub const OUTBOUND_MESSAGE_RECORD_SEED: &[u8] = b"outbound_message_record";
+pub const MESSAGE_BUFFER_SEED: &[u8] = b"message_buffer";
+pub const ACK_CALLBACK_SEED: &[u8] = b"ack_callback";
+pub const EXECUTOR_ALLOWLIST_SEED: &[u8] = b"executor_allowlist";
 pub const ACCOUNT_ROLES_SEED: &[u8] = b"account_roles";
+/// Seed of the PDA a program signs with to act for itself in the mailbox
+pub const MAILBOX_AUTHORITY_SEED: &[u8] = b"mailbox_authority";
 
 pub const MAX_PAYLOAD_SIZE: u32 = 1024 * 1024; // 1MB
 pub const DEFAULT_FEE_PER_BYTE: u64 = 1000; // lamports per byte
 pub const FEE_ADJUSTMET_BASE: u64 = 10000;
+pub const FEE_TOKEN_RATE_BASE: u64 = 1_000_000_000; // lamports per SOL
+pub const MAX_VERIFIERS: usize = 8;
+pub const MAX_EXECUTORS: usize = 16;
+pub const MAX_SENDER_DESTINATIONS: usize = 8;
+pub const MAX_SENDER_RECIPIENTS: usize = 16;
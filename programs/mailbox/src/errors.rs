@@ -49,6 +49,58 @@ pub enum MailboxError {
     PublicSendWithFeeDisabled,
     #[msg("Invalid destination caller")]
     InvalidDestinationCaller,
+    #[msg("Fee calculation overflow")]
+    FeeOverflow,
+    #[msg("Missing fee token account")]
+    MissingFeeTokenAccount,
+    #[msg("Fee token mismatch")]
+    FeeTokenMismatch,
+    #[msg("Invalid deliverer")]
+    InvalidDeliverer,
+    #[msg("Outbound message is neither notarized nor past retention")]
+    OutboundMessageNotClosable,
+    #[msg("Invalid validated payload")]
+    InvalidValidatedPayload,
+    #[msg("Invalid payer")]
+    InvalidPayer,
+    #[msg("Invalid retention period")]
+    InvalidRetention,
+    #[msg("Message expired")]
+    MessageExpired,
+    #[msg("Inbound message path required to handle an ordered message")]
+    MissingInboundMessagePath,
+    #[msg("Message handled out of order")]
+    MessageOutOfOrder,
+    #[msg("Invalid verification policy")]
+    InvalidVerificationPolicy,
+    #[msg("Invalid verifier attestation account")]
+    InvalidVerifierAttestation,
+    #[msg("Payload not verified according to the path verification policy")]
+    PayloadNotVerified,
+    #[msg("Message body must be empty when sent from a message buffer")]
+    InvalidMessageBuffer,
+    #[msg("Outbound path and message accounts required to send the acknowledgement")]
+    MissingAckAccounts,
+    #[msg("Invalid acknowledgement")]
+    InvalidAck,
+    #[msg("Invalid acknowledgement callback")]
+    InvalidAckCallback,
+    #[msg("Invalid executor allowlist")]
+    InvalidExecutorAllowlist,
+    #[msg("Message can only be cancelled by the admin, its recipient or anyone once expired")]
+    MessageNotCancellable,
+    #[msg("Invalid sender allowlist")]
+    InvalidSenderAllowlist,
+    #[msg("Destination chain not allowed for the sender")]
+    DestinationNotAllowed,
+    #[msg("Recipient not allowed for the sender")]
+    RecipientNotAllowed,
+    #[msg("Account already migrated")]
+    AccountAlreadyMigrated,
+    #[msg("Uint256 value exceeds 64 bits")]
+    Uint256Overflow,
+    #[msg("Outbound message too large, reserve it with reserve_outbound_message first")]
+    OutboundMessageNotReserved,
 }
 
 impl From<std::io::Error> for MailboxError {
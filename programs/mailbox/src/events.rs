@@ -1,7 +1,9 @@
 //! Defines all events for the Lombard Mailbox protocol.
 use anchor_lang::prelude::*;
 
-use crate::state::AccountRole;
+use base_token_pool::rate_limiter::RateLimitConfig;
+
+use crate::state::{AccountRole, VerificationPolicy};
 
 #[event]
 pub struct OutboundMessagePathStatusChanged {
@@ -18,9 +20,26 @@ pub struct InboundMessagePathStatusChanged {
     pub enabled: bool,
 }
 
+/// Emitted through a self-CPI, see `emit_cpi!`, so that it is not lost to log truncation.
 #[event]
 pub struct MessageSent {
     pub nonce: u64,
+    pub payload_hash: [u8; 32],
+    pub message_path_identifier: [u8; 32],
+    pub destination_chain_id: [u8; 32],
+    pub sender: [u8; 32],
+    pub recipient: [u8; 32],
+    pub body_length: u64,
+    /// The fee paid, in lamports or in units of the fee mint when paid with a fee token
+    pub fee: u64,
+    pub fee_mint: Option<Pubkey>,
+}
+
+#[event]
+pub struct OutboundMessageClosed {
+    pub nonce: u64,
+    pub payload_hash: [u8; 32],
+    pub payer: Pubkey,
 }
 
 #[event]
@@ -30,9 +49,23 @@ pub struct MessageDelivered {
     pub source_chain_id: [u8; 32],
 }
 
+/// Emitted through a self-CPI, see `emit_cpi!`, so that it is not lost to log truncation.
 #[event]
 pub struct MessageHandled {
     pub payload_hash: [u8; 32],
+    pub nonce: u64,
+    pub message_path_identifier: [u8; 32],
+    pub sender: [u8; 32],
+    pub recipient: [u8; 32],
+    pub body_length: u64,
+    pub handler: Pubkey,
+}
+
+#[event]
+pub struct MessageCancelled {
+    pub payload_hash: [u8; 32],
+    pub cancelled_by: Pubkey,
+    pub reason: String,
 }
 
 #[event]
@@ -42,6 +75,16 @@ pub struct ConfigUpdated {
     pub fee_per_byte: u64,
 }
 
+#[event]
+pub struct OutboundMessageRetentionSet {
+    pub retention: i64,
+}
+
+#[event]
+pub struct DeliveredMessageExpirySet {
+    pub expiry: i64,
+}
+
 #[event]
 pub struct PauserAdded {
     pub pauser: Pubkey,
@@ -79,6 +122,45 @@ pub struct SenderConfigUnset {
     pub sender_program: Pubkey,
 }
 
+#[event]
+pub struct InboundPathOrderingSet {
+    pub source_chain_id: [u8; 32],
+    pub ordered: bool,
+    pub next_sequence: u64,
+}
+
+#[event]
+pub struct InboundPathVerificationSet {
+    pub source_chain_id: [u8; 32],
+    pub policy: VerificationPolicy,
+}
+
+#[event]
+pub struct OutboundPathFeesSet {
+    pub destination_chain_id: [u8; 32],
+    pub sender_program: Option<Pubkey>,
+    pub base_fee: u64,
+    pub fee_per_byte: u64,
+}
+
+#[event]
+pub struct SenderPathFeesUnset {
+    pub destination_chain_id: [u8; 32],
+    pub sender_program: Pubkey,
+}
+
+#[event]
+pub struct FeeTokenSet {
+    pub mint: Pubkey,
+    pub treasury_token_account: Pubkey,
+    pub rate: u64,
+}
+
+#[event]
+pub struct FeeTokenRemoved {
+    pub mint: Pubkey,
+}
+
 #[event]
 pub struct TreasuryChanged {
     pub old_treasury: Pubkey,
@@ -95,3 +177,60 @@ pub struct AccountRoleGranted {
 pub struct AccountRolesRevoked {
     pub account: Pubkey,
 }
+
+#[event]
+pub struct OutboundPathRateLimitsSet {
+    pub destination_chain_id: [u8; 32],
+    pub message_limit: RateLimitConfig,
+    pub bytes_limit: RateLimitConfig,
+}
+
+#[event]
+pub struct SenderRateLimitsSet {
+    pub sender_program: Pubkey,
+    pub message_limit: RateLimitConfig,
+    pub bytes_limit: RateLimitConfig,
+}
+
+#[event]
+pub struct AckSent {
+    pub payload_hash: [u8; 32],
+    pub nonce: u64,
+}
+
+#[event]
+pub struct AckHandled {
+    pub payload_hash: [u8; 32],
+    pub sender: [u8; 32],
+    pub callback_program: Option<Pubkey>,
+}
+
+#[event]
+pub struct AckCallbackSet {
+    pub sender: Pubkey,
+    pub callback_program: Pubkey,
+}
+
+#[event]
+pub struct AckCallbackUnset {
+    pub sender: Pubkey,
+}
+
+#[event]
+pub struct ExecutorAllowlistSet {
+    pub recipient: Pubkey,
+    pub executors: Vec<Pubkey>,
+    pub fallback_delay: i64,
+}
+
+#[event]
+pub struct ExecutorAllowlistRemoved {
+    pub recipient: Pubkey,
+}
+
+#[event]
+pub struct SenderAllowlistsSet {
+    pub sender_program: Pubkey,
+    pub destination_chain_ids: Vec<[u8; 32]>,
+    pub recipients: Vec<[u8; 32]>,
+}
@@ -2,7 +2,9 @@ use anchor_lang::prelude::*;
 
 use crate::constants::CONFIG_SEED;
 use crate::errors::MailboxError;
-use crate::events::{ProgramPaused, TreasuryChanged};
+use crate::events::{
+    DeliveredMessageExpirySet, OutboundMessageRetentionSet, ProgramPaused, TreasuryChanged,
+};
 use crate::state::Config;
 
 #[derive(Accounts)]
@@ -59,6 +61,20 @@ pub fn set_treasury(ctx: Context<Admin>, new_treasury: Pubkey) -> Result<()> {
     Ok(())
 }
 
+pub fn set_outbound_message_retention(ctx: Context<Admin>, retention: i64) -> Result<()> {
+    require!(retention >= 0, MailboxError::InvalidRetention);
+    ctx.accounts.config.outbound_message_retention = retention;
+    emit!(OutboundMessageRetentionSet { retention });
+    Ok(())
+}
+
+pub fn set_delivered_message_expiry(ctx: Context<Admin>, expiry: i64) -> Result<()> {
+    require!(expiry >= 0, MailboxError::InvalidRetention);
+    ctx.accounts.config.delivered_message_expiry = expiry;
+    emit!(DeliveredMessageExpirySet { expiry });
+    Ok(())
+}
+
 pub fn unpause(ctx: Context<Admin>) -> Result<()> {
     let config = &mut ctx.accounts.config;
 
@@ -0,0 +1,94 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{CONFIG_SEED, MESSAGE_SEED};
+use crate::errors::MailboxError;
+use crate::instructions::handle_message::{
+    advance_ordered_path, compact_message_info, load_message_info,
+};
+use crate::state::{Config, InboundMessagePath, MessageState};
+use crate::utils::authority::is_authority;
+
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct CancelMessage<'info> {
+    /// The admin, the recipient itself if it is a wallet, the mailbox authority PDA of the recipient
+    /// program otherwise, or anyone once the message is past the delivered message expiry
+    pub canceller: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Deserialized in the instruction since the account is compacted once cancelled
+    #[account(mut, seeds = [MESSAGE_SEED, &payload_hash], bump)]
+    pub message_info: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the deliverer recorded in the message info, receives the freed rent
+    #[account(mut)]
+    pub deliverer: UncheckedAccount<'info>,
+
+    /// Required for sequenced messages delivered on an ordered path, which moves past the cancelled
+    /// message
+    #[account(mut)]
+    pub inbound_message_path: Option<Account<'info, InboundMessagePath>>,
+}
+
+/// Cancels a delivered message that was not handled, so that it can no longer be handled nor
+/// delivered again. The message info is compacted and its freed rent returned to the deliverer.
+pub fn cancel_message(
+    ctx: Context<CancelMessage>,
+    payload_hash: [u8; 32],
+    reason: String,
+) -> Result<()> {
+    let message_info = load_message_info(&ctx.accounts.message_info)?;
+    require!(
+        message_info.status == MessageState::Delivered,
+        MailboxError::InvalidPayloadState
+    );
+    require_keys_eq!(
+        ctx.accounts.deliverer.key(),
+        message_info.deliverer,
+        MailboxError::InvalidDeliverer
+    );
+
+    let canceller = ctx.accounts.canceller.key();
+    let config = &ctx.accounts.config;
+    let expired = config.delivered_message_expiry > 0
+        && Clock::get()?.unix_timestamp
+            >= message_info
+                .delivered_at
+                .saturating_add(config.delivered_message_expiry);
+    require!(
+        canceller == config.admin
+            || is_authority(&canceller, &Pubkey::from(message_info.message.recipient))
+            || expired,
+        MailboxError::MessageNotCancellable
+    );
+
+    // only the next message of an ordered path can be cancelled, moving the path past it, while a
+    // message the admin already moved the path past is cancelled without moving the path back
+    if let Some(sequence) = message_info.ordered_sequence() {
+        let inbound_message_path = ctx
+            .accounts
+            .inbound_message_path
+            .as_mut()
+            .ok_or(MailboxError::MissingInboundMessagePath)?;
+        if sequence >= inbound_message_path.next_sequence {
+            advance_ordered_path(&message_info.message, sequence, inbound_message_path)?;
+        }
+    }
+
+    compact_message_info(
+        &ctx.accounts.message_info.to_account_info(),
+        &ctx.accounts.deliverer.to_account_info(),
+        payload_hash,
+        MessageState::Cancelled,
+    )?;
+
+    emit!(crate::events::MessageCancelled {
+        payload_hash,
+        cancelled_by: canceller,
+        reason,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,91 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash as sha256;
+use anchor_lang::system_program;
+
+use consortium::constants::VALIDATED_PAYLOAD_SEED;
+
+use crate::constants::{CONFIG_SEED, OUTBOUND_MESSAGE, OUTBOUND_MESSAGE_RECORD_SEED};
+use crate::errors::MailboxError;
+use crate::events::OutboundMessageClosed;
+use crate::state::{Config, OutboundMessageRecord};
+
+#[derive(Accounts)]
+#[instruction(nonce: u64)]
+pub struct CloseOutboundMessage<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Holds the payload as written by `send_message`
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [OUTBOUND_MESSAGE, &nonce.to_be_bytes()],
+        bump
+    )]
+    pub outbound_message: UncheckedAccount<'info>,
+
+    #[account(
+        mut,
+        close = payer,
+        has_one = payer @ MailboxError::InvalidPayer,
+        seeds = [OUTBOUND_MESSAGE_RECORD_SEED, &nonce.to_be_bytes()],
+        bump = outbound_message_record.bump
+    )]
+    pub outbound_message_record: Account<'info, OutboundMessageRecord>,
+
+    /// CHECK: Checked against the payer recorded for the outbound message, receives the rent
+    #[account(mut)]
+    pub payer: UncheckedAccount<'info>,
+
+    /// Proves the consortium notarized the payload, not needed once the retention period is over.
+    /// CHECK: Address and owner are verified in the instruction body
+    pub consortium_validated_payload: Option<UncheckedAccount<'info>>,
+}
+
+/// Closes an outbound message account and its record, returning their rent to the payer who
+/// created them.
+/// Permissionless, as the rent can only go back to the recorded payer.
+pub fn close_outbound_message(ctx: Context<CloseOutboundMessage>, nonce: u64) -> Result<()> {
+    let outbound_message = ctx.accounts.outbound_message.to_account_info();
+
+    let payload_hash = sha256(&outbound_message.try_borrow_data()?).to_bytes();
+    let record = &ctx.accounts.outbound_message_record;
+
+    match &ctx.accounts.consortium_validated_payload {
+        Some(validated_payload) => {
+            let consortium = ctx.accounts.config.consortium;
+            let (expected, _) = Pubkey::find_program_address(
+                &[VALIDATED_PAYLOAD_SEED, &payload_hash],
+                &consortium,
+            );
+            require!(
+                validated_payload.key() == expected
+                    && validated_payload.owner == &consortium
+                    && !validated_payload.data_is_empty(),
+                MailboxError::InvalidValidatedPayload
+            );
+        }
+        None => {
+            let retention = ctx.accounts.config.outbound_message_retention;
+            let now = Clock::get()?.unix_timestamp;
+            require!(
+                retention > 0 && now >= record.sent_at.saturating_add(retention),
+                MailboxError::OutboundMessageNotClosable
+            );
+        }
+    }
+
+    let payer = ctx.accounts.payer.to_account_info();
+    **payer.try_borrow_mut_lamports()? += outbound_message.lamports();
+    **outbound_message.try_borrow_mut_lamports()? = 0;
+    outbound_message.assign(&system_program::ID);
+    outbound_message.realloc(0, false)?;
+
+    emit!(OutboundMessageClosed {
+        nonce,
+        payload_hash,
+        payer: record.payer,
+    });
+
+    Ok(())
+}
@@ -0,0 +1,233 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash as sha256;
+
+use consortium::constants::{SESSION_PAYLOAD_SEED, VALIDATED_PAYLOAD_SEED};
+use consortium::state::{SessionPayload, ValidatedPayload};
+
+use crate::constants::{CONFIG_SEED, MESSAGE_SEED};
+use crate::errors::MailboxError;
+use crate::instructions::deliver_message::decode_inbound_message;
+use crate::instructions::handle_message::{
+    advance_ordered_path, check_destination_caller, check_recipient, compact_message_info,
+    invoke_recipient,
+};
+use crate::state::{
+    Config, ExecutorAllowlist, InboundMessagePath, MessageState, MessageV1Info,
+    OutboundMessagePath,
+};
+use crate::utils::accounts::create_pda_account;
+use crate::utils::ack::{handle_ack, send_ack, AckOutbound};
+use crate::utils::message_utils::MessageV2Fields;
+use crate::utils::verification::{attesting_verifiers, verify_payload};
+
+#[event_cpi]
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct DeliverAndHandleMessage<'info> {
+    /// Delivers and handles the message, so it must be allowed by the destination caller if one is set
+    #[account(mut)]
+    pub deliverer: Signer<'info>,
+    // mutable as sending an acknowledgement uses the global nonce
+    #[account(
+        mut,
+        constraint = !config.paused @ MailboxError::Paused,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: Created in the instruction and compacted once the message is handled,
+    /// its creation fails if the message was already delivered.
+    #[account(mut, seeds = [MESSAGE_SEED, &payload_hash], bump)]
+    pub message_info: UncheckedAccount<'info>,
+
+    /// CHECK: This is the program that will handle the message, checked against the message recipient
+    pub recipient_program: UncheckedAccount<'info>,
+
+    #[account(mut)]
+    pub inbound_message_path: Account<'info, InboundMessagePath>,
+
+    // expects that the deliverer has submitted the full payload to the consortium program
+    #[account(
+        owner = config.consortium,
+        seeds = [SESSION_PAYLOAD_SEED, &deliverer.key.to_bytes()[..], &payload_hash[..]],
+        seeds::program = config.consortium,
+        constraint = (sha256(&consortium_payload.payload).to_bytes() == payload_hash[..]) @ MailboxError::InvalidPayloadHash,
+        bump
+    )]
+    pub consortium_payload: Account<'info, SessionPayload>,
+
+    /// check that the consortium program has validated the payload, if required by the path
+    /// verification policy, the attestations of other verifiers are passed as remaining accounts
+    #[account(
+        owner = config.consortium,
+        seeds = [VALIDATED_PAYLOAD_SEED, &payload_hash[..]],
+        seeds::program = config.consortium,
+        bump
+    )]
+    pub consortium_validated_payload: Option<Account<'info, ValidatedPayload>>,
+
+    pub system_program: Program<'info, System>,
+
+    // The following accounts are required to send back the acknowledgement of a message
+    // requesting one, paid by the deliverer
+    pub ack_outbound_message_path: Option<Account<'info, OutboundMessagePath>>,
+    /// CHECK: Created in the instruction at the outbound message PDA of the current nonce
+    #[account(mut)]
+    pub ack_outbound_message: Option<UncheckedAccount<'info>>,
+    /// CHECK: Created in the instruction at the outbound message record PDA of the current nonce
+    #[account(mut)]
+    pub ack_outbound_message_record: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Required to handle an acknowledgement, checked against the `ACK_CALLBACK_SEED` PDA
+    /// of the sender of the acknowledged message, which may not exist
+    pub ack_callback: Option<UncheckedAccount<'info>>,
+
+    /// Required when the destination caller of the message designates an executor allowlist, see
+    /// `HandleMessage`
+    pub executor_allowlist: Option<Account<'info, ExecutorAllowlist>>,
+}
+
+/// Delivers and handles a message in a single instruction. The message info account only
+/// lives in full for the duration of the recipient call and is left compacted as handled.
+pub fn deliver_and_handle_message<'a, 'b, 'c, 'info>(
+    ctx: Context<'a, 'b, 'c, 'info, DeliverAndHandleMessage<'info>>,
+    payload_hash: [u8; 32],
+) -> Result<Option<Vec<u8>>> {
+    // the attestations of the verifiers come first in the remaining accounts,
+    // followed by the accounts needed by the recipient program
+    let policy = &ctx.accounts.inbound_message_path.verification_policy;
+    let attestation_count = attesting_verifiers(policy, &ctx.accounts.config.consortium).len();
+    require!(
+        ctx.remaining_accounts.len() >= attestation_count,
+        MailboxError::InvalidVerifierAttestation
+    );
+    let (attestations, recipient_accounts) = ctx.remaining_accounts.split_at(attestation_count);
+    verify_payload(
+        policy,
+        &ctx.accounts.config.consortium,
+        ctx.accounts.consortium_validated_payload.is_some(),
+        attestations,
+        &payload_hash,
+    )?;
+
+    let payload = &ctx.accounts.consortium_payload.payload;
+    let (decoded_message, v2_fields) =
+        decode_inbound_message(payload, &ctx.accounts.inbound_message_path)?;
+
+    check_recipient(&decoded_message, ctx.accounts.recipient_program.key)?;
+    // the message is delivered now, so the fallback delay of an executor allowlist never applies
+    let now = Clock::get()?.unix_timestamp;
+    check_destination_caller(
+        &decoded_message,
+        ctx.accounts.deliverer.key,
+        ctx.accounts.executor_allowlist.as_ref(),
+        now,
+        now,
+    )?;
+
+    let sequence = v2_fields.as_ref().and_then(MessageV2Fields::sequence);
+    let ordered = ctx.accounts.inbound_message_path.ordered && sequence.is_some();
+    if let (true, Some(sequence)) = (ordered, sequence) {
+        advance_ordered_path(
+            &decoded_message,
+            sequence,
+            &mut ctx.accounts.inbound_message_path,
+        )?;
+    }
+
+    // the recipient program reads the message from the message info account, so it is
+    // written in full before the CPI
+    let message_info = MessageV1Info {
+        status: MessageState::Delivered,
+        deliverer: ctx.accounts.deliverer.key(),
+        message: decoded_message,
+        v2_fields,
+        ordered,
+        delivered_at: now,
+    };
+    let message_info_account = ctx.accounts.message_info.to_account_info();
+    let space = 8 + MessageV1Info::size(payload.len());
+    create_pda_account(
+        &ctx.accounts.deliverer.to_account_info(),
+        &message_info_account,
+        &ctx.accounts.system_program.to_account_info(),
+        &[MESSAGE_SEED, &payload_hash[..], &[ctx.bumps.message_info]],
+        space,
+    )?;
+    message_info.try_serialize(&mut &mut message_info_account.try_borrow_mut_data()?[..])?;
+
+    emit!(crate::events::MessageDelivered {
+        payload_hash,
+        source_mailbox_address: ctx.accounts.inbound_message_path.source_mailbox_address,
+        source_chain_id: ctx.accounts.inbound_message_path.source_chain_id,
+    });
+
+    // acknowledgements are addressed to the mailbox itself
+    let result_data = match message_info.message.recipient == crate::ID.to_bytes() {
+        true => {
+            handle_ack(
+                &message_info.message,
+                Some(&ctx.accounts.inbound_message_path),
+                ctx.accounts.ack_callback.as_deref(),
+                &message_info_account,
+                recipient_accounts,
+                payload_hash,
+                ctx.bumps.message_info,
+            )?;
+            None
+        }
+        false => invoke_recipient(
+            &message_info_account,
+            ctx.accounts.recipient_program.key,
+            recipient_accounts,
+            payload_hash,
+            ctx.bumps.message_info,
+        )?,
+    };
+
+    if message_info.ack_requested() {
+        let accounts = &mut *ctx.accounts;
+        let (Some(outbound_message_path), Some(outbound_message), Some(outbound_message_record)) = (
+            &accounts.ack_outbound_message_path,
+            &accounts.ack_outbound_message,
+            &accounts.ack_outbound_message_record,
+        ) else {
+            return err!(MailboxError::MissingAckAccounts);
+        };
+        let ack_sent = send_ack(
+            &mut accounts.config,
+            &accounts.inbound_message_path,
+            AckOutbound {
+                outbound_message_path,
+                outbound_message: outbound_message.to_account_info(),
+                outbound_message_record: outbound_message_record.to_account_info(),
+                payer: accounts.deliverer.to_account_info(),
+                system_program: accounts.system_program.to_account_info(),
+            },
+            &message_info.message,
+            payload_hash,
+            result_data.as_deref().unwrap_or_default(),
+        )?;
+        emit_cpi!(ack_sent);
+    }
+
+    compact_message_info(
+        &message_info_account,
+        &ctx.accounts.deliverer.to_account_info(),
+        payload_hash,
+        MessageState::Handled,
+    )?;
+
+    emit_cpi!(crate::events::MessageHandled {
+        payload_hash,
+        nonce: message_info.message.nonce,
+        message_path_identifier: message_info.message.message_path_identifier,
+        sender: message_info.message.sender,
+        recipient: message_info.message.recipient,
+        body_length: message_info.message.body_length() as u64,
+        handler: ctx.accounts.deliverer.key(),
+    });
+
+    Ok(result_data)
+}
@@ -7,7 +7,8 @@ use consortium::state::{SessionPayload, ValidatedPayload};
 use crate::constants::{CONFIG_SEED, MESSAGE_SEED};
 use crate::errors::MailboxError;
 use crate::state::{Config, InboundMessagePath, MessageState, MessageV1Info};
-use crate::utils::message_utils::MessageV1;
+use crate::utils::message_utils::{MessageV1, MessageV2Fields};
+use crate::utils::verification::verify_payload;
 
 #[derive(Accounts)]
 #[instruction(payload_hash: [u8; 32])]
@@ -44,14 +45,15 @@ pub struct DeliverMessage<'info> {
     )]
     pub consortium_payload: Account<'info, SessionPayload>,
 
-    /// check that the consortium program has validated the payload
+    /// check that the consortium program has validated the payload, if required by the path
+    /// verification policy, the attestations of other verifiers are passed as remaining accounts
     #[account(
         owner = config.consortium,
         seeds = [VALIDATED_PAYLOAD_SEED, &payload_hash[..]],
         seeds::program = config.consortium,
         bump
     )]
-    pub consortium_validated_payload: Account<'info, ValidatedPayload>,
+    pub consortium_validated_payload: Option<Account<'info, ValidatedPayload>>,
 
     pub system_program: Program<'info, System>,
 }
@@ -61,19 +63,29 @@ pub fn deliver_message(ctx: Context<DeliverMessage>, payload_hash: [u8; 32]) ->
     // no need to check if the message was already deliverd or handled
     // since the account init would fail if it was already initialized
 
-    let decoded_message =
-        MessageV1::from_session_payload(&ctx.accounts.consortium_payload.payload)?;
+    verify_payload(
+        &ctx.accounts.inbound_message_path.verification_policy,
+        &ctx.accounts.config.consortium,
+        ctx.accounts.consortium_validated_payload.is_some(),
+        ctx.remaining_accounts,
+        &payload_hash,
+    )?;
 
-    require!(
-        decoded_message.message_path_identifier == ctx.accounts.inbound_message_path.identifier,
-        MailboxError::InvalidMessagePath
-    );
+    let (decoded_message, v2_fields) = decode_inbound_message(
+        &ctx.accounts.consortium_payload.payload,
+        &ctx.accounts.inbound_message_path,
+    )?;
+    let sequenced = v2_fields.as_ref().and_then(MessageV2Fields::sequence).is_some();
 
     let message_info = &mut ctx.accounts.message_info;
 
     // Update payload state to delivered
     message_info.status = MessageState::Delivered;
+    message_info.deliverer = ctx.accounts.deliverer.key();
     message_info.message = decoded_message;
+    message_info.v2_fields = v2_fields;
+    message_info.ordered = ctx.accounts.inbound_message_path.ordered && sequenced;
+    message_info.delivered_at = Clock::get()?.unix_timestamp;
 
     emit!(crate::events::MessageDelivered {
         payload_hash,
@@ -83,3 +95,25 @@ pub fn deliver_message(ctx: Context<DeliverMessage>, payload_hash: [u8; 32]) ->
 
     Ok(())
 }
+
+/// Decodes a notarized payload, checking it is not expired and was sent on the inbound path.
+pub(crate) fn decode_inbound_message(
+    payload: &[u8],
+    inbound_message_path: &InboundMessagePath,
+) -> Result<(MessageV1, Option<MessageV2Fields>)> {
+    let (decoded_message, v2_fields) = MessageV1::from_versioned_session_payload(payload)?;
+
+    if let Some(v2_fields) = &v2_fields {
+        require!(
+            !v2_fields.is_expired(Clock::get()?.unix_timestamp),
+            MailboxError::MessageExpired
+        );
+    }
+
+    require!(
+        decoded_message.message_path_identifier == inbound_message_path.identifier,
+        MailboxError::InvalidMessagePath
+    );
+
+    Ok((decoded_message, v2_fields))
+}
@@ -5,7 +5,7 @@ use crate::utils::message_utils::message_path_identifier;
 use crate::{
     constants::{CONFIG_SEED, OUTBOUND_MESSAGE_PATH_SEED, SELF_CHAIN_ID},
     errors::MailboxError,
-    state::{Config, OutboundMessagePath},
+    state::{Config, FeeSchedule, OutboundMessagePath},
     ID,
 };
 
@@ -41,6 +41,11 @@ pub fn enable_outbound_message_path(
     let identifier = message_path_identifier(ID.to_bytes(), SELF_CHAIN_ID, destination_chain_id);
     ctx.accounts.outbound_message_path.identifier = identifier;
     ctx.accounts.outbound_message_path.destination_chain_id = destination_chain_id;
+    // new paths start with the default per-byte fee and no base fee
+    ctx.accounts.outbound_message_path.fee_schedule = FeeSchedule {
+        base_fee: 0,
+        fee_per_byte: ctx.accounts.config.fee_per_byte,
+    };
     emit!(OutboundMessagePathStatusChanged {
         identifier,
         destination_chain_id,
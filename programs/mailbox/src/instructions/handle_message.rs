@@ -1,44 +1,94 @@
+use anchor_lang::error::ErrorCode;
 use anchor_lang::prelude::*;
 use anchor_lang::solana_program::instruction::{AccountMeta, Instruction};
 use anchor_lang::solana_program::program::{get_return_data, invoke_signed};
+use anchor_lang::Discriminator;
 
 use crate::constants::{CONFIG_SEED, MESSAGE_SEED};
 use crate::errors::MailboxError;
-use crate::state::{Config, MessageState, MessageV1Info};
+use crate::state::{
+    Config, ExecutorAllowlist, HandledMessageInfo, InboundMessagePath, MessageState,
+    MessageV1Info, OutboundMessagePath,
+};
 use crate::utils;
+use crate::utils::ack::{handle_ack, send_ack, AckOutbound};
+use crate::utils::message_utils::MessageV1;
 
+#[event_cpi]
 #[derive(Accounts)]
 #[instruction(payload_hash: [u8; 32])]
 pub struct HandleMessage<'info> {
     #[account(mut)]
     pub handler: Signer<'info>,
+    // mutable as sending an acknowledgement uses the global nonce
     #[account(
+        mut,
         constraint = config.paused == false @ MailboxError::Paused,
         seeds = [CONFIG_SEED],
         bump
     )]
     pub config: Account<'info, Config>,
 
+    /// CHECK: Deserialized in the instruction since the account is compacted once handled,
+    /// which anchor would otherwise overwrite when persisting the account on exit.
     #[account(mut, seeds = [MESSAGE_SEED, &payload_hash], bump)]
-    pub message_info: Account<'info, MessageV1Info>,
+    pub message_info: UncheckedAccount<'info>,
 
-    /// CHECK: This is the program that will handle the message
-    #[account(address = message_info.message.recipient.into())]
+    /// CHECK: This is the program that will handle the message, checked against the message recipient
     pub recipient_program: UncheckedAccount<'info>,
+
+    /// CHECK: Checked against the deliverer recorded in the message info, receives the freed rent
+    #[account(mut)]
+    pub deliverer: UncheckedAccount<'info>,
+
+    /// Required for messages delivered on an ordered path, to track the next nonce to handle,
+    /// and for messages requesting or carrying an acknowledgement
+    #[account(mut)]
+    pub inbound_message_path: Option<Account<'info, InboundMessagePath>>,
+
+    // The following accounts are required to send back the acknowledgement of a message
+    // requesting one, paid by the handler
+    pub ack_outbound_message_path: Option<Account<'info, OutboundMessagePath>>,
+    /// CHECK: Created in the instruction at the outbound message PDA of the current nonce
+    #[account(mut)]
+    pub ack_outbound_message: Option<UncheckedAccount<'info>>,
+    /// CHECK: Created in the instruction at the outbound message record PDA of the current nonce
+    #[account(mut)]
+    pub ack_outbound_message_record: Option<UncheckedAccount<'info>>,
+    pub system_program: Option<Program<'info, System>>,
+
+    /// CHECK: Required to handle an acknowledgement, checked against the `ACK_CALLBACK_SEED` PDA
+    /// of the sender of the acknowledged message, which may not exist
+    pub ack_callback: Option<UncheckedAccount<'info>>,
+
+    /// Required when the destination caller of the message designates an executor allowlist, or to
+    /// handle a message with another destination caller once the fallback delay of the allowlist
+    /// of the recipient has passed
+    pub executor_allowlist: Option<Account<'info, ExecutorAllowlist>>,
 }
 
 pub fn handle_message<'a, 'b, 'c, 'info>(
     ctx: Context<'a, 'b, 'c, 'info, HandleMessage<'info>>,
     payload_hash: [u8; 32],
 ) -> Result<Option<Vec<u8>>> {
-    let message_info = &mut ctx.accounts.message_info;
+    let message_info = load_message_info(&ctx.accounts.message_info)?;
 
-    if let Some(destination_caller) = message_info.message.destination_caller {
-        require!(
-            destination_caller == ctx.accounts.handler.key().to_bytes(),
-            MailboxError::InvalidDestinationCaller
-        );
-    }
+    check_recipient(&message_info.message, ctx.accounts.recipient_program.key)?;
+
+    require_keys_eq!(
+        ctx.accounts.deliverer.key(),
+        message_info.deliverer,
+        MailboxError::InvalidDeliverer
+    );
+
+    let now = Clock::get()?.unix_timestamp;
+    check_destination_caller(
+        &message_info.message,
+        ctx.accounts.handler.key,
+        ctx.accounts.executor_allowlist.as_ref(),
+        message_info.delivered_at,
+        now,
+    )?;
 
     // Check payload state
     require!(
@@ -46,18 +96,200 @@ pub fn handle_message<'a, 'b, 'c, 'info>(
         MailboxError::InvalidPayloadState
     );
 
-    // Update payload state to handled
-    message_info.status = MessageState::Handled;
+    require!(!message_info.is_expired(now), MailboxError::MessageExpired);
 
-    // Configure the CPI to handle the message on the recipient program
+    if let Some(sequence) = message_info.ordered_sequence() {
+        let inbound_message_path = ctx
+            .accounts
+            .inbound_message_path
+            .as_mut()
+            .ok_or(MailboxError::MissingInboundMessagePath)?;
+        advance_ordered_path(&message_info.message, sequence, inbound_message_path)?;
+    }
 
+    let message_info_account = ctx.accounts.message_info.to_account_info();
+    // acknowledgements are addressed to the mailbox itself
+    let result_data = match message_info.message.recipient == crate::ID.to_bytes() {
+        true => {
+            handle_ack(
+                &message_info.message,
+                ctx.accounts.inbound_message_path.as_deref(),
+                ctx.accounts.ack_callback.as_deref(),
+                &message_info_account,
+                ctx.remaining_accounts,
+                payload_hash,
+                ctx.bumps.message_info,
+            )?;
+            None
+        }
+        false => invoke_recipient(
+            &message_info_account,
+            ctx.accounts.recipient_program.key,
+            ctx.remaining_accounts,
+            payload_hash,
+            ctx.bumps.message_info,
+        )?,
+    };
+
+    if message_info.ack_requested() {
+        let accounts = &mut *ctx.accounts;
+        let (
+            Some(inbound_message_path),
+            Some(outbound_message_path),
+            Some(outbound_message),
+            Some(outbound_message_record),
+            Some(system_program),
+        ) = (
+            &accounts.inbound_message_path,
+            &accounts.ack_outbound_message_path,
+            &accounts.ack_outbound_message,
+            &accounts.ack_outbound_message_record,
+            &accounts.system_program,
+        ) else {
+            return err!(MailboxError::MissingAckAccounts);
+        };
+        let ack_sent = send_ack(
+            &mut accounts.config,
+            inbound_message_path,
+            AckOutbound {
+                outbound_message_path,
+                outbound_message: outbound_message.to_account_info(),
+                outbound_message_record: outbound_message_record.to_account_info(),
+                payer: accounts.handler.to_account_info(),
+                system_program: system_program.to_account_info(),
+            },
+            &message_info.message,
+            payload_hash,
+            result_data.as_deref().unwrap_or_default(),
+        )?;
+        emit_cpi!(ack_sent);
+    }
+
+    // Update payload state to handled, keeping only what is needed to prevent redelivery
+    compact_message_info(
+        &message_info_account,
+        &ctx.accounts.deliverer.to_account_info(),
+        payload_hash,
+        MessageState::Handled,
+    )?;
+
+    emit_cpi!(crate::events::MessageHandled {
+        payload_hash,
+        nonce: message_info.message.nonce,
+        message_path_identifier: message_info.message.message_path_identifier,
+        sender: message_info.message.sender,
+        recipient: message_info.message.recipient,
+        body_length: message_info.message.body_length() as u64,
+        handler: ctx.accounts.handler.key(),
+    });
+
+    Ok(result_data)
+}
+
+pub(crate) fn check_recipient(message: &MessageV1, recipient_program: &Pubkey) -> Result<()> {
+    if message.recipient != recipient_program.to_bytes() {
+        return Err(error!(ErrorCode::ConstraintAddress)
+            .with_account_name("recipient_program")
+            .with_pubkeys((*recipient_program, Pubkey::from(message.recipient))));
+    }
+    Ok(())
+}
+
+/// Checks the handler is allowed by the destination caller of the message, if any. The destination
+/// caller is either the handler itself or an executor allowlist of the recipient listing the handler.
+/// Once the fallback delay of the allowlist of the recipient has passed since delivery, anyone can
+/// handle the message, whichever the destination caller.
+pub(crate) fn check_destination_caller(
+    message: &MessageV1,
+    handler: &Pubkey,
+    executor_allowlist: Option<&Account<ExecutorAllowlist>>,
+    delivered_at: i64,
+    now: i64,
+) -> Result<()> {
+    let Some(destination_caller) = message.destination_caller else {
+        return Ok(());
+    };
+    if destination_caller == handler.to_bytes() {
+        return Ok(());
+    }
+    if let Some(allowlist) = executor_allowlist {
+        require!(
+            allowlist.recipient.to_bytes() == message.recipient,
+            MailboxError::InvalidExecutorAllowlist
+        );
+        let designated = allowlist.key().to_bytes() == destination_caller;
+        let fallback_open = allowlist.fallback_delay > 0
+            && now >= delivered_at.saturating_add(allowlist.fallback_delay);
+        if (designated && allowlist.executors.contains(handler)) || fallback_open {
+            return Ok(());
+        }
+    }
+    err!(MailboxError::InvalidDestinationCaller)
+}
+
+/// Requires the sequenced message to be the next one of an ordered path and moves the path past
+/// it. The source mailbox numbers the sequenced messages of a path without gaps, so a message is
+/// only skipped by cancelling it or by the admin setting the next sequence of the path.
+pub(crate) fn advance_ordered_path(
+    message: &MessageV1,
+    sequence: u64,
+    inbound_message_path: &mut InboundMessagePath,
+) -> Result<()> {
+    require!(
+        inbound_message_path.identifier == message.message_path_identifier,
+        MailboxError::InvalidMessagePath
+    );
+    require!(
+        sequence == inbound_message_path.next_sequence,
+        MailboxError::MessageOutOfOrder
+    );
+    inbound_message_path.next_sequence = sequence.checked_add(1).unwrap();
+    Ok(())
+}
+
+/// Invokes `gmp_receive` on the recipient program and returns the data it returned, if any.
+pub(crate) fn invoke_recipient<'info>(
+    message_info: &AccountInfo<'info>,
+    recipient_program: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    payload_hash: [u8; 32],
+    message_info_bump: u8,
+) -> Result<Option<Vec<u8>>> {
+    invoke_with_message_info(
+        message_info,
+        recipient_program,
+        remaining_accounts,
+        utils::cpi::gmp_receive_instr_data(payload_hash),
+        payload_hash,
+        message_info_bump,
+    )?;
+
+    let result_data= match get_return_data() {
+        Some(res) => if &res.0 == recipient_program {
+            Some(res.1)
+        } else { None },
+        None => None
+    };
+
+    Ok(result_data)
+}
+
+/// Invokes a program with the message info signing the call, followed by the given accounts.
+pub(crate) fn invoke_with_message_info<'info>(
+    message_info: &AccountInfo<'info>,
+    program_id: &Pubkey,
+    remaining_accounts: &[AccountInfo<'info>],
+    data: Vec<u8>,
+    payload_hash: [u8; 32],
+    message_info_bump: u8,
+) -> Result<()> {
     // the message info is the account signing the CPI to the recipient program
     // this provides to the recipient program the proof that the message is legitimate
     let mut accounts = vec![AccountMeta::new_readonly(message_info.key(), true)];
-    let mut account_infos = vec![message_info.to_account_info()];
+    let mut account_infos = vec![message_info.clone()];
 
     // the remaining accounts are the accounts needed to handle the message on the recipient program
-    ctx.remaining_accounts.iter().for_each(|a| {
+    remaining_accounts.iter().for_each(|a| {
         accounts.push(match a.is_writable {
             true => AccountMeta::new(a.key(), a.is_signer),
             false => AccountMeta::new_readonly(a.key(), a.is_signer),
@@ -66,28 +298,50 @@ pub fn handle_message<'a, 'b, 'c, 'info>(
     });
 
     let instruction = Instruction {
-        program_id: ctx.accounts.recipient_program.key(),
+        program_id: *program_id,
         accounts,
-        data: utils::cpi::gmp_receive_instr_data(payload_hash),
+        data,
     };
 
     invoke_signed(
         &instruction,
         &account_infos,
-        &[&[MESSAGE_SEED, &payload_hash[..], &[ctx.bumps.message_info]]],
+        &[&[MESSAGE_SEED, &payload_hash[..], &[message_info_bump]]],
     )?;
+    Ok(())
+}
 
-    emit!(crate::events::MessageHandled { payload_hash });
+pub(crate) fn load_message_info(account: &UncheckedAccount) -> Result<MessageV1Info> {
+    if account.data_is_empty() {
+        return Err(error!(ErrorCode::AccountNotInitialized).with_account_name("message_info"));
+    }
+    let data = account.try_borrow_data()?;
+    if data.starts_with(HandledMessageInfo::DISCRIMINATOR) {
+        return err!(MailboxError::InvalidPayloadState);
+    }
+    MessageV1Info::try_deserialize(&mut &data[..])
+}
 
-    let result_data= match get_return_data() {
-        Some(res) => if &res.0 == ctx.accounts.recipient_program.key {
-            Some(res.1)
-        } else { None },
-        None => None
+/// Shrinks the message info account to a `HandledMessageInfo` with the final status of the
+/// message and returns the rent no longer needed to the deliverer who paid for it.
+pub(crate) fn compact_message_info<'info>(
+    message_info: &AccountInfo<'info>,
+    deliverer: &AccountInfo<'info>,
+    payload_hash: [u8; 32],
+    status: MessageState,
+) -> Result<()> {
+    let handled = HandledMessageInfo {
+        status,
+        payload_hash,
     };
+    let new_len = 8 + HandledMessageInfo::INIT_SPACE;
+    message_info.realloc(new_len, false)?;
+    handled.try_serialize(&mut &mut message_info.try_borrow_mut_data()?[..])?;
 
-    // todo: we could resize the message info account to save on-chain space and only
-    // store the handled status discarding the message body
-
-    Ok(result_data)
+    let refund = message_info
+        .lamports()
+        .saturating_sub(Rent::get()?.minimum_balance(new_len));
+    **message_info.try_borrow_mut_lamports()? -= refund;
+    **deliverer.try_borrow_mut_lamports()? += refund;
+    Ok(())
 }
@@ -46,5 +46,7 @@ pub fn initialize(
     config.global_nonce = 1; // Lombard ledger requires all nonces to be greater than 0
     config.default_max_payload_size = default_max_payload_size;
     config.fee_per_byte = fee_per_byte;
+    config.outbound_message_retention = 0;
+    config.delivered_message_expiry = 0;
     Ok(())
 }
@@ -0,0 +1,84 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{MAX_PAYLOAD_SIZE, MESSAGE_BUFFER_SEED};
+use crate::errors::MailboxError;
+use crate::state::MessageBuffer;
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64)]
+pub struct InitMessageBuffer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        init,
+        payer = payer,
+        space = MessageBuffer::BODY_OFFSET,
+        seeds = [MESSAGE_BUFFER_SEED, owner.key.as_ref(), &buffer_id.to_be_bytes()],
+        bump
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn init_message_buffer(ctx: Context<InitMessageBuffer>, _buffer_id: u64) -> Result<()> {
+    ctx.accounts.message_buffer.bump = ctx.bumps.message_buffer;
+    ctx.accounts.message_buffer.owner = ctx.accounts.owner.key();
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64, chunk: Vec<u8>)]
+pub struct WriteMessageBuffer<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        seeds = [MESSAGE_BUFFER_SEED, owner.key.as_ref(), &buffer_id.to_be_bytes()],
+        bump = message_buffer.bump,
+        realloc = message_buffer.to_account_info().data_len() + chunk.len(),
+        realloc::payer = payer,
+        realloc::zero = false,
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Appends a chunk to the body staged in the buffer.
+pub fn write_message_buffer(
+    ctx: Context<WriteMessageBuffer>,
+    _buffer_id: u64,
+    chunk: Vec<u8>,
+) -> Result<()> {
+    let info = ctx.accounts.message_buffer.to_account_info();
+    require!(
+        MessageBuffer::body_len(&info) <= MAX_PAYLOAD_SIZE as usize,
+        MailboxError::PayloadTooLarge
+    );
+    let mut data = info.try_borrow_mut_data()?;
+    let start = data.len() - chunk.len();
+    data[start..].copy_from_slice(&chunk);
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(buffer_id: u64)]
+pub struct CloseMessageBuffer<'info> {
+    pub owner: Signer<'info>,
+    #[account(
+        mut,
+        close = receiver,
+        seeds = [MESSAGE_BUFFER_SEED, owner.key.as_ref(), &buffer_id.to_be_bytes()],
+        bump = message_buffer.bump,
+    )]
+    pub message_buffer: Account<'info, MessageBuffer>,
+    /// CHECK: Chosen by the owner to receive the rent of the buffer
+    #[account(mut)]
+    pub receiver: UncheckedAccount<'info>,
+}
+
+/// Discards a buffer that will not be sent, sending it closes the buffer already.
+pub fn close_message_buffer(_ctx: Context<CloseMessageBuffer>, _buffer_id: u64) -> Result<()> {
+    Ok(())
+}
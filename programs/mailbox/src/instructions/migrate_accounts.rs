@@ -0,0 +1,270 @@
+//! Migrates accounts created before fields were appended to their layout. Appended fields start
+//! out zeroed, which leaves the features they configure disabled, except for the fee schedule of
+//! outbound paths which keeps charging the fee per byte of the config.
+//!
+//! The config must be migrated first, as the other migrations read the admin from it.
+use anchor_lang::{
+    prelude::*,
+    system_program::{self, Transfer},
+    Discriminator,
+};
+
+use crate::{
+    constants::{
+        CONFIG_SEED, INBOUND_MESSAGE_PATH_SEED, MESSAGE_SEED, OUTBOUND_MESSAGE_PATH_SEED,
+        SENDER_CONFIG_SEED,
+    },
+    errors::MailboxError,
+    state::{
+        Config, InboundMessagePath, LegacyMessageV1Info, MessageState, MessageV1Info,
+        OutboundMessagePath, SenderConfig,
+    },
+};
+
+use super::handle_message::compact_message_info;
+
+#[derive(Accounts)]
+pub struct MigrateConfig<'info> {
+    #[account(mut)]
+    pub admin: Signer<'info>,
+    /// CHECK: The seeds constraint ensures the correct address is passed. The account can't be
+    /// decoded as `Config` yet, so the legacy layout and the admin are checked in the handler.
+    #[account(mut, owner = crate::ID, seeds = [CONFIG_SEED], bump)]
+    pub config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+    let config_info = ctx.accounts.config.to_account_info();
+    check_legacy_layout(&config_info, Config::DISCRIMINATOR, Config::LEGACY_SPACE)?;
+    // the admin is the first field of the config
+    let admin = Pubkey::try_from(&config_info.try_borrow_data()?[8..40]).unwrap();
+    require_keys_eq!(
+        admin,
+        ctx.accounts.admin.key(),
+        MailboxError::Unauthorized
+    );
+
+    grow_account(
+        &config_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + Config::INIT_SPACE,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32])]
+pub struct MigrateOutboundMessagePath<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [OUTBOUND_MESSAGE_PATH_SEED, &destination_chain_id],
+        bump
+    )]
+    pub outbound_message_path: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_outbound_message_path(
+    ctx: Context<MigrateOutboundMessagePath>,
+    _destination_chain_id: [u8; 32],
+) -> Result<()> {
+    let path_info = ctx.accounts.outbound_message_path.to_account_info();
+    check_legacy_layout(
+        &path_info,
+        OutboundMessagePath::DISCRIMINATOR,
+        OutboundMessagePath::LEGACY_SPACE,
+    )?;
+    grow_account(
+        &path_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + OutboundMessagePath::INIT_SPACE,
+    )?;
+
+    // legacy paths were charged the fee per byte of the config
+    let mut path = OutboundMessagePath::try_deserialize(&mut &path_info.try_borrow_data()?[..])?;
+    path.fee_schedule.fee_per_byte = ctx.accounts.config.fee_per_byte;
+    path.try_serialize(&mut &mut path_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+#[derive(Accounts)]
+#[instruction(source_chain_id: [u8; 32])]
+pub struct MigrateInboundMessagePath<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(
+        mut,
+        owner = crate::ID,
+        seeds = [INBOUND_MESSAGE_PATH_SEED, &source_chain_id],
+        bump
+    )]
+    pub inbound_message_path: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_inbound_message_path(
+    ctx: Context<MigrateInboundMessagePath>,
+    _source_chain_id: [u8; 32],
+) -> Result<()> {
+    let path_info = ctx.accounts.inbound_message_path.to_account_info();
+    check_legacy_layout(
+        &path_info,
+        InboundMessagePath::DISCRIMINATOR,
+        InboundMessagePath::LEGACY_SPACE,
+    )?;
+    grow_account(
+        &path_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + InboundMessagePath::INIT_SPACE,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct MigrateSenderConfig<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(mut, owner = crate::ID, seeds = [SENDER_CONFIG_SEED, sender.as_ref()], bump)]
+    pub sender_config: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn migrate_sender_config(ctx: Context<MigrateSenderConfig>, _sender: Pubkey) -> Result<()> {
+    let sender_config_info = ctx.accounts.sender_config.to_account_info();
+    check_legacy_layout(
+        &sender_config_info,
+        SenderConfig::DISCRIMINATOR,
+        SenderConfig::LEGACY_SPACE,
+    )?;
+    grow_account(
+        &sender_config_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + SenderConfig::INIT_SPACE,
+    )
+}
+
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct MigrateMessageInfo<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// CHECK: The seeds constraint ensures the correct address is passed, the legacy layout is
+    /// checked in the handler.
+    #[account(mut, owner = crate::ID, seeds = [MESSAGE_SEED, &payload_hash], bump)]
+    pub message_info: UncheckedAccount<'info>,
+    pub system_program: Program<'info, System>,
+}
+
+/// Handled messages are compacted like they are when handled now, returning the rent no longer
+/// needed to the admin. Messages still to be handled get the admin as deliverer, who pays for the
+/// extra space and is refunded when the message is handled, and start their delivery timers now.
+pub fn migrate_message_info(ctx: Context<MigrateMessageInfo>, payload_hash: [u8; 32]) -> Result<()> {
+    let message_info = ctx.accounts.message_info.to_account_info();
+    let legacy = {
+        let data = message_info.try_borrow_data()?;
+        require!(
+            data.starts_with(MessageV1Info::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+        let legacy = LegacyMessageV1Info::deserialize(&mut &data[8..])?;
+        // the current layout has fields after the ones sized by the payload
+        require_eq!(
+            data.len(),
+            8 + 1 + legacy.message.to_session_payload().len(),
+            MailboxError::AccountAlreadyMigrated
+        );
+        legacy
+    };
+
+    if legacy.status == MessageState::Handled {
+        return compact_message_info(
+            &message_info,
+            &ctx.accounts.admin.to_account_info(),
+            payload_hash,
+            MessageState::Handled,
+        );
+    }
+
+    let payload_len = legacy.message.to_session_payload().len();
+    let migrated = MessageV1Info {
+        status: legacy.status,
+        message: legacy.message,
+        deliverer: ctx.accounts.admin.key(),
+        v2_fields: None,
+        ordered: false,
+        delivered_at: Clock::get()?.unix_timestamp,
+    };
+    grow_account(
+        &message_info,
+        &ctx.accounts.admin,
+        &ctx.accounts.system_program,
+        8 + MessageV1Info::size(payload_len),
+    )?;
+    migrated.try_serialize(&mut &mut message_info.try_borrow_mut_data()?[..])?;
+    Ok(())
+}
+
+fn check_legacy_layout(
+    account: &AccountInfo,
+    discriminator: &[u8],
+    legacy_space: usize,
+) -> Result<()> {
+    let data = account.try_borrow_data()?;
+    require!(
+        data.starts_with(discriminator),
+        ErrorCode::AccountDiscriminatorMismatch
+    );
+    require_eq!(
+        data.len(),
+        8 + legacy_space,
+        MailboxError::AccountAlreadyMigrated
+    );
+    Ok(())
+}
+
+/// Reallocates the account to its current size, zeroing the appended space, and tops up its rent.
+fn grow_account<'info>(
+    account: &AccountInfo<'info>,
+    payer: &Signer<'info>,
+    system_program: &Program<'info, System>,
+    new_len: usize,
+) -> Result<()> {
+    let missing = Rent::get()?
+        .minimum_balance(new_len)
+        .saturating_sub(account.lamports());
+    if missing > 0 {
+        system_program::transfer(
+            CpiContext::new(
+                system_program.to_account_info(),
+                Transfer {
+                    from: payer.to_account_info(),
+                    to: account.clone(),
+                },
+            ),
+            missing,
+        )?;
+    }
+    account.realloc(new_len, true)?;
+    Ok(())
+}
@@ -2,6 +2,12 @@ pub mod accept_ownership;
 pub use accept_ownership::*;
 pub mod admin;
 pub use admin::*;
+pub mod cancel_message;
+pub use cancel_message::*;
+pub mod close_outbound_message;
+pub use close_outbound_message::*;
+pub mod deliver_and_handle_message;
+pub use deliver_and_handle_message::*;
 pub mod deliver_message;
 pub use deliver_message::*;
 pub mod disable_inbound_message_path;
@@ -18,13 +24,51 @@ pub mod initialize;
 pub use initialize::*;
 pub mod handle_message;
 pub use handle_message::*;
+pub mod message_buffer;
+pub use message_buffer::*;
+pub mod migrate_accounts;
+pub use migrate_accounts::*;
 pub mod pause;
 pub use pause::*;
+pub mod quote_fee;
+pub use quote_fee::*;
+pub mod remove_fee_token;
+pub use remove_fee_token::*;
+pub mod remove_executor_allowlist;
+pub use remove_executor_allowlist::*;
+pub mod reserve_outbound_message;
+pub use reserve_outbound_message::*;
 pub mod revoke_account_roles;
 pub use revoke_account_roles::*;
 pub mod send_message;
 pub use send_message::*;
+pub mod set_ack_callback;
+pub use set_ack_callback::*;
+pub mod set_executor_allowlist;
+pub use set_executor_allowlist::*;
+pub mod set_fee_token;
+pub use set_fee_token::*;
+pub mod set_inbound_path_ordering;
+pub use set_inbound_path_ordering::*;
+pub mod set_inbound_path_verification;
+pub use set_inbound_path_verification::*;
+pub mod set_outbound_path_fees;
+pub use set_outbound_path_fees::*;
+pub mod set_outbound_path_rate_limits;
+pub use set_outbound_path_rate_limits::*;
+pub mod set_sender_allowlists;
+pub use set_sender_allowlists::*;
 pub mod set_sender_config;
 pub use set_sender_config::*;
+pub mod set_sender_path_fees;
+pub use set_sender_path_fees::*;
+pub mod set_sender_rate_limits;
+pub use set_sender_rate_limits::*;
+pub mod unset_ack_callback;
+pub use unset_ack_callback::*;
 pub mod unset_sender_config;
 pub use unset_sender_config::*;
+pub mod unset_sender_path_fees;
+pub use unset_sender_path_fees::*;
+pub mod views;
+pub use views::*;
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{
+        FEE_TOKEN_SEED, OUTBOUND_MESSAGE_PATH_SEED, SENDER_CONFIG_SEED, SENDER_FEE_SCHEDULE_SEED,
+    },
+    state::{FeeToken, OutboundMessagePath, SenderConfig, SenderFeeSchedule},
+    utils::fees::{message_fee, token_fee},
+};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32], sender: Pubkey)]
+pub struct QuoteFee<'info> {
+    #[account(
+        seeds = [
+            OUTBOUND_MESSAGE_PATH_SEED,
+            &destination_chain_id,
+        ],
+        bump
+    )]
+    pub outbound_message_path: Account<'info, OutboundMessagePath>,
+    #[account(
+        seeds = [SENDER_CONFIG_SEED, &sender.to_bytes()],
+        bump = sender_config.bump
+    )]
+    pub sender_config: Option<Account<'info, SenderConfig>>,
+    #[account(
+        seeds = [SENDER_FEE_SCHEDULE_SEED, &destination_chain_id, &sender.to_bytes()],
+        bump = sender_fee_schedule.bump
+    )]
+    pub sender_fee_schedule: Option<Account<'info, SenderFeeSchedule>>,
+    #[account(seeds = [FEE_TOKEN_SEED, fee_token.mint.as_ref()], bump = fee_token.bump)]
+    pub fee_token: Option<Account<'info, FeeToken>>,
+}
+
+/// Returns, via return data, the fee `send_message` would charge the given sender
+/// for a message body of `body_length` bytes, denominated in the fee token when one is given.
+pub fn quote_fee(
+    ctx: Context<QuoteFee>,
+    _destination_chain_id: [u8; 32],
+    _sender: Pubkey,
+    body_length: u32,
+    fee_override: u64,
+) -> Result<u64> {
+    let fee_disabled = ctx
+        .accounts
+        .sender_config
+        .as_ref()
+        .is_some_and(|c| c.fee_disabled);
+    let fee = message_fee(
+        &ctx.accounts.outbound_message_path.fee_schedule,
+        ctx.accounts.sender_fee_schedule.as_ref().map(|s| &s.fee_schedule),
+        fee_disabled,
+        fee_override,
+        body_length as usize,
+    )?;
+    match &ctx.accounts.fee_token {
+        Some(fee_token) => Ok(token_fee(fee, fee_token.rate)?),
+        None => Ok(fee),
+    }
+}
@@ -0,0 +1,31 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::EXECUTOR_ALLOWLIST_SEED;
+use crate::utils::authority::check_authority;
+use crate::{events::ExecutorAllowlistRemoved, state::ExecutorAllowlist};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct RemoveExecutorAllowlist<'info> {
+    /// The recipient authority, see `SetExecutorAllowlist`
+    #[account(mut)]
+    pub recipient_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = recipient_authority,
+        seeds = [EXECUTOR_ALLOWLIST_SEED, recipient.as_ref()],
+        bump = executor_allowlist.bump
+    )]
+    pub executor_allowlist: Account<'info, ExecutorAllowlist>,
+}
+
+/// Removes the allowlist, messages designating it cannot be handled until it is set again.
+pub fn remove_executor_allowlist(
+    ctx: Context<RemoveExecutorAllowlist>,
+    recipient: Pubkey,
+) -> Result<()> {
+    check_authority(&ctx.accounts.recipient_authority, &recipient)?;
+    emit!(ExecutorAllowlistRemoved { recipient });
+    Ok(())
+}
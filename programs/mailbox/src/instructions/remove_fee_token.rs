@@ -0,0 +1,29 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, FEE_TOKEN_SEED},
+    errors::MailboxError,
+    events::FeeTokenRemoved,
+    state::{Config, FeeToken},
+};
+
+#[derive(Accounts)]
+#[instruction(mint: Pubkey)]
+pub struct RemoveFeeToken<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [FEE_TOKEN_SEED, mint.as_ref()],
+        bump = fee_token.bump
+    )]
+    pub fee_token: Account<'info, FeeToken>,
+}
+
+pub fn remove_fee_token(_ctx: Context<RemoveFeeToken>, mint: Pubkey) -> Result<()> {
+    emit!(FeeTokenRemoved { mint });
+    Ok(())
+}
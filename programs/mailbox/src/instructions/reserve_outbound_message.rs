@@ -0,0 +1,71 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+
+use crate::constants::{CONFIG_SEED, MAX_PAYLOAD_SIZE, OUTBOUND_MESSAGE};
+use crate::errors::MailboxError;
+use crate::state::Config;
+use crate::utils::accounts::{create_pda_account, resize_pda_account};
+use crate::utils::message_utils::{MessageV1, MessageV2Fields};
+
+#[derive(Accounts)]
+pub struct ReserveOutboundMessage<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    #[account(
+        constraint = !config.paused @ MailboxError::Paused,
+        seeds = [CONFIG_SEED],
+        bump
+    )]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The outbound message account of the next message, created or grown here and
+    /// written by `send_message`
+    #[account(
+        mut,
+        seeds = [OUTBOUND_MESSAGE, &config.global_nonce.to_be_bytes()],
+        bump
+    )]
+    pub outbound_message: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Grows the outbound message account of the next message towards `space` bytes, by at most
+/// 10 KiB per instruction, so that `send_message` can write messages larger than an instruction
+/// can allocate. Meant to precede `send_message` in the same transaction, as any sender can
+/// use the account otherwise. `send_message` resizes the account to the payload, refunding
+/// its fee payer any rent no longer needed.
+pub fn reserve_outbound_message(ctx: Context<ReserveOutboundMessage>, space: u64) -> Result<()> {
+    let max_space = MessageV1::accountable_abi_bytes(MAX_PAYLOAD_SIZE as usize)
+        + MessageV2Fields::V3_ABI_BYTES;
+    require!(space <= max_space, MailboxError::PayloadTooLarge);
+
+    let outbound_message = ctx.accounts.outbound_message.to_account_info();
+    if outbound_message.owner != &crate::ID {
+        let nonce_bytes = ctx.accounts.config.global_nonce.to_be_bytes();
+        return create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &outbound_message,
+            &ctx.accounts.system_program.to_account_info(),
+            &[
+                OUTBOUND_MESSAGE,
+                &nonce_bytes,
+                &[ctx.bumps.outbound_message],
+            ],
+            (space as usize).min(MAX_PERMITTED_DATA_INCREASE),
+        );
+    }
+
+    let current_len = outbound_message.data_len();
+    let new_len = (space as usize).min(current_len + MAX_PERMITTED_DATA_INCREASE);
+    if new_len <= current_len {
+        return Ok(());
+    }
+    resize_pda_account(
+        &ctx.accounts.payer.to_account_info(),
+        &outbound_message,
+        &ctx.accounts.system_program.to_account_info(),
+        new_len,
+    )
+}
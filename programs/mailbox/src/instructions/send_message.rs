@@ -1,16 +1,36 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::entrypoint::MAX_PERMITTED_DATA_INCREASE;
+use anchor_lang::solana_program::hash::hash as sha256;
 use anchor_lang::solana_program::program::invoke;
 use anchor_lang::solana_program::system_instruction::transfer;
+use anchor_spl::token_interface::{Mint, TokenInterface};
+use base_token_pool::rate_limiter::RateLimitTokenBucket;
 use solana_address::bytes_are_curve_point;
 
-use crate::constants::{CONFIG_SEED, FEE_ADJUSTMET_BASE, OUTBOUND_MESSAGE, SENDER_CONFIG_SEED};
+use crate::constants::{
+    CONFIG_SEED, FEE_TOKEN_SEED, OUTBOUND_MESSAGE, OUTBOUND_MESSAGE_RECORD_SEED,
+    SENDER_CONFIG_SEED, SENDER_FEE_SCHEDULE_SEED,
+};
 use crate::errors::MailboxError;
 use crate::utils::message_utils::SendResult;
-use crate::state::{Config, OutboundMessagePath, SenderConfig};
-use crate::utils::message_utils::MessageV1;
+use crate::state::{
+    Config, FeeToken, MessageBuffer, OutboundMessagePath, OutboundMessageRecord, SenderConfig,
+    SenderFeeSchedule,
+};
+use crate::utils::accounts::{create_pda_account, resize_pda_account};
+use crate::utils::fees::{message_fee, token_fee};
+use crate::utils::message_utils::{MessageV1, MessageV2Fields, SendOptions};
 
+#[event_cpi]
 #[derive(Accounts)]
-#[instruction(message_body: Vec<u8>)]
+#[instruction(
+    message_body: Vec<u8>,
+    recipient: [u8; 32],
+    destination_caller: Option<[u8; 32]>,
+    fee_override: u64,
+    options: Option<SendOptions>,
+    request_ack: bool
+)]
 pub struct SendMessage<'info> {
     #[account(mut)]
     pub fee_payer: Signer<'info>,
@@ -26,25 +46,46 @@ pub struct SendMessage<'info> {
     )]
     pub config: Account<'info, Config>,
 
+    #[account(mut)]
     pub outbound_message_path: Account<'info, OutboundMessagePath>,
 
+    /// A body staged with `write_message_buffer`, sent in place of `message_body` which must be empty.
+    /// The buffer is closed to the fee payer once sent.
+    #[account(
+        mut,
+        constraint = message_buffer.owner == sender_authority.key() @ MailboxError::InvalidMessageBuffer
+    )]
+    pub message_buffer: Option<Account<'info, MessageBuffer>>,
+
     /// CHECK: This will hold the GMP message suitable for notarization by consortium to be used as-is
-    /// so the mailbox program handles its data
+    /// so the mailbox program handles its data. Created in the instruction unless it was reserved
+    /// with `reserve_outbound_message`, which messages over 10 KiB need.
     #[account(
-        init,
-        payer = fee_payer,
-        space = MessageV1::accountable_abi_bytes(message_body.len()) as usize,
+        mut,
         seeds = [OUTBOUND_MESSAGE, &config.global_nonce.to_be_bytes()],
         bump
     )]
     pub outbound_message: UncheckedAccount<'info>,
 
+    /// Records the payer of the outbound message and when it was sent, to close both later
+    #[account(
+        init,
+        payer = fee_payer,
+        space = 8 + OutboundMessageRecord::INIT_SPACE,
+        seeds = [OUTBOUND_MESSAGE_RECORD_SEED, &config.global_nonce.to_be_bytes()],
+        bump
+    )]
+    pub outbound_message_record: Account<'info, OutboundMessageRecord>,
+
     pub system_program: Program<'info, System>,
 
     #[account(mut)]
     pub treasury: Option<UncheckedAccount<'info>>,
 
+    /// CHECK: The config of the sender, required whether it exists or not so that its limits and
+    /// allowlists can't be skipped, loaded in the instruction. Senders without one get the defaults.
     #[account(
+        mut,
         seeds = [
             SENDER_CONFIG_SEED,
             if bytes_are_curve_point(sender_authority.key.as_ref()) || sender_authority.data_is_empty() {
@@ -55,28 +96,79 @@ pub struct SendMessage<'info> {
         ],
         bump
     )]
-    pub sender_config: Option<Account<'info, SenderConfig>>,
+    pub sender_config: UncheckedAccount<'info>,
+
+    #[account(
+        seeds = [
+            SENDER_FEE_SCHEDULE_SEED,
+            &outbound_message_path.destination_chain_id,
+            if bytes_are_curve_point(sender_authority.key.as_ref()) || sender_authority.data_is_empty() {
+                sender_authority.key.as_ref()
+            } else {
+                sender_authority.owner.as_ref()
+            }
+        ],
+        bump = sender_fee_schedule.bump
+    )]
+    pub sender_fee_schedule: Option<Account<'info, SenderFeeSchedule>>,
+
+    // The following accounts are provided to pay the fee with an approved SPL token
+    // instead of lamports. The fee payer is the authority of the fee payer token account.
+    #[account(seeds = [FEE_TOKEN_SEED, fee_token.mint.as_ref()], bump = fee_token.bump)]
+    pub fee_token: Option<Account<'info, FeeToken>>,
+    pub fee_mint: Option<InterfaceAccount<'info, Mint>>,
+    /// CHECK: Verified by the token program during the transfer
+    #[account(mut)]
+    pub fee_payer_token_account: Option<UncheckedAccount<'info>>,
+    /// CHECK: Checked against the fee token configuration
+    #[account(mut)]
+    pub treasury_token_account: Option<UncheckedAccount<'info>>,
+    pub fee_token_program: Option<Interface<'info, TokenInterface>>,
 }
 
-pub fn send_message(
-    ctx: Context<SendMessage>,
+pub fn send_message<'info>(
+    ctx: Context<'_, '_, '_, 'info, SendMessage<'info>>,
     message_body: Vec<u8>,
     recipient: [u8; 32],
     destination_caller: Option<[u8; 32]>,
     fee_override: u64,
+    options: Option<SendOptions>,
+    request_ack: bool,
 ) -> Result<SendResult> {
-    let config = &mut ctx.accounts.config;
-    let outbound_message_account = &mut ctx.accounts.outbound_message;
+    let message_body = match &ctx.accounts.message_buffer {
+        Some(message_buffer) => {
+            require!(message_body.is_empty(), MailboxError::InvalidMessageBuffer);
+            let info = message_buffer.to_account_info();
+            let data = info.try_borrow_data()?;
+            data[MessageBuffer::BODY_OFFSET..].to_vec()
+        }
+        None => message_body,
+    };
+
+    // Consume rate limits of the path and of the sender, if configured
+    let outbound_message_path: &mut OutboundMessagePath = &mut ctx.accounts.outbound_message_path;
+    consume_rate_limits(
+        &mut outbound_message_path.message_rate_limit,
+        &mut outbound_message_path.bytes_rate_limit,
+        message_body.len(),
+    )?;
+    let sender_config_info = ctx.accounts.sender_config.to_account_info();
+    let mut sender_config = load_sender_config(&sender_config_info)?;
+    if let Some(sender_config) = sender_config.as_mut() {
+        consume_rate_limits(
+            &mut sender_config.message_rate_limit,
+            &mut sender_config.bytes_rate_limit,
+            message_body.len(),
+        )?;
+        sender_config.try_serialize(&mut &mut sender_config_info.try_borrow_mut_data()?[..])?;
+    }
 
-    let (mut fee_disabled, max_payload_size) = match &ctx.accounts.sender_config {
+    let config = &ctx.accounts.config;
+
+    let (fee_disabled, max_payload_size) = match &sender_config {
         Some(sender_config) => (sender_config.fee_disabled, sender_config.max_payload_size),
         None => (false, config.default_max_payload_size),
     };
-    let mut fee_per_byte = config.fee_per_byte;
-    if fee_disabled && fee_override > 0 {
-        fee_per_byte = fee_per_byte * fee_override / FEE_ADJUSTMET_BASE;
-        fee_disabled = false;
-    }
 
     // Check payload size
     require!(
@@ -84,6 +176,19 @@ pub fn send_message(
         MailboxError::PayloadTooLarge
     );
 
+    // Check the sender allowlists, if configured. The sender config is always read from the PDA
+    // of the sender, so a restricted sender can't skip them
+    if let Some(sender_config) = &sender_config {
+        require!(
+            sender_config.allows_destination(&ctx.accounts.outbound_message_path.destination_chain_id),
+            MailboxError::DestinationNotAllowed
+        );
+        require!(
+            sender_config.allows_recipient(&recipient),
+            MailboxError::RecipientNotAllowed
+        );
+    }
+
     let sender = match bytes_are_curve_point(ctx.accounts.sender_authority.key.as_ref()) ||
         ctx.accounts.sender_authority.data_is_empty() {
         true => ctx.accounts.sender_authority.key.to_bytes(),
@@ -99,46 +204,201 @@ pub fn send_message(
         sender: sender,
     };
 
-    if !fee_disabled {
-        let fee = MessageV1::accountable_abi_bytes(message.body_length()) * fee_per_byte;
-        msg!("gmp fee: {}", fee);
-        if fee != 0 {
-            let treasury = match ctx.accounts.treasury.clone() {
-                Some(treasury) => {
-                    require_eq!(
-                        treasury.key(),
-                        config.treasury,
-                        MailboxError::TreasuryMismatch
-                    );
-                    treasury
-                }
-                None => return err!(MailboxError::PublicSendWithFeeDisabled),
-            };
-            let account_infos = vec![
-                ctx.accounts.fee_payer.to_account_info(),
-                treasury.to_account_info(),
-            ];
-            invoke(
-                &transfer(ctx.accounts.fee_payer.key, &treasury.key(), fee),
-                &account_infos,
-            )?
+    let fee = message_fee(
+        &ctx.accounts.outbound_message_path.fee_schedule,
+        ctx.accounts.sender_fee_schedule.as_ref().map(|s| &s.fee_schedule),
+        fee_disabled,
+        fee_override,
+        message.body_length(),
+    )?;
+    msg!("gmp fee: {}", fee);
+    // the fee as paid, in lamports unless paid with a fee token
+    let (mut fee_paid, mut fee_mint) = (fee, None);
+    if fee != 0 {
+        match &ctx.accounts.fee_token {
+            // remaining accounts carry the transfer hook accounts of the fee token, if any
+            Some(fee_token) => {
+                fee_paid = pay_fee_in_token(ctx.accounts, fee_token, ctx.remaining_accounts, fee)?;
+                fee_mint = Some(fee_token.mint);
+            }
+            None => {
+                let treasury = match ctx.accounts.treasury.clone() {
+                    Some(treasury) => {
+                        require_eq!(
+                            treasury.key(),
+                            config.treasury,
+                            MailboxError::TreasuryMismatch
+                        );
+                        treasury
+                    }
+                    None => return err!(MailboxError::PublicSendWithFeeDisabled),
+                };
+                let account_infos = vec![
+                    ctx.accounts.fee_payer.to_account_info(),
+                    treasury.to_account_info(),
+                ];
+                invoke(
+                    &transfer(ctx.accounts.fee_payer.key, &treasury.key(), fee),
+                    &account_infos,
+                )?
+            }
         }
     }
 
-    emit!(crate::events::MessageSent {
-        nonce: config.global_nonce,
-    });
+    // a message with options is sent as a MessageV2, one requesting an acknowledgement or
+    // sequenced as a MessageV3
+    let v2_fields = match (options, request_ack) {
+        (None, false) => None,
+        (options, request_ack) => {
+            let options = options.unwrap_or_default();
+            let mut v2_fields = MessageV2Fields {
+                deadline: options.deadline,
+                execution_hint: options.execution_hint,
+                refund_address: options.refund_address,
+                flags: if request_ack { MessageV2Fields::ACK_REQUESTED } else { 0 },
+                sequence: 0,
+            };
+            if options.sequenced {
+                let path = &mut ctx.accounts.outbound_message_path;
+                v2_fields.flags |= MessageV2Fields::SEQUENCED;
+                v2_fields.sequence = path.next_sequence;
+                path.next_sequence = path.next_sequence.checked_add(1).unwrap();
+            }
+            require!(
+                !v2_fields.is_expired(Clock::get()?.unix_timestamp),
+                MailboxError::MessageExpired
+            );
+            Some(v2_fields)
+        }
+    };
 
-    let (payload_hash, payload) = message.calculate_payload_hash();
+    // Write payload to outbound message account
+    let payload_len = MessageV1::versioned_payload_len(message.body_length(), v2_fields.as_ref());
+    prepare_outbound_message(ctx.accounts, ctx.bumps.outbound_message, payload_len)?;
+    let payload_hash = {
+        let mut data = ctx.accounts.outbound_message.try_borrow_mut_data()?;
+        message
+            .write_versioned_session_payload(v2_fields.as_ref(), &mut &mut data[..])
+            .map_err(MailboxError::from)?;
+        sha256(&data).to_bytes()
+    };
+
+    emit_cpi!(crate::events::MessageSent {
+        nonce: message.nonce,
+        payload_hash,
+        message_path_identifier: message.message_path_identifier,
+        destination_chain_id: ctx.accounts.outbound_message_path.destination_chain_id,
+        sender: message.sender,
+        recipient: message.recipient,
+        body_length: message.body_length() as u64,
+        fee: fee_paid,
+        fee_mint,
+    });
 
     // Increment global nonce
+    let config = &mut ctx.accounts.config;
     config.global_nonce = config.global_nonce.checked_add(1).unwrap();
 
-    // Write payload to outbound message account
-    outbound_message_account.try_borrow_mut_data()?.copy_from_slice(&payload);
+    ctx.accounts.outbound_message_record.set_inner(OutboundMessageRecord {
+        bump: ctx.bumps.outbound_message_record,
+        payer: ctx.accounts.fee_payer.key(),
+        sent_at: Clock::get()?.unix_timestamp,
+    });
+    if let Some(message_buffer) = &ctx.accounts.message_buffer {
+        message_buffer.close(ctx.accounts.fee_payer.to_account_info())?;
+    }
 
     Ok(SendResult{
         nonce: config.global_nonce,
         payload_hash: payload_hash,
     })
 }
+
+/// Creates the outbound message account, or resizes it when it was reserved ahead. Either grows
+/// the account by at most 10 KiB, the most an instruction can.
+fn prepare_outbound_message(accounts: &SendMessage, bump: u8, payload_len: usize) -> Result<()> {
+    let outbound_message = accounts.outbound_message.to_account_info();
+    let reserved = outbound_message.owner == &crate::ID;
+    require!(
+        payload_len <= outbound_message.data_len() + MAX_PERMITTED_DATA_INCREASE,
+        MailboxError::OutboundMessageNotReserved
+    );
+    if !reserved {
+        let nonce_bytes = accounts.config.global_nonce.to_be_bytes();
+        create_pda_account(
+            &accounts.fee_payer.to_account_info(),
+            &outbound_message,
+            &accounts.system_program.to_account_info(),
+            &[OUTBOUND_MESSAGE, &nonce_bytes, &[bump]],
+            payload_len,
+        )
+    } else {
+        resize_pda_account(
+            &accounts.fee_payer.to_account_info(),
+            &outbound_message,
+            &accounts.system_program.to_account_info(),
+            payload_len,
+        )
+    }
+}
+
+/// Loads the config of the sender from its PDA, if the admin set one.
+fn load_sender_config(sender_config: &AccountInfo) -> Result<Option<SenderConfig>> {
+    if sender_config.data_is_empty() {
+        return Ok(None);
+    }
+    require_keys_eq!(
+        *sender_config.owner,
+        crate::ID,
+        ErrorCode::AccountOwnedByWrongProgram
+    );
+    let data = sender_config.try_borrow_data()?;
+    Ok(Some(SenderConfig::try_deserialize(&mut &data[..])?))
+}
+
+/// Consumes one message and the body bytes from the rate limits of a path or of a sender.
+fn consume_rate_limits(
+    message_rate_limit: &mut RateLimitTokenBucket,
+    bytes_rate_limit: &mut RateLimitTokenBucket,
+    body_length: usize,
+) -> Result<()> {
+    message_rate_limit.consume::<Clock>(1)?;
+    bytes_rate_limit.consume::<Clock>(body_length as u64)
+}
+
+fn pay_fee_in_token<'info>(
+    accounts: &SendMessage<'info>,
+    fee_token: &FeeToken,
+    additional_accounts: &[AccountInfo<'info>],
+    fee: u64,
+) -> Result<u64> {
+    let (Some(mint), Some(from), Some(to), Some(token_program)) = (
+        &accounts.fee_mint,
+        &accounts.fee_payer_token_account,
+        &accounts.treasury_token_account,
+        &accounts.fee_token_program,
+    ) else {
+        return err!(MailboxError::MissingFeeTokenAccount);
+    };
+    require_keys_eq!(mint.key(), fee_token.mint, MailboxError::FeeTokenMismatch);
+    require_keys_eq!(
+        to.key(),
+        fee_token.treasury_token_account,
+        MailboxError::TreasuryMismatch
+    );
+
+    let amount = token_fee(fee, fee_token.rate)?;
+    msg!("gmp fee in {}: {}", fee_token.mint, amount);
+    spl_token_2022::onchain::invoke_transfer_checked(
+        token_program.key,
+        from.to_account_info(),
+        mint.to_account_info(),
+        to.to_account_info(),
+        accounts.fee_payer.to_account_info(),
+        additional_accounts,
+        amount,
+        mint.decimals,
+        &[],
+    )?;
+    Ok(amount)
+}
@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ACK_CALLBACK_SEED;
+use crate::utils::authority::check_authority;
+use crate::{events::AckCallbackSet, state::AckCallback};
+
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct SetAckCallback<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The sender itself if it is a wallet, the mailbox authority PDA of the program otherwise
+    pub sender_authority: Signer<'info>,
+
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + AckCallback::INIT_SPACE,
+        seeds = [ACK_CALLBACK_SEED, sender.as_ref()],
+        bump
+    )]
+    pub ack_callback: Account<'info, AckCallback>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the program invoked with `gmp_ack` when the acknowledgement of a message of the sender is handled.
+pub fn set_ack_callback(
+    ctx: Context<SetAckCallback>,
+    sender: Pubkey,
+    callback_program: Pubkey,
+) -> Result<()> {
+    check_authority(&ctx.accounts.sender_authority, &sender)?;
+    ctx.accounts.ack_callback.bump = ctx.bumps.ack_callback;
+    ctx.accounts.ack_callback.callback_program = callback_program;
+    emit!(AckCallbackSet {
+        sender,
+        callback_program,
+    });
+    Ok(())
+}
@@ -0,0 +1,87 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::{EXECUTOR_ALLOWLIST_SEED, MAX_EXECUTORS};
+use crate::errors::MailboxError;
+use crate::utils::accounts::create_pda_account;
+use crate::utils::authority::check_authority;
+use crate::{events::ExecutorAllowlistSet, state::ExecutorAllowlist};
+
+#[derive(Accounts)]
+#[instruction(recipient: Pubkey)]
+pub struct SetExecutorAllowlist<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    /// The recipient itself if it is a wallet, the mailbox authority PDA of the program otherwise
+    pub recipient_authority: Signer<'info>,
+
+    /// CHECK: Created in the instruction when the recipient has no allowlist yet, checked to be an
+    /// allowlist otherwise
+    #[account(mut, seeds = [EXECUTOR_ALLOWLIST_SEED, recipient.as_ref()], bump)]
+    pub executor_allowlist: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sets the executors allowed to handle the messages of the recipient designating its allowlist,
+/// and the delay after delivery from which anyone can handle the messages of the recipient with a
+/// destination caller, 0 to disable the fallback.
+pub fn set_executor_allowlist(
+    ctx: Context<SetExecutorAllowlist>,
+    recipient: Pubkey,
+    executors: Vec<Pubkey>,
+    fallback_delay: i64,
+) -> Result<()> {
+    check_authority(&ctx.accounts.recipient_authority, &recipient)?;
+    let unique = executors
+        .iter()
+        .enumerate()
+        .all(|(i, e)| *e != Pubkey::default() && !executors[..i].contains(e));
+    require!(
+        unique && executors.len() <= MAX_EXECUTORS && fallback_delay >= 0,
+        MailboxError::InvalidExecutorAllowlist
+    );
+
+    let executor_allowlist = ctx.accounts.executor_allowlist.to_account_info();
+    if executor_allowlist.data_is_empty() {
+        create_pda_account(
+            &ctx.accounts.payer.to_account_info(),
+            &executor_allowlist,
+            &ctx.accounts.system_program.to_account_info(),
+            &[
+                EXECUTOR_ALLOWLIST_SEED,
+                recipient.as_ref(),
+                &[ctx.bumps.executor_allowlist],
+            ],
+            8 + ExecutorAllowlist::INIT_SPACE,
+        )?;
+    } else {
+        require_keys_eq!(
+            *executor_allowlist.owner,
+            crate::ID,
+            ErrorCode::AccountOwnedByWrongProgram
+        );
+        require!(
+            executor_allowlist
+                .try_borrow_data()?
+                .starts_with(ExecutorAllowlist::DISCRIMINATOR),
+            ErrorCode::AccountDiscriminatorMismatch
+        );
+    }
+
+    ExecutorAllowlist {
+        bump: ctx.bumps.executor_allowlist,
+        recipient,
+        executors: executors.clone(),
+        fallback_delay,
+    }
+    .try_serialize(&mut &mut executor_allowlist.try_borrow_mut_data()?[..])?;
+
+    emit!(ExecutorAllowlistSet {
+        recipient,
+        executors,
+        fallback_delay,
+    });
+    Ok(())
+}
@@ -0,0 +1,46 @@
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::{Mint, TokenAccount};
+
+use crate::{
+    constants::{CONFIG_SEED, FEE_TOKEN_SEED},
+    errors::MailboxError,
+    events::FeeTokenSet,
+    state::{Config, FeeToken},
+};
+
+#[derive(Accounts)]
+pub struct SetFeeToken<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(token::mint = mint)]
+    pub treasury_token_account: InterfaceAccount<'info, TokenAccount>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + FeeToken::INIT_SPACE,
+        seeds = [FEE_TOKEN_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub fee_token: Account<'info, FeeToken>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_fee_token(ctx: Context<SetFeeToken>, rate: u64) -> Result<()> {
+    require!(rate > 0, MailboxError::InvalidFeeConfiguration);
+
+    let fee_token = &mut ctx.accounts.fee_token;
+    fee_token.bump = ctx.bumps.fee_token;
+    fee_token.mint = ctx.accounts.mint.key();
+    fee_token.treasury_token_account = ctx.accounts.treasury_token_account.key();
+    fee_token.rate = rate;
+
+    emit!(FeeTokenSet {
+        mint: fee_token.mint,
+        treasury_token_account: fee_token.treasury_token_account,
+        rate,
+    });
+    Ok(())
+}
@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, INBOUND_MESSAGE_PATH_SEED},
+    errors::MailboxError,
+    events::InboundPathOrderingSet,
+    state::{Config, InboundMessagePath},
+};
+
+#[derive(Accounts)]
+#[instruction(source_chain_id: [u8; 32])]
+pub struct SetInboundPathOrdering<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            INBOUND_MESSAGE_PATH_SEED,
+            &source_chain_id,
+        ],
+        bump
+    )]
+    pub inbound_message_path: Account<'info, InboundMessagePath>,
+}
+
+/// Switches the path between unordered and ordered handling. When ordered, sequenced messages
+/// delivered from now on must be handled one after the other from `next_sequence` on, which also
+/// allows the admin to resynchronize a path with the source mailbox or skip a missing message.
+/// Messages without a sequence are handled as on an unordered path.
+pub fn set_inbound_path_ordering(
+    ctx: Context<SetInboundPathOrdering>,
+    source_chain_id: [u8; 32],
+    ordered: bool,
+    next_sequence: u64,
+) -> Result<()> {
+    let inbound_message_path = &mut ctx.accounts.inbound_message_path;
+    inbound_message_path.ordered = ordered;
+    inbound_message_path.next_sequence = next_sequence;
+    emit!(InboundPathOrderingSet {
+        source_chain_id,
+        ordered,
+        next_sequence,
+    });
+    Ok(())
+}
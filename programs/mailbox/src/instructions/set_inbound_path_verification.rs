@@ -0,0 +1,42 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, INBOUND_MESSAGE_PATH_SEED},
+    errors::MailboxError,
+    events::InboundPathVerificationSet,
+    state::{Config, InboundMessagePath, VerificationPolicy},
+    utils::verification::validate_policy,
+};
+
+#[derive(Accounts)]
+#[instruction(source_chain_id: [u8; 32])]
+pub struct SetInboundPathVerification<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            INBOUND_MESSAGE_PATH_SEED,
+            &source_chain_id,
+        ],
+        bump
+    )]
+    pub inbound_message_path: Account<'info, InboundMessagePath>,
+}
+
+/// Sets which verifiers must attest a payload before it can be delivered on the path.
+pub fn set_inbound_path_verification(
+    ctx: Context<SetInboundPathVerification>,
+    source_chain_id: [u8; 32],
+    policy: VerificationPolicy,
+) -> Result<()> {
+    validate_policy(&policy, &ctx.accounts.config.consortium)?;
+    ctx.accounts.inbound_message_path.verification_policy = policy.clone();
+    emit!(InboundPathVerificationSet {
+        source_chain_id,
+        policy,
+    });
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, OUTBOUND_MESSAGE_PATH_SEED},
+    errors::MailboxError,
+    events::OutboundPathFeesSet,
+    state::{Config, FeeSchedule, OutboundMessagePath},
+};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32])]
+pub struct SetOutboundPathFees<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            OUTBOUND_MESSAGE_PATH_SEED,
+            &destination_chain_id,
+        ],
+        bump
+    )]
+    pub outbound_message_path: Account<'info, OutboundMessagePath>,
+}
+
+pub fn set_outbound_path_fees(
+    ctx: Context<SetOutboundPathFees>,
+    destination_chain_id: [u8; 32],
+    base_fee: u64,
+    fee_per_byte: u64,
+) -> Result<()> {
+    ctx.accounts.outbound_message_path.fee_schedule = FeeSchedule {
+        base_fee,
+        fee_per_byte,
+    };
+    emit!(OutboundPathFeesSet {
+        destination_chain_id,
+        sender_program: None,
+        base_fee,
+        fee_per_byte,
+    });
+    Ok(())
+}
@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+use base_token_pool::rate_limiter::RateLimitConfig;
+
+use crate::{
+    constants::{CONFIG_SEED, OUTBOUND_MESSAGE_PATH_SEED},
+    errors::MailboxError,
+    events::OutboundPathRateLimitsSet,
+    state::{Config, OutboundMessagePath},
+};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32])]
+pub struct SetOutboundPathRateLimits<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [
+            OUTBOUND_MESSAGE_PATH_SEED,
+            &destination_chain_id,
+        ],
+        bump
+    )]
+    pub outbound_message_path: Account<'info, OutboundMessagePath>,
+}
+
+/// Sets the limits on the number of messages and of body bytes sent over the path.
+pub fn set_outbound_path_rate_limits(
+    ctx: Context<SetOutboundPathRateLimits>,
+    destination_chain_id: [u8; 32],
+    message_limit: RateLimitConfig,
+    bytes_limit: RateLimitConfig,
+) -> Result<()> {
+    let outbound_message_path = &mut ctx.accounts.outbound_message_path;
+    outbound_message_path
+        .message_rate_limit
+        .set_token_bucket_config(message_limit.clone())?;
+    outbound_message_path
+        .bytes_rate_limit
+        .set_token_bucket_config(bytes_limit.clone())?;
+    emit!(OutboundPathRateLimitsSet {
+        destination_chain_id,
+        message_limit,
+        bytes_limit,
+    });
+    Ok(())
+}
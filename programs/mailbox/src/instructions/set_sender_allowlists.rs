@@ -0,0 +1,49 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::{
+    CONFIG_SEED, MAX_SENDER_DESTINATIONS, MAX_SENDER_RECIPIENTS, SENDER_CONFIG_SEED,
+};
+use crate::{
+    errors::MailboxError,
+    events::SenderAllowlistsSet,
+    state::{Config, SenderConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(sender_program: Pubkey)]
+pub struct SetSenderAllowlists<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [SENDER_CONFIG_SEED, &sender_program.to_bytes()],
+        bump = sender_config.bump
+    )]
+    pub sender_config: Account<'info, SenderConfig>,
+}
+
+/// Sets the destination chains and the recipients the sender can send to, an empty list
+/// allowing any. The sender must have a sender config, set with `set_sender_config`.
+pub fn set_sender_allowlists(
+    ctx: Context<SetSenderAllowlists>,
+    sender_program: Pubkey,
+    destination_chain_ids: Vec<[u8; 32]>,
+    recipients: Vec<[u8; 32]>,
+) -> Result<()> {
+    require!(
+        destination_chain_ids.len() <= MAX_SENDER_DESTINATIONS
+            && recipients.len() <= MAX_SENDER_RECIPIENTS,
+        MailboxError::InvalidSenderAllowlist
+    );
+    let sender_config = &mut ctx.accounts.sender_config;
+    sender_config.allowed_destination_chain_ids = destination_chain_ids.clone();
+    sender_config.allowed_recipients = recipients.clone();
+    emit!(SenderAllowlistsSet {
+        sender_program,
+        destination_chain_ids,
+        recipients,
+    });
+    Ok(())
+}
@@ -0,0 +1,55 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, OUTBOUND_MESSAGE_PATH_SEED, SENDER_FEE_SCHEDULE_SEED},
+    errors::MailboxError,
+    events::OutboundPathFeesSet,
+    state::{Config, FeeSchedule, OutboundMessagePath, SenderFeeSchedule},
+};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32], sender_program: Pubkey)]
+pub struct SetSenderPathFees<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        seeds = [
+            OUTBOUND_MESSAGE_PATH_SEED,
+            &destination_chain_id,
+        ],
+        bump
+    )]
+    pub outbound_message_path: Account<'info, OutboundMessagePath>,
+    #[account(
+        init_if_needed,
+        payer = admin,
+        space = 8 + SenderFeeSchedule::INIT_SPACE,
+        seeds = [SENDER_FEE_SCHEDULE_SEED, &destination_chain_id, &sender_program.to_bytes()],
+        bump
+    )]
+    pub sender_fee_schedule: Account<'info, SenderFeeSchedule>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_sender_path_fees(
+    ctx: Context<SetSenderPathFees>,
+    destination_chain_id: [u8; 32],
+    sender_program: Pubkey,
+    base_fee: u64,
+    fee_per_byte: u64,
+) -> Result<()> {
+    ctx.accounts.sender_fee_schedule.bump = ctx.bumps.sender_fee_schedule;
+    ctx.accounts.sender_fee_schedule.fee_schedule = FeeSchedule {
+        base_fee,
+        fee_per_byte,
+    };
+    emit!(OutboundPathFeesSet {
+        destination_chain_id,
+        sender_program: Some(sender_program),
+        base_fee,
+        fee_per_byte,
+    });
+    Ok(())
+}
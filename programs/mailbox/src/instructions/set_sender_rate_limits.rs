@@ -0,0 +1,47 @@
+use anchor_lang::prelude::*;
+use base_token_pool::rate_limiter::RateLimitConfig;
+
+use crate::constants::{CONFIG_SEED, SENDER_CONFIG_SEED};
+use crate::{
+    errors::MailboxError,
+    events::SenderRateLimitsSet,
+    state::{Config, SenderConfig},
+};
+
+#[derive(Accounts)]
+#[instruction(sender_program: Pubkey)]
+pub struct SetSenderRateLimits<'info> {
+    #[account(address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        seeds = [SENDER_CONFIG_SEED, &sender_program.to_bytes()],
+        bump = sender_config.bump
+    )]
+    pub sender_config: Account<'info, SenderConfig>,
+}
+
+/// Sets the limits on the number of messages and of body bytes the sender can send over all paths.
+/// The sender must have a sender config, set with `set_sender_config`.
+pub fn set_sender_rate_limits(
+    ctx: Context<SetSenderRateLimits>,
+    sender_program: Pubkey,
+    message_limit: RateLimitConfig,
+    bytes_limit: RateLimitConfig,
+) -> Result<()> {
+    let sender_config = &mut ctx.accounts.sender_config;
+    sender_config
+        .message_rate_limit
+        .set_token_bucket_config(message_limit.clone())?;
+    sender_config
+        .bytes_rate_limit
+        .set_token_bucket_config(bytes_limit.clone())?;
+    emit!(SenderRateLimitsSet {
+        sender_program,
+        message_limit,
+        bytes_limit,
+    });
+    Ok(())
+}
@@ -0,0 +1,27 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::ACK_CALLBACK_SEED;
+use crate::utils::authority::check_authority;
+use crate::{events::AckCallbackUnset, state::AckCallback};
+
+#[derive(Accounts)]
+#[instruction(sender: Pubkey)]
+pub struct UnsetAckCallback<'info> {
+    /// The sender authority, see `SetAckCallback`
+    #[account(mut)]
+    pub sender_authority: Signer<'info>,
+
+    #[account(
+        mut,
+        close = sender_authority,
+        seeds = [ACK_CALLBACK_SEED, sender.as_ref()],
+        bump = ack_callback.bump
+    )]
+    pub ack_callback: Account<'info, AckCallback>,
+}
+
+pub fn unset_ack_callback(ctx: Context<UnsetAckCallback>, sender: Pubkey) -> Result<()> {
+    check_authority(&ctx.accounts.sender_authority, &sender)?;
+    emit!(AckCallbackUnset { sender });
+    Ok(())
+}
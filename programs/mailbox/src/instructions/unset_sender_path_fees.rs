@@ -0,0 +1,37 @@
+use anchor_lang::prelude::*;
+
+use crate::{
+    constants::{CONFIG_SEED, SENDER_FEE_SCHEDULE_SEED},
+    errors::MailboxError,
+    events::SenderPathFeesUnset,
+    state::{Config, SenderFeeSchedule},
+};
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32], sender_program: Pubkey)]
+pub struct UnsetSenderPathFees<'info> {
+    #[account(mut, address = config.admin @ MailboxError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    #[account(
+        mut,
+        close = admin,
+        seeds = [SENDER_FEE_SCHEDULE_SEED, &destination_chain_id, &sender_program.to_bytes()],
+        bump = sender_fee_schedule.bump
+    )]
+    pub sender_fee_schedule: Account<'info, SenderFeeSchedule>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn unset_sender_path_fees(
+    _ctx: Context<UnsetSenderPathFees>,
+    destination_chain_id: [u8; 32],
+    sender_program: Pubkey,
+) -> Result<()> {
+    emit!(SenderPathFeesUnset {
+        destination_chain_id,
+        sender_program,
+    });
+    Ok(())
+}
@@ -0,0 +1,116 @@
+use anchor_lang::prelude::*;
+use anchor_lang::Discriminator;
+
+use crate::constants::{CONFIG_SEED, INBOUND_MESSAGE_PATH_SEED, MESSAGE_SEED, OUTBOUND_MESSAGE_PATH_SEED};
+use crate::state::{
+    Config, FeeSchedule, HandledMessageInfo, InboundMessagePath, MessageState, MessageV1Info,
+    OutboundMessagePath, VerificationPolicy,
+};
+
+// The view instructions return, via return data, information integrators would otherwise
+// read from the mailbox accounts, so that they can branch on it through CPI without
+// depending on the account layouts. Missing accounts are reported as such rather than failing.
+
+/// The configuration of an outbound path, `enabled` is false and the other fields are
+/// defaulted when the path does not exist.
+#[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct OutboundPathView {
+    pub enabled: bool,
+    pub identifier: [u8; 32],
+    pub fee_schedule: FeeSchedule,
+}
+
+/// The configuration of an inbound path, `enabled` is false and the other fields are
+/// defaulted when the path does not exist.
+#[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct InboundPathView {
+    pub enabled: bool,
+    pub identifier: [u8; 32],
+    pub source_mailbox_address: [u8; 32],
+    pub ordered: bool,
+    pub next_sequence: u64,
+    pub verification_policy: VerificationPolicy,
+}
+
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct GetMessageStatus<'info> {
+    /// CHECK: May not exist, or hold either a `MessageV1Info` or a `HandledMessageInfo`
+    #[account(seeds = [MESSAGE_SEED, &payload_hash], bump)]
+    pub message_info: UncheckedAccount<'info>,
+}
+
+/// Returns the status of an inbound message, Unknown if it has not been delivered.
+pub fn get_message_status(ctx: Context<GetMessageStatus>, _payload_hash: [u8; 32]) -> Result<MessageState> {
+    let message_info = &ctx.accounts.message_info;
+    if message_info.data_is_empty() || message_info.owner != &crate::ID {
+        return Ok(MessageState::Unknown);
+    }
+    let data = message_info.try_borrow_data()?;
+    if data.starts_with(HandledMessageInfo::DISCRIMINATOR) {
+        return Ok(HandledMessageInfo::try_deserialize(&mut &data[..])?.status);
+    }
+    Ok(MessageV1Info::try_deserialize(&mut &data[..])?.status)
+}
+
+#[derive(Accounts)]
+pub struct GetOutboundNonce<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+}
+
+/// Returns the nonce the next outbound message will be sent with.
+pub fn get_outbound_nonce(ctx: Context<GetOutboundNonce>) -> Result<u64> {
+    Ok(ctx.accounts.config.global_nonce)
+}
+
+#[derive(Accounts)]
+#[instruction(destination_chain_id: [u8; 32])]
+pub struct GetOutboundPath<'info> {
+    /// CHECK: Exists only while the path is enabled
+    #[account(seeds = [OUTBOUND_MESSAGE_PATH_SEED, &destination_chain_id], bump)]
+    pub outbound_message_path: UncheckedAccount<'info>,
+}
+
+pub fn get_outbound_path(
+    ctx: Context<GetOutboundPath>,
+    _destination_chain_id: [u8; 32],
+) -> Result<OutboundPathView> {
+    let account = &ctx.accounts.outbound_message_path;
+    if account.data_is_empty() || account.owner != &crate::ID {
+        return Ok(OutboundPathView::default());
+    }
+    let path = OutboundMessagePath::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(OutboundPathView {
+        enabled: true,
+        identifier: path.identifier,
+        fee_schedule: path.fee_schedule,
+    })
+}
+
+#[derive(Accounts)]
+#[instruction(source_chain_id: [u8; 32])]
+pub struct GetInboundPath<'info> {
+    /// CHECK: Exists only while the path is enabled
+    #[account(seeds = [INBOUND_MESSAGE_PATH_SEED, &source_chain_id], bump)]
+    pub inbound_message_path: UncheckedAccount<'info>,
+}
+
+pub fn get_inbound_path(
+    ctx: Context<GetInboundPath>,
+    _source_chain_id: [u8; 32],
+) -> Result<InboundPathView> {
+    let account = &ctx.accounts.inbound_message_path;
+    if account.data_is_empty() || account.owner != &crate::ID {
+        return Ok(InboundPathView::default());
+    }
+    let path = InboundMessagePath::try_deserialize(&mut &account.try_borrow_data()?[..])?;
+    Ok(InboundPathView {
+        enabled: true,
+        identifier: path.identifier,
+        source_mailbox_address: path.source_mailbox_address,
+        ordered: path.ordered,
+        next_sequence: path.next_sequence,
+        verification_policy: path.verification_policy,
+    })
+}
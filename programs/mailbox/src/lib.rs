@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use base_token_pool::rate_limiter::RateLimitConfig;
 
 pub mod constants;
 pub mod errors;
@@ -10,8 +11,8 @@ pub mod utils;
 
 use instructions::*;
 
-use crate::state::AccountRole;
-use crate::utils::message_utils::SendResult;
+use crate::state::{AccountRole, MessageState, VerificationPolicy};
+use crate::utils::message_utils::{SendOptions, SendResult};
 
 #[cfg(feature = "mainnet")]
 declare_id!("Lomsq4ZNkZQGysC1pQc2NqNiAbXQm6C8nLmKcUoESEA");
@@ -47,6 +48,35 @@ pub mod mailbox {
         )
     }
 
+    pub fn migrate_config(ctx: Context<MigrateConfig>) -> Result<()> {
+        instructions::migrate_config(ctx)
+    }
+
+    pub fn migrate_outbound_message_path(
+        ctx: Context<MigrateOutboundMessagePath>,
+        destination_chain_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_outbound_message_path(ctx, destination_chain_id)
+    }
+
+    pub fn migrate_inbound_message_path(
+        ctx: Context<MigrateInboundMessagePath>,
+        source_chain_id: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_inbound_message_path(ctx, source_chain_id)
+    }
+
+    pub fn migrate_sender_config(ctx: Context<MigrateSenderConfig>, sender: Pubkey) -> Result<()> {
+        instructions::migrate_sender_config(ctx, sender)
+    }
+
+    pub fn migrate_message_info(
+        ctx: Context<MigrateMessageInfo>,
+        payload_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::migrate_message_info(ctx, payload_hash)
+    }
+
     pub fn grant_account_role(
         ctx: Context<GrantAccountRole>,
         account: Pubkey,
@@ -88,14 +118,83 @@ pub mod mailbox {
         instructions::disable_outbound_message_path(ctx, destination_chain_id)
     }
 
-    pub fn send_message(
-        ctx: Context<SendMessage>,
+    /// Sends a message, as a MessageV2 carrying the `options` when given. With `request_ack` the
+    /// message is sent as a MessageV3 and the destination mailbox sends back an acknowledgement
+    /// once the message is handled, calling the callback set with `set_ack_callback`. Sequenced
+    /// messages are sent as a MessageV3 too, carrying the next sequence of the outbound path.
+    pub fn send_message<'info>(
+        ctx: Context<'_, '_, '_, 'info, SendMessage<'info>>,
         message_body: Vec<u8>,
         recipient: [u8; 32],
         destination_caller: Option<[u8; 32]>,
         fee_override: u64,
+        options: Option<SendOptions>,
+        request_ack: bool,
     ) -> Result<SendResult> {
-        instructions::send_message(ctx, message_body, recipient, destination_caller, fee_override)
+        instructions::send_message(
+            ctx,
+            message_body,
+            recipient,
+            destination_caller,
+            fee_override,
+            options,
+            request_ack,
+        )
+    }
+
+    pub fn set_ack_callback(
+        ctx: Context<SetAckCallback>,
+        sender: Pubkey,
+        callback_program: Pubkey,
+    ) -> Result<()> {
+        instructions::set_ack_callback(ctx, sender, callback_program)
+    }
+
+    pub fn unset_ack_callback(ctx: Context<UnsetAckCallback>, sender: Pubkey) -> Result<()> {
+        instructions::unset_ack_callback(ctx, sender)
+    }
+
+    pub fn set_executor_allowlist(
+        ctx: Context<SetExecutorAllowlist>,
+        recipient: Pubkey,
+        executors: Vec<Pubkey>,
+        fallback_delay: i64,
+    ) -> Result<()> {
+        instructions::set_executor_allowlist(ctx, recipient, executors, fallback_delay)
+    }
+
+    pub fn remove_executor_allowlist(
+        ctx: Context<RemoveExecutorAllowlist>,
+        recipient: Pubkey,
+    ) -> Result<()> {
+        instructions::remove_executor_allowlist(ctx, recipient)
+    }
+
+    pub fn init_message_buffer(ctx: Context<InitMessageBuffer>, buffer_id: u64) -> Result<()> {
+        instructions::init_message_buffer(ctx, buffer_id)
+    }
+
+    pub fn write_message_buffer(
+        ctx: Context<WriteMessageBuffer>,
+        buffer_id: u64,
+        chunk: Vec<u8>,
+    ) -> Result<()> {
+        instructions::write_message_buffer(ctx, buffer_id, chunk)
+    }
+
+    pub fn close_message_buffer(ctx: Context<CloseMessageBuffer>, buffer_id: u64) -> Result<()> {
+        instructions::close_message_buffer(ctx, buffer_id)
+    }
+
+    pub fn reserve_outbound_message(
+        ctx: Context<ReserveOutboundMessage>,
+        space: u64,
+    ) -> Result<()> {
+        instructions::reserve_outbound_message(ctx, space)
+    }
+
+    pub fn close_outbound_message(ctx: Context<CloseOutboundMessage>, nonce: u64) -> Result<()> {
+        instructions::close_outbound_message(ctx, nonce)
     }
 
     pub fn deliver_message(ctx: Context<DeliverMessage>, payload_hash: [u8; 32]) -> Result<()> {
@@ -109,6 +208,21 @@ pub mod mailbox {
         instructions::handle_message(ctx, payload_hash)
     }
 
+    pub fn deliver_and_handle_message<'a, 'b, 'c, 'info>(
+        ctx: Context<'a, 'b, 'c, 'info, DeliverAndHandleMessage<'info>>,
+        payload_hash: [u8; 32],
+    ) -> Result<Option<Vec<u8>>> {
+        instructions::deliver_and_handle_message(ctx, payload_hash)
+    }
+
+    pub fn cancel_message(
+        ctx: Context<CancelMessage>,
+        payload_hash: [u8; 32],
+        reason: String,
+    ) -> Result<()> {
+        instructions::cancel_message(ctx, payload_hash, reason)
+    }
+
     pub fn transfer_ownership(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
         instructions::transfer_ownership(ctx, new_admin)
     }
@@ -124,6 +238,14 @@ pub mod mailbox {
     ) -> Result<()> {
         instructions::update_config(ctx, default_max_payload_size, fee_per_byte)
     }
+    pub fn set_outbound_message_retention(ctx: Context<Admin>, retention: i64) -> Result<()> {
+        instructions::set_outbound_message_retention(ctx, retention)
+    }
+
+    pub fn set_delivered_message_expiry(ctx: Context<Admin>, expiry: i64) -> Result<()> {
+        instructions::set_delivered_message_expiry(ctx, expiry)
+    }
+
     pub fn set_treasury(ctx: Context<Admin>, new_treasury: Pubkey) -> Result<()> {
         instructions::set_treasury(ctx, new_treasury)
     }
@@ -148,4 +270,129 @@ pub mod mailbox {
     pub fn unset_sender_config(ctx: Context<UnsetSenderConfig>, sender_program: Pubkey) -> Result<()> {
         instructions::unset_sender_config(ctx, sender_program)
     }
+
+    pub fn set_inbound_path_ordering(
+        ctx: Context<SetInboundPathOrdering>,
+        source_chain_id: [u8; 32],
+        ordered: bool,
+        next_sequence: u64,
+    ) -> Result<()> {
+        instructions::set_inbound_path_ordering(ctx, source_chain_id, ordered, next_sequence)
+    }
+
+    pub fn set_inbound_path_verification(
+        ctx: Context<SetInboundPathVerification>,
+        source_chain_id: [u8; 32],
+        policy: VerificationPolicy,
+    ) -> Result<()> {
+        instructions::set_inbound_path_verification(ctx, source_chain_id, policy)
+    }
+
+    pub fn set_outbound_path_fees(
+        ctx: Context<SetOutboundPathFees>,
+        destination_chain_id: [u8; 32],
+        base_fee: u64,
+        fee_per_byte: u64,
+    ) -> Result<()> {
+        instructions::set_outbound_path_fees(ctx, destination_chain_id, base_fee, fee_per_byte)
+    }
+
+    pub fn set_sender_path_fees(
+        ctx: Context<SetSenderPathFees>,
+        destination_chain_id: [u8; 32],
+        sender_program: Pubkey,
+        base_fee: u64,
+        fee_per_byte: u64,
+    ) -> Result<()> {
+        instructions::set_sender_path_fees(
+            ctx,
+            destination_chain_id,
+            sender_program,
+            base_fee,
+            fee_per_byte,
+        )
+    }
+
+    pub fn unset_sender_path_fees(
+        ctx: Context<UnsetSenderPathFees>,
+        destination_chain_id: [u8; 32],
+        sender_program: Pubkey,
+    ) -> Result<()> {
+        instructions::unset_sender_path_fees(ctx, destination_chain_id, sender_program)
+    }
+
+    pub fn set_outbound_path_rate_limits(
+        ctx: Context<SetOutboundPathRateLimits>,
+        destination_chain_id: [u8; 32],
+        message_limit: RateLimitConfig,
+        bytes_limit: RateLimitConfig,
+    ) -> Result<()> {
+        instructions::set_outbound_path_rate_limits(
+            ctx,
+            destination_chain_id,
+            message_limit,
+            bytes_limit,
+        )
+    }
+
+    pub fn set_sender_allowlists(
+        ctx: Context<SetSenderAllowlists>,
+        sender_program: Pubkey,
+        destination_chain_ids: Vec<[u8; 32]>,
+        recipients: Vec<[u8; 32]>,
+    ) -> Result<()> {
+        instructions::set_sender_allowlists(ctx, sender_program, destination_chain_ids, recipients)
+    }
+
+    pub fn set_sender_rate_limits(
+        ctx: Context<SetSenderRateLimits>,
+        sender_program: Pubkey,
+        message_limit: RateLimitConfig,
+        bytes_limit: RateLimitConfig,
+    ) -> Result<()> {
+        instructions::set_sender_rate_limits(ctx, sender_program, message_limit, bytes_limit)
+    }
+
+    pub fn set_fee_token(ctx: Context<SetFeeToken>, rate: u64) -> Result<()> {
+        instructions::set_fee_token(ctx, rate)
+    }
+
+    pub fn remove_fee_token(ctx: Context<RemoveFeeToken>, mint: Pubkey) -> Result<()> {
+        instructions::remove_fee_token(ctx, mint)
+    }
+
+    pub fn quote_fee(
+        ctx: Context<QuoteFee>,
+        destination_chain_id: [u8; 32],
+        sender: Pubkey,
+        body_length: u32,
+        fee_override: u64,
+    ) -> Result<u64> {
+        instructions::quote_fee(ctx, destination_chain_id, sender, body_length, fee_override)
+    }
+
+    pub fn get_message_status(
+        ctx: Context<GetMessageStatus>,
+        payload_hash: [u8; 32],
+    ) -> Result<MessageState> {
+        instructions::get_message_status(ctx, payload_hash)
+    }
+
+    pub fn get_outbound_nonce(ctx: Context<GetOutboundNonce>) -> Result<u64> {
+        instructions::get_outbound_nonce(ctx)
+    }
+
+    pub fn get_outbound_path(
+        ctx: Context<GetOutboundPath>,
+        destination_chain_id: [u8; 32],
+    ) -> Result<OutboundPathView> {
+        instructions::get_outbound_path(ctx, destination_chain_id)
+    }
+
+    pub fn get_inbound_path(
+        ctx: Context<GetInboundPath>,
+        source_chain_id: [u8; 32],
+    ) -> Result<InboundPathView> {
+        instructions::get_inbound_path(ctx, source_chain_id)
+    }
 }
@@ -1,6 +1,10 @@
 use anchor_lang::prelude::*;
+use base_token_pool::rate_limiter::RateLimitTokenBucket;
 
-use crate::utils::message_utils::MessageV1;
+use crate::constants::{
+    MAX_EXECUTORS, MAX_SENDER_DESTINATIONS, MAX_SENDER_RECIPIENTS, MAX_VERIFIERS,
+};
+use crate::utils::message_utils::{MessageV1, MessageV2Fields};
 
 // todo: optimize by saving bumps for accounts used more often
 
@@ -23,8 +27,17 @@ pub struct Config {
     pub global_nonce: u64,
     // The default max payload size for messages
     pub default_max_payload_size: u32,
-    // Lamports to charge per byte of payload when sending a message
+    // Default lamports per byte of payload for newly enabled outbound paths
     pub fee_per_byte: u64,
+    // Seconds after which an outbound message can be closed even if not notarized, 0 to disable
+    pub outbound_message_retention: i64,
+    // Seconds after delivery from which anyone can cancel a message not handled, 0 to disable
+    pub delivered_message_expiry: i64,
+}
+
+impl Config {
+    /// The space of the account before the retention and expiry settings were appended.
+    pub const LEGACY_SPACE: usize = 32 + 32 + 32 + 1 + 32 + 8 + 4 + 8;
 }
 
 #[account]
@@ -32,6 +45,26 @@ pub struct Config {
 pub struct OutboundMessagePath {
     pub identifier: [u8; 32],
     pub destination_chain_id: [u8; 32],
+    // Fees charged when sending a message over this path
+    pub fee_schedule: FeeSchedule,
+    // Limits the number of messages and of body bytes sent over this path
+    pub message_rate_limit: RateLimitTokenBucket,
+    pub bytes_rate_limit: RateLimitTokenBucket,
+    // The sequence of the next sequenced message sent over this path
+    pub next_sequence: u64,
+}
+
+impl OutboundMessagePath {
+    /// The space of the account before the fee schedule, rate limits and sequence were appended.
+    pub const LEGACY_SPACE: usize = 32 + 32;
+}
+
+/// The fee charged to send a message: a flat base fee plus a fee for every
+/// accountable (ABI-encoded) byte of the message.
+#[derive(Debug, Default, Clone, Copy, InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct FeeSchedule {
+    pub base_fee: u64,
+    pub fee_per_byte: u64,
 }
 
 #[account]
@@ -40,6 +73,42 @@ pub struct InboundMessagePath {
     pub identifier: [u8; 32],
     pub source_mailbox_address: [u8; 32],
     pub source_chain_id: [u8; 32],
+    // when set, sequenced messages must be handled one after the other, from `next_sequence` on
+    pub ordered: bool,
+    pub next_sequence: u64,
+    // which verifiers must attest a payload before it can be delivered on this path
+    pub verification_policy: VerificationPolicy,
+}
+
+impl InboundMessagePath {
+    /// The space of the account before the ordering and verification settings were appended.
+    pub const LEGACY_SPACE: usize = 32 + 32 + 32;
+}
+
+/// Verifiers other than the consortium attest a payload with an account they own at
+/// the `[VALIDATED_PAYLOAD_SEED, payload_hash]` PDA of their program, like the consortium does.
+#[derive(Debug, Default, Clone, InitSpace, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub enum VerificationPolicy {
+    #[default]
+    Consortium,
+    ConsortiumAndSecondary {
+        verifier: Pubkey,
+    },
+    Threshold {
+        #[max_len(MAX_VERIFIERS)]
+        verifiers: Vec<Pubkey>,
+        threshold: u8,
+    },
+}
+
+/// Records who paid for an outbound message account and when the message was sent, so that
+/// the account can be closed later. Kept apart as the outbound message holds the payload as-is.
+#[account]
+#[derive(InitSpace)]
+pub struct OutboundMessageRecord {
+    pub bump: u8,
+    pub payer: Pubkey,
+    pub sent_at: i64,
 }
 
 /// The state of a payload including a GMP message
@@ -52,28 +121,162 @@ pub enum MessageState {
     Delivered,
     /// The state of a payload after it has been handled
     Handled,
+    /// The state of a payload cancelled before being handled, it can no longer be handled
+    Cancelled,
 }
 
 #[account]
 pub struct MessageV1Info {
     pub status: MessageState,
     pub message: MessageV1,
+    // receives the rent freed when the message is compacted after handling
+    pub deliverer: Pubkey,
+    // set when the message was delivered as a MessageV2 or MessageV3
+    pub v2_fields: Option<MessageV2Fields>,
+    // whether the message is sequenced and the path was ordered when it was delivered
+    pub ordered: bool,
+    // unix timestamp of the delivery, from which the executor fallback delay runs
+    pub delivered_at: i64,
 }
 
 impl MessageV1Info {
     pub fn size(message_v1_size: usize) -> usize {
-        return 1 + message_v1_size; // 1 for the status enum
+        // 1 for the status enum, 32 for the deliverer, 1 for the option of the v2 fields,
+        // 1 for the ordered flag, 8 for the delivery timestamp
+        return 1 + 32 + message_v1_size + 1 + MessageV2Fields::SIZE + 1 + 8;
+    }
+
+    /// The payload the message was delivered with, to recompute its hash.
+    pub fn to_session_payload(&self) -> Vec<u8> {
+        self.message.to_versioned_session_payload(self.v2_fields.as_ref())
+    }
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.v2_fields.as_ref().is_some_and(|f| f.is_expired(now))
+    }
+
+    pub fn ack_requested(&self) -> bool {
+        self.v2_fields.as_ref().is_some_and(|f| f.ack_requested())
+    }
+
+    /// The sequence of the message when it must be handled in order on its path.
+    pub fn ordered_sequence(&self) -> Option<u64> {
+        self.v2_fields
+            .as_ref()
+            .and_then(MessageV2Fields::sequence)
+            .filter(|_| self.ordered)
     }
 }
 
-// todo: implement sender specific config
+/// The layout of [`MessageV1Info`] before the delivery details were appended. Only used by
+/// `migrate_message_info` to read the existing account.
+#[derive(AnchorSerialize, AnchorDeserialize)]
+pub struct LegacyMessageV1Info {
+    pub status: MessageState,
+    pub message: MessageV1,
+}
+
+/// What remains of a `MessageV1Info` once the message is handled or cancelled. The account
+/// is kept so that the payload hash cannot be delivered again.
+#[account]
+#[derive(InitSpace)]
+pub struct HandledMessageInfo {
+    pub status: MessageState,
+    pub payload_hash: [u8; 32],
+}
 
+/// The allowlists and limits of a sender apply when `send_message` is given its sender config,
+/// which senders relying on a raised payload size or disabled fees always provide.
 #[account]
 #[derive(InitSpace)]
 pub struct SenderConfig {
     pub bump: u8,
     pub max_payload_size: u32,
     pub fee_disabled: bool,
+    // Limits the number of messages and of body bytes sent by the sender over all paths
+    pub message_rate_limit: RateLimitTokenBucket,
+    pub bytes_rate_limit: RateLimitTokenBucket,
+    // Destination chains the sender can send to, any enabled path when empty
+    #[max_len(MAX_SENDER_DESTINATIONS)]
+    pub allowed_destination_chain_ids: Vec<[u8; 32]>,
+    // Recipients the sender can send to, any recipient when empty
+    #[max_len(MAX_SENDER_RECIPIENTS)]
+    pub allowed_recipients: Vec<[u8; 32]>,
+}
+
+impl SenderConfig {
+    /// The space of the account before the rate limits and allowlists were appended.
+    pub const LEGACY_SPACE: usize = 1 + 4 + 1;
+
+    pub fn allows_destination(&self, destination_chain_id: &[u8; 32]) -> bool {
+        self.allowed_destination_chain_ids.is_empty()
+            || self.allowed_destination_chain_ids.contains(destination_chain_id)
+    }
+
+    pub fn allows_recipient(&self, recipient: &[u8; 32]) -> bool {
+        self.allowed_recipients.is_empty() || self.allowed_recipients.contains(recipient)
+    }
+}
+
+/// Overrides the fee schedule of an outbound path for a given sender.
+#[account]
+#[derive(InitSpace)]
+pub struct SenderFeeSchedule {
+    pub bump: u8,
+    pub fee_schedule: FeeSchedule,
+}
+
+/// Stages a message body too large to fit in a transaction, the body follows the
+/// account fields and grows with every write.
+#[account]
+#[derive(InitSpace)]
+pub struct MessageBuffer {
+    pub bump: u8,
+    // the sender authority allowed to write to the buffer and send it
+    pub owner: Pubkey,
+}
+
+impl MessageBuffer {
+    pub const BODY_OFFSET: usize = 8 + MessageBuffer::INIT_SPACE;
+
+    pub fn body_len(info: &AccountInfo) -> usize {
+        info.data_len().saturating_sub(Self::BODY_OFFSET)
+    }
+}
+
+/// The program a sender registers to be called when the acknowledgement of one of
+/// its messages is handled.
+#[account]
+#[derive(InitSpace)]
+pub struct AckCallback {
+    pub bump: u8,
+    pub callback_program: Pubkey,
+}
+
+/// The executors a recipient allows to handle its messages. A sender designates them by
+/// setting the address of this account as the destination caller of a message.
+#[account]
+#[derive(InitSpace)]
+pub struct ExecutorAllowlist {
+    pub bump: u8,
+    pub recipient: Pubkey,
+    #[max_len(MAX_EXECUTORS)]
+    pub executors: Vec<Pubkey>,
+    // seconds after delivery from which anyone can handle a message of the recipient with a
+    // destination caller, designating the allowlist or not, 0 to disable
+    pub fallback_delay: i64,
+}
+
+/// An SPL token approved to pay GMP fees in place of lamports.
+#[account]
+#[derive(InitSpace)]
+pub struct FeeToken {
+    pub bump: u8,
+    pub mint: Pubkey,
+    // the treasury token account collecting fees paid with this token
+    pub treasury_token_account: Pubkey,
+    // token base units charged per FEE_TOKEN_RATE_BASE lamports of fee
+    pub rate: u64,
 }
 
 #[derive(Clone, Copy, AnchorSerialize, AnchorDeserialize, PartialEq, InitSpace)]
@@ -0,0 +1,207 @@
+use anchor_lang::error::ErrorCode;
+use anchor_lang::prelude::*;
+
+use crate::constants::{ACK_CALLBACK_SEED, OUTBOUND_MESSAGE, OUTBOUND_MESSAGE_RECORD_SEED};
+use crate::errors::MailboxError;
+use crate::events::MessageSent;
+use crate::instructions::handle_message::invoke_with_message_info;
+use crate::state::{
+    AckCallback, Config, InboundMessagePath, OutboundMessagePath, OutboundMessageRecord,
+};
+use crate::utils::accounts::{create_pda_account, resize_pda_account};
+use crate::utils::cpi::gmp_ack_instr_data;
+use crate::utils::message_utils::{AckBody, MessageV1};
+
+/// The accounts needed to send an acknowledgement back to the source chain.
+pub struct AckOutbound<'a, 'info> {
+    /// The outbound path back to the source chain of the acknowledged message
+    pub outbound_message_path: &'a OutboundMessagePath,
+    /// The account of the acknowledgement message, at the outbound message PDA of the current nonce
+    pub outbound_message: AccountInfo<'info>,
+    /// The record of the acknowledgement message, at the outbound message record PDA of the
+    /// current nonce
+    pub outbound_message_record: AccountInfo<'info>,
+    pub payer: AccountInfo<'info>,
+    pub system_program: AccountInfo<'info>,
+}
+
+/// Sends an acknowledgement of a handled message to the mailbox of its source chain, as an
+/// outbound message from this mailbox. Acknowledgements are not charged any fee nor counted
+/// against rate limits, they are part of the message they acknowledge.
+///
+/// Returns the `MessageSent` event of the acknowledgement, for the instruction to emit it with
+/// `emit_cpi!`.
+pub fn send_ack(
+    config: &mut Config,
+    inbound_message_path: &InboundMessagePath,
+    ack: AckOutbound,
+    acked_message: &MessageV1,
+    acked_payload_hash: [u8; 32],
+    result: &[u8],
+) -> Result<MessageSent> {
+    require!(
+        inbound_message_path.identifier == acked_message.message_path_identifier
+            && ack.outbound_message_path.destination_chain_id == inbound_message_path.source_chain_id,
+        MailboxError::InvalidMessagePath
+    );
+
+    let nonce = config.global_nonce;
+    let message = MessageV1 {
+        message_path_identifier: ack.outbound_message_path.identifier,
+        nonce,
+        sender: crate::ID.to_bytes(),
+        recipient: inbound_message_path.source_mailbox_address,
+        destination_caller: None,
+        body: AckBody {
+            payload_hash: acked_payload_hash,
+            sender: acked_message.sender,
+            result: result.to_vec(),
+        }
+        .to_bytes(),
+    };
+    let (payload_hash, payload) = message.calculate_payload_hash();
+
+    let nonce_bytes = nonce.to_be_bytes();
+    create_pda(
+        &ack,
+        &ack.outbound_message,
+        "ack_outbound_message",
+        &[OUTBOUND_MESSAGE, &nonce_bytes],
+        payload.len(),
+    )?;
+    // Write payload to outbound message account as-is
+    ack.outbound_message
+        .try_borrow_mut_data()?
+        .copy_from_slice(&payload);
+
+    let record_bump = create_pda(
+        &ack,
+        &ack.outbound_message_record,
+        "ack_outbound_message_record",
+        &[OUTBOUND_MESSAGE_RECORD_SEED, &nonce_bytes],
+        8 + OutboundMessageRecord::INIT_SPACE,
+    )?;
+    OutboundMessageRecord {
+        bump: record_bump,
+        payer: ack.payer.key(),
+        sent_at: Clock::get()?.unix_timestamp,
+    }
+    .try_serialize(&mut &mut ack.outbound_message_record.try_borrow_mut_data()?[..])?;
+
+    emit!(crate::events::AckSent {
+        payload_hash: acked_payload_hash,
+        nonce,
+    });
+
+    config.global_nonce = nonce.checked_add(1).unwrap();
+    Ok(MessageSent {
+        nonce,
+        payload_hash,
+        message_path_identifier: message.message_path_identifier,
+        destination_chain_id: ack.outbound_message_path.destination_chain_id,
+        sender: message.sender,
+        recipient: message.recipient,
+        body_length: message.body_length() as u64,
+        fee: 0,
+        fee_mint: None,
+    })
+}
+
+/// Creates the account at the PDA of the given seeds, owned by the mailbox and paid for by the
+/// payer of the acknowledgement. An account already owned by the mailbox, an outbound message
+/// reserved with `reserve_outbound_message`, is resized instead. Returns the bump of the PDA.
+fn create_pda<'info>(
+    ack: &AckOutbound<'_, 'info>,
+    account: &AccountInfo<'info>,
+    account_name: &str,
+    seeds: &[&[u8]],
+    space: usize,
+) -> Result<u8> {
+    let (expected, bump) = Pubkey::find_program_address(seeds, &crate::ID);
+    if account.key() != expected {
+        return Err(error!(ErrorCode::ConstraintSeeds)
+            .with_account_name(account_name)
+            .with_pubkeys((account.key(), expected)));
+    }
+
+    if account.owner == &crate::ID {
+        resize_pda_account(&ack.payer, account, &ack.system_program, space)?;
+        return Ok(bump);
+    }
+    let bump_seed = [bump];
+    let mut signer_seeds = seeds.to_vec();
+    signer_seeds.push(&bump_seed);
+    create_pda_account(
+        &ack.payer,
+        account,
+        &ack.system_program,
+        &signer_seeds,
+        space,
+    )?;
+    Ok(bump)
+}
+
+/// Handles an acknowledgement sent by the mailbox of the source chain of the message,
+/// invoking the callback registered by the sender of the acknowledged message, if any.
+///
+/// The callback program comes first in the remaining accounts, followed by the accounts it needs.
+pub fn handle_ack<'info>(
+    message: &MessageV1,
+    inbound_message_path: Option<&InboundMessagePath>,
+    ack_callback: Option<&AccountInfo<'info>>,
+    message_info: &AccountInfo<'info>,
+    remaining_accounts: &[AccountInfo<'info>],
+    payload_hash: [u8; 32],
+    message_info_bump: u8,
+) -> Result<()> {
+    let inbound_message_path =
+        inbound_message_path.ok_or(MailboxError::MissingInboundMessagePath)?;
+    // only the remote mailbox sends acknowledgements
+    require!(
+        inbound_message_path.identifier == message.message_path_identifier
+            && inbound_message_path.source_mailbox_address == message.sender,
+        MailboxError::InvalidAck
+    );
+    let ack = AckBody::from_bytes(&message.body)?;
+
+    let ack_callback = ack_callback.ok_or(MailboxError::InvalidAckCallback)?;
+    let (expected, _) =
+        Pubkey::find_program_address(&[ACK_CALLBACK_SEED, &ack.sender], &crate::ID);
+    require_keys_eq!(
+        ack_callback.key(),
+        expected,
+        MailboxError::InvalidAckCallback
+    );
+
+    // senders without a registered callback only get the event
+    let callback_program = match ack_callback.owner == &crate::ID && !ack_callback.data_is_empty() {
+        true => {
+            let callback = AckCallback::try_deserialize(&mut &ack_callback.try_borrow_data()?[..])?;
+            let (program, accounts) = remaining_accounts
+                .split_first()
+                .ok_or(MailboxError::InvalidAckCallback)?;
+            require_keys_eq!(
+                program.key(),
+                callback.callback_program,
+                MailboxError::InvalidAckCallback
+            );
+            invoke_with_message_info(
+                message_info,
+                &callback.callback_program,
+                accounts,
+                gmp_ack_instr_data(payload_hash, ack.payload_hash, &ack.result),
+                payload_hash,
+                message_info_bump,
+            )?;
+            Some(callback.callback_program)
+        }
+        false => None,
+    };
+
+    emit!(crate::events::AckHandled {
+        payload_hash: ack.payload_hash,
+        sender: ack.sender,
+        callback_program,
+    });
+    Ok(())
+}
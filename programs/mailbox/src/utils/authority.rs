@@ -0,0 +1,21 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::MAILBOX_AUTHORITY_SEED;
+use crate::errors::MailboxError;
+
+/// Checks that `authority` acts for `account`, a sender or recipient of messages: the account
+/// itself when it can sign, such as a wallet, or the `[MAILBOX_AUTHORITY_SEED]` PDA of the program
+/// otherwise. Unlike the owner of a signing account, the PDA can only be signed for by the program.
+pub fn check_authority(authority: &Signer, account: &Pubkey) -> Result<()> {
+    require!(
+        is_authority(authority.key, account),
+        MailboxError::Unauthorized
+    );
+    Ok(())
+}
+
+/// Whether the signing `authority` acts for `account`, see `check_authority`.
+pub fn is_authority(authority: &Pubkey, account: &Pubkey) -> bool {
+    authority == account
+        || *authority == Pubkey::find_program_address(&[MAILBOX_AUTHORITY_SEED], account).0
+}
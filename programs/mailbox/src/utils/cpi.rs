@@ -15,3 +15,26 @@ pub fn gmp_receive_instr_data(payload_hash: [u8; 32]) -> Vec<u8> {
     data.extend_from_slice(&payload_hash);
     data
 }
+
+pub const GMP_ACK_DISCRIMINATOR: [u8; 8] = [0x1c, 0x52, 0x8f, 0x31, 0xf7, 0x81, 0xbf, 0xd8];
+
+/// The data for the GMP ack instruction issued by the mailbox program to the callback
+/// program registered by a sender when the acknowledgement of one of its messages is handled.
+///
+/// The discriminator is followed by the payload hash of the acknowledgement message, the
+/// payload hash of the acknowledged message and the data returned by its recipient,
+/// encoded as the `gmp_ack(payload_hash: [u8; 32], acked_payload_hash: [u8; 32], result: Vec<u8>)`
+/// Anchor instruction.
+pub fn gmp_ack_instr_data(
+    payload_hash: [u8; 32],
+    acked_payload_hash: [u8; 32],
+    result: &[u8],
+) -> Vec<u8> {
+    let mut data = Vec::with_capacity(8 + 32 + 32 + 4 + result.len());
+    data.extend_from_slice(&GMP_ACK_DISCRIMINATOR);
+    data.extend_from_slice(&payload_hash);
+    data.extend_from_slice(&acked_payload_hash);
+    data.extend_from_slice(&(result.len() as u32).to_le_bytes());
+    data.extend_from_slice(result);
+    data
+}
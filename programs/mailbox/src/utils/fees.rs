@@ -0,0 +1,111 @@
+use crate::constants::{FEE_ADJUSTMET_BASE, FEE_TOKEN_RATE_BASE};
+use crate::errors::MailboxError;
+use crate::state::FeeSchedule;
+use crate::utils::message_utils::MessageV1;
+
+/// Calculates the fee to send a message with a body of `body_length` bytes.
+///
+/// The sender specific schedule, if any, takes precedence over the path schedule.
+/// Senders with fees disabled pay nothing unless they request a `fee_override`,
+/// expressed in `FEE_ADJUSTMET_BASE` units, which scales both components of the schedule.
+pub fn message_fee(
+    path_schedule: &FeeSchedule,
+    sender_schedule: Option<&FeeSchedule>,
+    fee_disabled: bool,
+    fee_override: u64,
+    body_length: usize,
+) -> Result<u64, MailboxError> {
+    let schedule = sender_schedule.unwrap_or(path_schedule);
+    let (base_fee, fee_per_byte) = match fee_disabled {
+        true if fee_override == 0 => return Ok(0),
+        true => (
+            adjust(schedule.base_fee, fee_override)?,
+            adjust(schedule.fee_per_byte, fee_override)?,
+        ),
+        false => (schedule.base_fee, schedule.fee_per_byte),
+    };
+
+    MessageV1::accountable_abi_bytes(body_length)
+        .checked_mul(fee_per_byte)
+        .and_then(|fee| fee.checked_add(base_fee))
+        .ok_or(MailboxError::FeeOverflow)
+}
+
+/// Converts a fee in lamports to base units of a fee token charged at `rate`
+/// units per `FEE_TOKEN_RATE_BASE` lamports, rounding up in favour of the treasury.
+pub fn token_fee(fee: u64, rate: u64) -> Result<u64, MailboxError> {
+    let amount = (fee as u128 * rate as u128).div_ceil(FEE_TOKEN_RATE_BASE as u128);
+    u64::try_from(amount).map_err(|_| MailboxError::FeeOverflow)
+}
+
+fn adjust(fee: u64, fee_override: u64) -> Result<u64, MailboxError> {
+    fee.checked_mul(fee_override)
+        .map(|fee| fee / FEE_ADJUSTMET_BASE)
+        .ok_or(MailboxError::FeeOverflow)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const PATH_SCHEDULE: FeeSchedule = FeeSchedule {
+        base_fee: 5000,
+        fee_per_byte: 1000,
+    };
+
+    #[test]
+    fn test_message_fee_path_schedule() {
+        // 4 bytes of body are accounted as 260 abi bytes
+        assert_eq!(
+            message_fee(&PATH_SCHEDULE, None, false, 0, 4).unwrap(),
+            5000 + 260 * 1000
+        );
+    }
+
+    #[test]
+    fn test_message_fee_sender_schedule() {
+        let sender_schedule = FeeSchedule {
+            base_fee: 0,
+            fee_per_byte: 10,
+        };
+        assert_eq!(
+            message_fee(&PATH_SCHEDULE, Some(&sender_schedule), false, 0, 4).unwrap(),
+            2600
+        );
+    }
+
+    #[test]
+    fn test_message_fee_disabled() {
+        assert_eq!(message_fee(&PATH_SCHEDULE, None, true, 0, 4).unwrap(), 0);
+        // 40% of the standard fee
+        assert_eq!(
+            message_fee(&PATH_SCHEDULE, None, true, 4000, 4).unwrap(),
+            2000 + 260 * 400
+        );
+    }
+
+    #[test]
+    fn test_message_fee_overflow() {
+        let schedule = FeeSchedule {
+            base_fee: u64::MAX,
+            fee_per_byte: 1,
+        };
+        assert!(matches!(
+            message_fee(&schedule, None, false, 0, 4),
+            Err(MailboxError::FeeOverflow)
+        ));
+    }
+
+    #[test]
+    fn test_token_fee() {
+        // 0.01 SOL at 1000 token units per SOL
+        assert_eq!(token_fee(10_000_000, 1000).unwrap(), 10);
+        // rounded up
+        assert_eq!(token_fee(1, 1000).unwrap(), 1);
+        assert_eq!(token_fee(0, 1000).unwrap(), 0);
+        assert!(matches!(
+            token_fee(u64::MAX, u64::MAX),
+            Err(MailboxError::FeeOverflow)
+        ));
+    }
+}
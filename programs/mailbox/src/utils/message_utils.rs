@@ -7,8 +7,9 @@ use anchor_lang::solana_program::keccak::hash as keccak256;
 use crate::errors::MailboxError;
 
 pub const PAYLOAD_SELECTOR_LENGTH: usize = 4;
-pub const PAYLOAD_MIN_SIZE: usize = PAYLOAD_SELECTOR_LENGTH + 32 * 5;
 pub const MESSAGE_V1_SELECTOR: [u8; PAYLOAD_SELECTOR_LENGTH] = [0xe2, 0x88, 0xfb, 0x4a];
+pub const MESSAGE_V2_SELECTOR: [u8; PAYLOAD_SELECTOR_LENGTH] = [0xa6, 0x0f, 0xb7, 0x2e];
+pub const MESSAGE_V3_SELECTOR: [u8; PAYLOAD_SELECTOR_LENGTH] = [0xac, 0x30, 0x08, 0x40];
 
 pub fn message_path_identifier(
     source_mailbox_address: [u8; 32],
@@ -22,6 +23,109 @@ pub fn message_path_identifier(
     keccak256(&buffer).to_bytes()
 }
 
+/// The fields a MessageV2 adds on top of the MessageV1 ones. A MessageV2 is encoded as
+/// a MessageV1 with these fields placed between the destination caller and the body.
+///
+/// The flags and the sequence are only encoded by a MessageV3, which has its own selector.
+/// Messages with flags are always sent as a MessageV3, the others as a MessageV2, and the
+/// sequence is zero unless `SEQUENCED` is set, so that a message has a single encoding and its
+/// hash can be recomputed from its fields.
+#[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct MessageV2Fields {
+    /// Unix timestamp after which the message can no longer be delivered or handled, 0 for none
+    pub deadline: u64,
+    /// Compute expected to be needed to execute the message on the destination chain
+    pub execution_hint: u64,
+    /// Address entitled to any refund related to the message
+    pub refund_address: [u8; 32],
+    /// Options of the message, see `ACK_REQUESTED` and `SEQUENCED`
+    pub flags: u64,
+    /// Position of the message among the sequenced messages of its outbound path, from 0
+    pub sequence: u64,
+}
+
+impl MessageV2Fields {
+    pub const SIZE: usize = 8 + 8 + 32 + 8 + 8;
+    /// The bytes the fields add to the ABI encoding of a MessageV2
+    pub const V2_ABI_BYTES: u64 = 32 * 3;
+    /// The bytes the fields add to the ABI encoding of a MessageV3, which also encodes the flags
+    /// and the sequence
+    pub const V3_ABI_BYTES: u64 = 32 * 5;
+    /// The destination mailbox sends back an acknowledgement once the message is handled
+    pub const ACK_REQUESTED: u64 = 1;
+    /// The message carries a sequence, which ordered inbound paths handle without gaps
+    pub const SEQUENCED: u64 = 2;
+
+    pub fn is_expired(&self, now: i64) -> bool {
+        self.deadline != 0 && now > 0 && now as u64 > self.deadline
+    }
+
+    pub fn ack_requested(&self) -> bool {
+        self.flags & Self::ACK_REQUESTED != 0
+    }
+
+    /// The sequence of the message when it carries one
+    pub fn sequence(&self) -> Option<u64> {
+        (self.flags & Self::SEQUENCED != 0).then_some(self.sequence)
+    }
+
+    /// Whether the message is encoded as a MessageV3
+    pub fn is_v3(&self) -> bool {
+        self.flags != 0
+    }
+
+    pub fn abi_bytes(&self) -> u64 {
+        match self.is_v3() {
+            true => Self::V3_ABI_BYTES,
+            false => Self::V2_ABI_BYTES,
+        }
+    }
+}
+
+/// The options a sender can set on a message, sent as a MessageV2 when given.
+#[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
+pub struct SendOptions {
+    /// Unix timestamp after which the message can no longer be delivered or handled, 0 for none
+    pub deadline: u64,
+    /// Compute expected to be needed to execute the message on the destination chain
+    pub execution_hint: u64,
+    /// Address entitled to any refund related to the message
+    pub refund_address: [u8; 32],
+    /// Sends the message as a MessageV3 carrying the next sequence of the outbound path
+    pub sequenced: bool,
+}
+
+/// The body of an acknowledgement, sent by a destination mailbox to the source mailbox
+/// once a message requesting one is handled. Encoded as the hash of the acknowledged
+/// payload, its sender and the data returned by its recipient.
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct AckBody {
+    pub payload_hash: [u8; 32],
+    pub sender: [u8; 32],
+    pub result: Vec<u8>,
+}
+
+impl AckBody {
+    pub fn to_bytes(&self) -> Vec<u8> {
+        let mut body = Vec::with_capacity(64 + self.result.len());
+        body.extend_from_slice(&self.payload_hash);
+        body.extend_from_slice(&self.sender);
+        body.extend_from_slice(&self.result);
+        body
+    }
+
+    pub fn from_bytes(bytes: &[u8]) -> Result<Self, MailboxError> {
+        if bytes.len() < 64 {
+            return Err(MailboxError::InvalidAck);
+        }
+        Ok(Self {
+            payload_hash: bytes[..32].try_into().unwrap(),
+            sender: bytes[32..64].try_into().unwrap(),
+            result: bytes[64..].to_vec(),
+        })
+    }
+}
+
 #[derive(Debug, Default, Clone, AnchorSerialize, AnchorDeserialize, PartialEq)]
 pub struct MessageV1 {
     pub message_path_identifier: [u8; 32],
@@ -63,6 +167,17 @@ impl MessageV1 {
     }
 
     pub fn from_session_payload(bytes: &[u8]) -> Result<Self, MailboxError> {
+        match Self::from_versioned_session_payload(bytes)? {
+            (message, None) => Ok(message),
+            (_, Some(_)) => Err(MailboxError::InvalidPayloadSelector),
+        }
+    }
+
+    /// Decodes a MessageV1, MessageV2 or MessageV3 payload, returning for the latter two
+    /// the fields they add on top of MessageV1.
+    pub fn from_versioned_session_payload(
+        bytes: &[u8],
+    ) -> Result<(Self, Option<MessageV2Fields>), MailboxError> {
         let mut reader = BufReader::new(bytes);
 
         // check length is at least for all static fields and length of dynamic fields
@@ -75,8 +190,15 @@ impl MessageV1 {
         // check selector
         let mut selector_bytes = [0u8; 4];
         reader.read_exact(&mut selector_bytes)?;
-        if selector_bytes != MESSAGE_V1_SELECTOR {
-            return Err(MailboxError::InvalidPayloadSelector);
+        // MessageV2 has 3 more static fields, MessageV3 5
+        let extra_fields = match selector_bytes {
+            MESSAGE_V1_SELECTOR => 0,
+            MESSAGE_V2_SELECTOR => 3,
+            MESSAGE_V3_SELECTOR => 5,
+            _ => return Err(MailboxError::InvalidPayloadSelector),
+        };
+        if bytes.len() < 4 + 32 * (7 + extra_fields) {
+            return Err(MailboxError::InvalidPayloadLength);
         }
 
         let mut message_v1 = Self {
@@ -92,9 +214,7 @@ impl MessageV1 {
         reader.read_exact(&mut message_v1.message_path_identifier)?;
 
         // Read nonce
-        let mut nonce_bytes = [0u8; 32];
-        reader.read_exact(&mut nonce_bytes)?;
-        message_v1.nonce = u64::from_be_bytes(nonce_bytes[24..32].try_into().unwrap());
+        message_v1.nonce = read_u64_word(&mut reader)?;
 
         // Read sender
         reader.read_exact(&mut message_v1.sender)?;
@@ -109,62 +229,139 @@ impl MessageV1 {
             message_v1.destination_caller = Some(destination_caller_bytes);
         }
 
+        // Read MessageV2 fields, and the flags and sequence of a MessageV3
+        let v2_fields = match extra_fields {
+            0 => None,
+            _ => {
+                let deadline = read_u64_word(&mut reader)?;
+                let execution_hint = read_u64_word(&mut reader)?;
+                let mut refund_address = [0u8; 32];
+                reader.read_exact(&mut refund_address)?;
+                let (flags, sequence) = match extra_fields {
+                    5 => (read_u64_word(&mut reader)?, read_u64_word(&mut reader)?),
+                    _ => (0, 0),
+                };
+                // a MessageV3 without flags is encoded as a MessageV2
+                if extra_fields == 5 && flags == 0 {
+                    return Err(MailboxError::InvalidPayloadSelector);
+                }
+                let v2_fields = MessageV2Fields {
+                    deadline,
+                    execution_hint,
+                    refund_address,
+                    flags,
+                    sequence,
+                };
+                // a message without the sequenced flag is encoded with a zero sequence
+                if v2_fields.sequence().is_none() && sequence != 0 {
+                    return Err(MailboxError::InvalidPayloadSelector);
+                }
+                Some(v2_fields)
+            }
+        };
+
         // Read body
         // Skip body vector offset
-        reader.consume(32);
-        let mut body_length_bytes = [0u8; 32];
-        reader.read_exact(&mut body_length_bytes)?;
-        let body_length = u64::from_be_bytes(body_length_bytes[24..32].try_into().unwrap());
+        read_u64_word(&mut reader)?;
+        let body_length = read_u64_word(&mut reader)?;
         message_v1.body = vec![0u8; body_length as usize];
         reader.read_exact(&mut message_v1.body)?;
 
-        Ok(message_v1)
+        Ok((message_v1, v2_fields))
     }
 
     pub fn to_session_payload(&self) -> Vec<u8> {
-        let mut message = Vec::with_capacity(PAYLOAD_MIN_SIZE);
+        self.to_versioned_session_payload(None)
+    }
 
-        message.extend_from_slice(&MESSAGE_V1_SELECTOR);
-        message.extend_from_slice(&self.message_path_identifier);
+    /// Encodes the message as a MessageV2 or MessageV3 payload when `v2_fields` are given, as a
+    /// MessageV1 otherwise.
+    pub fn to_versioned_session_payload(&self, v2_fields: Option<&MessageV2Fields>) -> Vec<u8> {
+        let mut message = Vec::with_capacity(Self::versioned_payload_len(self.body.len(), v2_fields));
+        self.write_versioned_session_payload(v2_fields, &mut message)
+            .unwrap();
+        message
+    }
+
+    /// The length of the payload encoding a message with a body of `body_length` bytes.
+    pub fn versioned_payload_len(body_length: usize, v2_fields: Option<&MessageV2Fields>) -> usize {
+        (Self::accountable_abi_bytes(body_length)
+            + v2_fields.map_or(0, MessageV2Fields::abi_bytes)) as usize
+    }
+
+    /// Writes the payload of `to_versioned_session_payload` to `writer`, which lets large
+    /// messages be encoded in place without another copy of their body.
+    pub fn write_versioned_session_payload(
+        &self,
+        v2_fields: Option<&MessageV2Fields>,
+        writer: &mut impl Write,
+    ) -> std::io::Result<()> {
+        writer.write_all(match v2_fields {
+            Some(v2_fields) if v2_fields.is_v3() => &MESSAGE_V3_SELECTOR,
+            Some(_) => &MESSAGE_V2_SELECTOR,
+            None => &MESSAGE_V1_SELECTOR,
+        })?;
+        writer.write_all(&self.message_path_identifier)?;
         let mut nonce_bytes = [0u8; 32];
         nonce_bytes[24..].copy_from_slice(&self.nonce.to_be_bytes());
-        message.extend_from_slice(&nonce_bytes);
-        message.extend_from_slice(&self.sender);
-        message.extend_from_slice(&self.recipient);
-        match self.destination_caller {
-            Some(dst_caller) => {
-                message.extend_from_slice(&dst_caller);
-            }
-            None => {
-                message.extend_from_slice(&[0u8; 32]);
+        writer.write_all(&nonce_bytes)?;
+        writer.write_all(&self.sender)?;
+        writer.write_all(&self.recipient)?;
+        writer.write_all(&self.destination_caller.unwrap_or_default())?;
+        let mut offset: u64 = 192; // 32 * 6
+        if let Some(v2_fields) = v2_fields {
+            let mut word = [0u8; 32];
+            word[24..].copy_from_slice(&v2_fields.deadline.to_be_bytes());
+            writer.write_all(&word)?;
+            word[24..].copy_from_slice(&v2_fields.execution_hint.to_be_bytes());
+            writer.write_all(&word)?;
+            writer.write_all(&v2_fields.refund_address)?;
+            offset = 288; // 32 * 9
+            if v2_fields.is_v3() {
+                word[24..].copy_from_slice(&v2_fields.flags.to_be_bytes());
+                writer.write_all(&word)?;
+                word[24..].copy_from_slice(&v2_fields.sequence.to_be_bytes());
+                writer.write_all(&word)?;
+                offset = 352; // 32 * 11
             }
         }
         // Fist put the offset
         let mut offset_bytes = [0u8; 32];
-        let offset: u64 = 192; // 32 * 6
         offset_bytes[24..].copy_from_slice(&offset.to_be_bytes());
-        message.extend_from_slice(&offset_bytes);
+        writer.write_all(&offset_bytes)?;
         // Next put the length of the body
         let mut size_bytes = [0u8; 32];
-        size_bytes[24..].copy_from_slice(&self.body.len().to_be_bytes());
-        message.extend_from_slice(&size_bytes);
+        size_bytes[24..].copy_from_slice(&(self.body.len() as u64).to_be_bytes());
+        writer.write_all(&size_bytes)?;
         // Next add the body
-        message.extend_from_slice(&self.body); // ToDO: add padding to 
+        writer.write_all(&self.body)?;
         // Lastly put some padding if necessary
         let padding_len = (32 - self.body.len() % 32) % 32;
-        if padding_len > 0 {
-            for _ in 0..padding_len {
-                message.push(0);
-            }
-        }
-
-        message
+        writer.write_all(&[0u8; 32][..padding_len])
     }
 
     pub fn calculate_payload_hash(&self) -> ([u8; 32], Vec<u8>) {
-        let payload = self.to_session_payload();
+        self.calculate_versioned_payload_hash(None)
+    }
+
+    pub fn calculate_versioned_payload_hash(
+        &self,
+        v2_fields: Option<&MessageV2Fields>,
+    ) -> ([u8; 32], Vec<u8>) {
+        let payload = self.to_versioned_session_payload(v2_fields);
         (sha256(&payload).to_bytes(), payload)
-    } 
+    }
+}
+
+/// Reads an ABI encoded uint256 word holding a u64, rejecting it when any of its higher bytes is set
+/// rather than silently truncating the value.
+fn read_u64_word(reader: &mut impl Read) -> Result<u64, MailboxError> {
+    let mut word = [0u8; 32];
+    reader.read_exact(&mut word)?;
+    if word[..24] != [0u8; 24] {
+        return Err(MailboxError::Uint256Overflow);
+    }
+    Ok(u64::from_be_bytes(word[24..].try_into().unwrap()))
 }
 
 #[derive(AnchorSerialize, AnchorDeserialize)]
@@ -249,4 +446,181 @@ mod tests {
         let accountable_abi_bytes = MessageV1::accountable_abi_bytes(message.body_length());
         assert_eq!(accountable_abi_bytes, 260);
     }
+
+    fn message_v2() -> (MessageV1, MessageV2Fields) {
+        let message = MessageV1 {
+            message_path_identifier: [1u8; 32],
+            nonce: 7,
+            sender: [2u8; 32],
+            recipient: [3u8; 32],
+            destination_caller: Some([4u8; 32]),
+            body: b"TEST".to_vec(),
+        };
+        let v2_fields = MessageV2Fields {
+            deadline: 1_700_000_000,
+            execution_hint: 200_000,
+            refund_address: [5u8; 32],
+            flags: MessageV2Fields::ACK_REQUESTED | MessageV2Fields::SEQUENCED,
+            sequence: 3,
+        };
+        (message, v2_fields)
+    }
+
+    #[test]
+    fn test_selectors() {
+        let hash = keccak256(b"MessageV1(bytes32,uint256,bytes32,bytes32,bytes32,bytes)").to_bytes();
+        assert_eq!(MESSAGE_V1_SELECTOR, hash[..4]);
+        let hash = keccak256(
+            b"MessageV2(bytes32,uint256,bytes32,bytes32,bytes32,uint256,uint256,bytes32,bytes)",
+        )
+        .to_bytes();
+        assert_eq!(MESSAGE_V2_SELECTOR, hash[..4]);
+        let hash = keccak256(
+            b"MessageV3(bytes32,uint256,bytes32,bytes32,bytes32,uint256,uint256,bytes32,uint256,uint256,bytes)",
+        )
+        .to_bytes();
+        assert_eq!(MESSAGE_V3_SELECTOR, hash[..4]);
+    }
+
+    #[test]
+    fn test_message_v2_to_from_session_payload() {
+        let (message, mut v2_fields) = message_v2();
+        v2_fields.flags = 0;
+        v2_fields.sequence = 0;
+        let payload = message.to_versioned_session_payload(Some(&v2_fields));
+        assert_eq!(payload[..4], MESSAGE_V2_SELECTOR);
+        // 9 static fields, body length and one slot of body
+        assert_eq!(payload.len(), 4 + 32 * 11);
+        assert_eq!(
+            payload.len(),
+            MessageV1::versioned_payload_len(message.body_length(), Some(&v2_fields))
+        );
+
+        let (decoded, decoded_v2_fields) = MessageV1::from_versioned_session_payload(&payload).unwrap();
+        assert_eq!(decoded, message);
+        assert!(!decoded_v2_fields.as_ref().unwrap().ack_requested());
+        assert_eq!(decoded_v2_fields, Some(v2_fields));
+    }
+
+    #[test]
+    fn test_message_v3_to_from_session_payload() {
+        let (message, v2_fields) = message_v2();
+        let payload = message.to_versioned_session_payload(Some(&v2_fields));
+        assert_eq!(payload[..4], MESSAGE_V3_SELECTOR);
+        // 11 static fields, body length and one slot of body
+        assert_eq!(payload.len(), 4 + 32 * 13);
+        assert_eq!(
+            payload.len(),
+            MessageV1::versioned_payload_len(message.body_length(), Some(&v2_fields))
+        );
+
+        // written in place into a buffer of the payload length
+        let mut buffer = vec![0u8; payload.len()];
+        message
+            .write_versioned_session_payload(Some(&v2_fields), &mut &mut buffer[..])
+            .unwrap();
+        assert_eq!(buffer, payload);
+
+        let (decoded, decoded_v2_fields) = MessageV1::from_versioned_session_payload(&payload).unwrap();
+        assert_eq!(decoded, message);
+        assert!(decoded_v2_fields.as_ref().unwrap().ack_requested());
+        assert_eq!(decoded_v2_fields.as_ref().unwrap().sequence(), Some(3));
+        assert_eq!(decoded_v2_fields, Some(v2_fields));
+    }
+
+    #[test]
+    fn test_message_v3_without_flags_rejected() {
+        let (message, mut v2_fields) = message_v2();
+        v2_fields.flags = MessageV2Fields::ACK_REQUESTED;
+        v2_fields.sequence = 0;
+        let mut payload = message.to_versioned_session_payload(Some(&v2_fields));
+        // lowest byte of the flags
+        payload[4 + 32 * 9 - 1] = 0;
+        assert!(matches!(
+            MessageV1::from_versioned_session_payload(&payload),
+            Err(MailboxError::InvalidPayloadSelector)
+        ));
+    }
+
+    #[test]
+    fn test_message_v3_unsequenced_with_sequence_rejected() {
+        let (message, mut v2_fields) = message_v2();
+        v2_fields.flags = MessageV2Fields::ACK_REQUESTED;
+        v2_fields.sequence = 0;
+        let mut payload = message.to_versioned_session_payload(Some(&v2_fields));
+        assert_eq!(
+            MessageV1::from_versioned_session_payload(&payload).unwrap().1,
+            Some(v2_fields)
+        );
+        // lowest byte of the sequence
+        payload[4 + 32 * 10 - 1] = 1;
+        assert!(matches!(
+            MessageV1::from_versioned_session_payload(&payload),
+            Err(MailboxError::InvalidPayloadSelector)
+        ));
+    }
+
+    #[test]
+    fn test_message_v1_from_versioned_session_payload() {
+        let (message, _) = message_v2();
+        let payload = message.to_session_payload();
+        let (decoded, v2_fields) = MessageV1::from_versioned_session_payload(&payload).unwrap();
+        assert_eq!(decoded, message);
+        assert!(v2_fields.is_none());
+    }
+
+    #[test]
+    fn test_message_v1_from_session_payload_rejects_v2() {
+        let (message, v2_fields) = message_v2();
+        let payload = message.to_versioned_session_payload(Some(&v2_fields));
+        assert!(matches!(
+            MessageV1::from_session_payload(&payload),
+            Err(MailboxError::InvalidPayloadSelector)
+        ));
+    }
+
+    #[test]
+    fn test_message_from_session_payload_rejects_uint256_overflow() {
+        let (message, v2_fields) = message_v2();
+        let mut payload = message.to_session_payload();
+        // highest byte of the nonce
+        payload[4 + 32] = 1;
+        assert!(matches!(
+            MessageV1::from_session_payload(&payload),
+            Err(MailboxError::Uint256Overflow)
+        ));
+
+        let mut payload = message.to_versioned_session_payload(Some(&v2_fields));
+        // highest byte of the deadline
+        payload[4 + 32 * 5] = 1;
+        assert!(matches!(
+            MessageV1::from_versioned_session_payload(&payload),
+            Err(MailboxError::Uint256Overflow)
+        ));
+    }
+
+    #[test]
+    fn test_message_v2_is_expired() {
+        let (_, mut v2_fields) = message_v2();
+        assert!(!v2_fields.is_expired(1_700_000_000));
+        assert!(v2_fields.is_expired(1_700_000_001));
+        v2_fields.deadline = 0;
+        assert!(!v2_fields.is_expired(i64::MAX));
+    }
+
+    #[test]
+    fn test_ack_body_to_from_bytes() {
+        let ack = AckBody {
+            payload_hash: [1u8; 32],
+            sender: [2u8; 32],
+            result: b"OK".to_vec(),
+        };
+        let bytes = ack.to_bytes();
+        assert_eq!(bytes.len(), 66);
+        assert_eq!(AckBody::from_bytes(&bytes).unwrap(), ack);
+        assert!(matches!(
+            AckBody::from_bytes(&bytes[..63]),
+            Err(MailboxError::InvalidAck)
+        ));
+    }
 }
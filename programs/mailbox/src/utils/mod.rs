@@ -1,2 +1,7 @@
+pub(crate) mod accounts;
+pub(crate) mod ack;
+pub(crate) mod authority;
 pub(crate) mod cpi;
+pub(crate) mod fees;
 pub(crate) mod message_utils;
+pub(crate) mod verification;
@@ -0,0 +1,161 @@
+use anchor_lang::prelude::*;
+
+use consortium::constants::VALIDATED_PAYLOAD_SEED;
+
+use crate::constants::MAX_VERIFIERS;
+use crate::errors::MailboxError;
+use crate::state::VerificationPolicy;
+
+/// Checks that a policy can be satisfied and does not count any verifier twice. The secondary
+/// verifier must differ from the consortium, which would otherwise attest a payload twice.
+pub fn validate_policy(policy: &VerificationPolicy, consortium: &Pubkey) -> Result<()> {
+    let valid = match policy {
+        VerificationPolicy::Consortium => true,
+        VerificationPolicy::ConsortiumAndSecondary { verifier } => {
+            *verifier != Pubkey::default() && verifier != consortium
+        }
+        VerificationPolicy::Threshold { verifiers, threshold } => {
+            let unique = verifiers
+                .iter()
+                .enumerate()
+                .all(|(i, v)| *v != Pubkey::default() && !verifiers[..i].contains(v));
+            unique
+                && verifiers.len() <= MAX_VERIFIERS
+                && *threshold > 0
+                && *threshold as usize <= verifiers.len()
+        }
+    };
+    require!(valid, MailboxError::InvalidVerificationPolicy);
+    Ok(())
+}
+
+/// The verifiers, other than the consortium, whose attestation accounts are expected,
+/// in the order they must be provided.
+pub fn attesting_verifiers(policy: &VerificationPolicy, consortium: &Pubkey) -> Vec<Pubkey> {
+    match policy {
+        VerificationPolicy::Consortium => vec![],
+        VerificationPolicy::ConsortiumAndSecondary { verifier } => vec![*verifier],
+        VerificationPolicy::Threshold { verifiers, .. } => verifiers
+            .iter()
+            .filter(|v| *v != consortium)
+            .copied()
+            .collect(),
+    }
+}
+
+/// Verifies a payload against the policy of its inbound path.
+///
+/// `consortium_validated` tells whether the consortium has validated the payload, and
+/// `attestations` holds one account per verifier returned by `attesting_verifiers`. An
+/// attestation account that does not exist counts as a missing attestation.
+pub fn verify_payload(
+    policy: &VerificationPolicy,
+    consortium: &Pubkey,
+    consortium_validated: bool,
+    attestations: &[AccountInfo],
+    payload_hash: &[u8; 32],
+) -> Result<()> {
+    let verifiers = attesting_verifiers(policy, consortium);
+    require_eq!(
+        attestations.len(),
+        verifiers.len(),
+        MailboxError::InvalidVerifierAttestation
+    );
+
+    let mut attested = 0usize;
+    for (verifier, attestation) in verifiers.iter().zip(attestations) {
+        let (expected, _) =
+            Pubkey::find_program_address(&[VALIDATED_PAYLOAD_SEED, payload_hash], verifier);
+        require_keys_eq!(
+            attestation.key(),
+            expected,
+            MailboxError::InvalidVerifierAttestation
+        );
+        if attestation.owner == verifier && !attestation.data_is_empty() {
+            attested += 1;
+        }
+    }
+
+    let verified = match policy {
+        VerificationPolicy::Consortium => consortium_validated,
+        VerificationPolicy::ConsortiumAndSecondary { .. } => consortium_validated && attested == 1,
+        VerificationPolicy::Threshold { verifiers, threshold } => {
+            let consortium_vote = consortium_validated && verifiers.contains(consortium);
+            attested + consortium_vote as usize >= *threshold as usize
+        }
+    };
+    require!(verified, MailboxError::PayloadNotVerified);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_validate_policy() {
+        let consortium = Pubkey::new_unique();
+        let a = Pubkey::new_unique();
+        let b = Pubkey::new_unique();
+
+        assert!(validate_policy(&VerificationPolicy::Consortium, &consortium).is_ok());
+        assert!(validate_policy(
+            &VerificationPolicy::ConsortiumAndSecondary { verifier: a },
+            &consortium
+        )
+        .is_ok());
+        assert!(validate_policy(
+            &VerificationPolicy::ConsortiumAndSecondary {
+                verifier: Pubkey::default()
+            },
+            &consortium
+        )
+        .is_err());
+        // the consortium cannot be its own secondary verifier
+        assert!(validate_policy(
+            &VerificationPolicy::ConsortiumAndSecondary { verifier: consortium },
+            &consortium
+        )
+        .is_err());
+        assert!(validate_policy(
+            &VerificationPolicy::Threshold {
+                verifiers: vec![a, b],
+                threshold: 2
+            },
+            &consortium
+        )
+        .is_ok());
+        // threshold out of range
+        for threshold in [0, 3] {
+            assert!(validate_policy(
+                &VerificationPolicy::Threshold {
+                    verifiers: vec![a, b],
+                    threshold
+                },
+                &consortium
+            )
+            .is_err());
+        }
+        // duplicated verifier
+        assert!(validate_policy(
+            &VerificationPolicy::Threshold {
+                verifiers: vec![a, a],
+                threshold: 2
+            },
+            &consortium
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_attesting_verifiers_skips_consortium() {
+        let consortium = Pubkey::new_unique();
+        let a = Pubkey::new_unique();
+        let policy = VerificationPolicy::Threshold {
+            verifiers: vec![a, consortium],
+            threshold: 2,
+        };
+        assert_eq!(attesting_verifiers(&policy, &consortium), vec![a]);
+        assert!(attesting_verifiers(&VerificationPolicy::Consortium, &consortium).is_empty());
+    }
+}
@@ -1,2 +1,5 @@
 pub const CONFIG_SEED: &[u8] = b"mailbox_receiver_config";
-pub const MESSAGE_HANDLED_SEED: &[u8] = b"message_handled";
\ No newline at end of file
+pub const MESSAGE_HANDLED_SEED: &[u8] = b"message_handled";
+pub const ACK_RECEIVED_SEED: &[u8] = b"ack_received";
+// The PDA the receiver signs with to act for itself in the mailbox
+pub const MAILBOX_AUTHORITY_SEED: &[u8] = b"mailbox_authority";
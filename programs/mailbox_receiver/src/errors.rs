@@ -7,4 +7,8 @@ pub enum ReceiverError {
     InvalidMailboxAddress,
     #[msg("Unauthorized function call")]
     Unauthorized,
+    #[msg("Message already echoed")]
+    AlreadyEchoed,
+    #[msg("Outbound path does not lead back to the source chain of the message")]
+    InvalidEchoPath,
 }
\ No newline at end of file
@@ -0,0 +1,109 @@
+use anchor_lang::prelude::*;
+use mailbox::cpi::accounts::SendMessage;
+use mailbox::state::{InboundMessagePath, OutboundMessagePath};
+
+use crate::constants::{CONFIG_SEED, MESSAGE_HANDLED_SEED};
+use crate::errors::ReceiverError;
+use crate::state::{Config, MessageHandled};
+
+// The reply cannot be sent from `gmp_receive`, as the mailbox handling the message cannot be
+// invoked again while on the call stack, so it is sent by a separate instruction, paid by anyone.
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32])]
+pub struct Echo<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+
+    // The config PDA signs for the receiver program, which is the sender of the reply
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        mut,
+        seeds = [MESSAGE_HANDLED_SEED, &payload_hash],
+        bump,
+        constraint = !message_handled.echoed @ ReceiverError::AlreadyEchoed,
+    )]
+    pub message_handled: Account<'info, MessageHandled>,
+
+    /// CHECK: The mailbox program the receiver is configured with
+    #[account(address = config.mailbox_address @ ReceiverError::InvalidMailboxAddress)]
+    pub mailbox: UncheckedAccount<'info>,
+
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub mailbox_config: UncheckedAccount<'info>,
+
+    // The path the message came from, to send the reply back to its source chain
+    #[account(
+        constraint = inbound_message_path.identifier == message_handled.message_path_identifier @ ReceiverError::InvalidEchoPath,
+    )]
+    pub inbound_message_path: Account<'info, InboundMessagePath>,
+
+    #[account(
+        mut,
+        constraint = outbound_message_path.destination_chain_id == inbound_message_path.source_chain_id @ ReceiverError::InvalidEchoPath,
+    )]
+    pub outbound_message_path: Account<'info, OutboundMessagePath>,
+
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub outbound_message: UncheckedAccount<'info>,
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub outbound_message_record: UncheckedAccount<'info>,
+
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub treasury: Option<UncheckedAccount<'info>>,
+
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub mailbox_sender_config: UncheckedAccount<'info>,
+
+    /// CHECK: The event authority of the mailbox program, which emits its events through a self-CPI
+    #[account(seeds = [b"__event_authority"], bump, seeds::program = mailbox.key())]
+    pub mailbox_event_authority: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+/// Sends the body of a received message back to its sender, once.
+pub fn echo(ctx: Context<Echo>, _payload_hash: [u8; 32]) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[CONFIG_SEED, &[ctx.bumps.config]]];
+    mailbox::cpi::send_message(
+        CpiContext::new_with_signer(
+            ctx.accounts.mailbox.to_account_info(),
+            SendMessage {
+                fee_payer: ctx.accounts.payer.to_account_info(),
+                sender_authority: ctx.accounts.config.to_account_info(),
+                config: ctx.accounts.mailbox_config.to_account_info(),
+                outbound_message_path: ctx.accounts.outbound_message_path.to_account_info(),
+                message_buffer: None,
+                outbound_message: ctx.accounts.outbound_message.to_account_info(),
+                outbound_message_record: ctx.accounts.outbound_message_record.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+                treasury: ctx.accounts.treasury.as_ref().map(|t| t.to_account_info()),
+                sender_config: ctx.accounts.mailbox_sender_config.to_account_info(),
+                sender_fee_schedule: None,
+                fee_token: None,
+                fee_mint: None,
+                fee_payer_token_account: None,
+                treasury_token_account: None,
+                fee_token_program: None,
+                event_authority: ctx.accounts.mailbox_event_authority.to_account_info(),
+                program: ctx.accounts.mailbox.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        ctx.accounts.message_handled.body.clone(),
+        ctx.accounts.message_handled.sender,
+        None,
+        0,
+        None,
+        false,
+    )?;
+
+    ctx.accounts.message_handled.echoed = true;
+    Ok(())
+}
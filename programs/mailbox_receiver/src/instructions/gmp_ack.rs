@@ -0,0 +1,48 @@
+use anchor_lang::prelude::*;
+use mailbox::{constants::MESSAGE_SEED, state::MessageV1Info};
+
+use crate::{constants::{ACK_RECEIVED_SEED, CONFIG_SEED}, state::{AckReceived, Config}};
+
+#[derive(Accounts)]
+#[instruction(payload_hash: [u8; 32], acked_payload_hash: [u8; 32], result: Vec<u8>)]
+pub struct GMPAck<'info> {
+    // The PDA from the mailbox program that contains the acknowledgement message.
+    // Checking this account is signer ensures the acknowledgement legitimately comes from the mailbox program.
+    #[account(
+        signer,
+        owner = config.mailbox_address,
+        seeds = [MESSAGE_SEED, &payload_hash],
+        seeds::program = config.mailbox_address,
+        bump,
+    )]
+    pub message_info: Account<'info, MessageV1Info>,
+
+    // any other account needed to handle the acknowledgement
+
+    #[account(mut)]
+    pub handler: Signer<'info>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    #[account(
+        init,
+        payer = handler,
+        space = 8 + 4 + result.len(),
+        seeds = [ACK_RECEIVED_SEED, &acked_payload_hash],
+        bump,
+    )]
+    pub ack_received: Account<'info, AckReceived>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn gmp_ack(
+    ctx: Context<GMPAck>,
+    _payload_hash: [u8; 32],
+    _acked_payload_hash: [u8; 32],
+    result: Vec<u8>,
+) -> Result<()> {
+    ctx.accounts.ack_received.result = result;
+    Ok(())
+}
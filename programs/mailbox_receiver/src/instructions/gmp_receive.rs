@@ -1,4 +1,5 @@
 use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash as sha256;
 use mailbox::{constants::MESSAGE_SEED, state::MessageV1Info};
 
 use crate::{constants::{CONFIG_SEED, MESSAGE_HANDLED_SEED}, state::{Config, MessageHandled}};
@@ -28,7 +29,7 @@ pub struct GMPReceive<'info> {
     #[account(
         init,
         payer = handler,
-        space = 8 + MessageHandled::INIT_SPACE,
+        space = 8 + MessageHandled::size(message_info.message.body.len()),
         seeds = [MESSAGE_HANDLED_SEED, &payload_hash],
         bump,
     )]
@@ -37,6 +38,14 @@ pub struct GMPReceive<'info> {
     pub system_program: Program<'info, System>,
 }
 
-pub fn gmp_receive(_ctx: Context<GMPReceive>, _payload_hash: [u8; 32]) -> Result<()> {
-    Ok(())
+/// Stores the received message, which can then be echoed back with `echo`, and returns the
+/// hash of its body to the mailbox, which relays it in the acknowledgement if one was requested.
+pub fn gmp_receive(ctx: Context<GMPReceive>, _payload_hash: [u8; 32]) -> Result<[u8; 32]> {
+    let message = &ctx.accounts.message_info.message;
+    let message_handled = &mut ctx.accounts.message_handled;
+    message_handled.sender = message.sender;
+    message_handled.message_path_identifier = message.message_path_identifier;
+    message_handled.nonce = message.nonce;
+    message_handled.body = message.body.clone();
+    Ok(sha256(&message.body).to_bytes())
 }
\ No newline at end of file
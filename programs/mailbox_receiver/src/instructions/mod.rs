@@ -1,4 +1,10 @@
+pub mod echo;
+pub use echo::*;
+pub mod gmp_ack;
+pub use gmp_ack::*;
 pub mod gmp_receive;
 pub use gmp_receive::*;
 pub mod initialize;
-pub use initialize::*;
\ No newline at end of file
+pub use initialize::*;
+pub mod set_executor_allowlist;
+pub use set_executor_allowlist::*;
\ No newline at end of file
@@ -0,0 +1,61 @@
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::bpf_loader_upgradeable;
+use mailbox::cpi::accounts::SetExecutorAllowlist as MailboxSetExecutorAllowlist;
+
+use crate::constants::{CONFIG_SEED, MAILBOX_AUTHORITY_SEED};
+use crate::errors::ReceiverError;
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct SetExecutorAllowlist<'info> {
+    #[account(mut,
+        constraint = deployer.key() == program_data.upgrade_authority_address.unwrap_or_default() @ ReceiverError::Unauthorized)
+    ]
+    pub deployer: Signer<'info>,
+    #[account(
+        seeds = [crate::ID.as_ref()],
+        bump,
+        seeds::program = bpf_loader_upgradeable::id(),
+    )]
+    pub program_data: Account<'info, ProgramData>,
+
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+
+    /// CHECK: The PDA signing for the receiver program, so the allowlist is registered for it
+    #[account(seeds = [MAILBOX_AUTHORITY_SEED], bump)]
+    pub mailbox_authority: UncheckedAccount<'info>,
+
+    /// CHECK: The mailbox program the receiver is configured with
+    #[account(address = config.mailbox_address @ ReceiverError::InvalidMailboxAddress)]
+    pub mailbox: UncheckedAccount<'info>,
+
+    /// CHECK: Verified by the mailbox program
+    #[account(mut)]
+    pub executor_allowlist: UncheckedAccount<'info>,
+
+    pub system_program: Program<'info, System>,
+}
+
+pub fn set_executor_allowlist(
+    ctx: Context<SetExecutorAllowlist>,
+    executors: Vec<Pubkey>,
+    fallback_delay: i64,
+) -> Result<()> {
+    let signer_seeds: &[&[&[u8]]] = &[&[MAILBOX_AUTHORITY_SEED, &[ctx.bumps.mailbox_authority]]];
+    mailbox::cpi::set_executor_allowlist(
+        CpiContext::new_with_signer(
+            ctx.accounts.mailbox.to_account_info(),
+            MailboxSetExecutorAllowlist {
+                payer: ctx.accounts.deployer.to_account_info(),
+                recipient_authority: ctx.accounts.mailbox_authority.to_account_info(),
+                executor_allowlist: ctx.accounts.executor_allowlist.to_account_info(),
+                system_program: ctx.accounts.system_program.to_account_info(),
+            },
+            signer_seeds,
+        ),
+        crate::ID,
+        executors,
+        fallback_delay,
+    )
+}
@@ -18,8 +18,29 @@ pub mod mailbox_receiver {
         instructions::initialize(ctx, mailbox_address)
     }
 
-    pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<()> {
+    pub fn set_executor_allowlist(
+        ctx: Context<SetExecutorAllowlist>,
+        executors: Vec<Pubkey>,
+        fallback_delay: i64,
+    ) -> Result<()> {
+        instructions::set_executor_allowlist(ctx, executors, fallback_delay)
+    }
+
+    pub fn gmp_receive(ctx: Context<GMPReceive>, payload_hash: [u8; 32]) -> Result<[u8; 32]> {
         instructions::gmp_receive(ctx, payload_hash)
     }
 
+    pub fn echo(ctx: Context<Echo>, payload_hash: [u8; 32]) -> Result<()> {
+        instructions::echo(ctx, payload_hash)
+    }
+
+    pub fn gmp_ack(
+        ctx: Context<GMPAck>,
+        payload_hash: [u8; 32],
+        acked_payload_hash: [u8; 32],
+        result: Vec<u8>,
+    ) -> Result<()> {
+        instructions::gmp_ack(ctx, payload_hash, acked_payload_hash, result)
+    }
+
 }
\ No newline at end of file
@@ -6,6 +6,24 @@ pub struct Config {
     pub mailbox_address: Pubkey,
 }
 
+// Records a received message, so that it can be read back and echoed to its sender
 #[account]
-#[derive(InitSpace)]
-pub struct MessageHandled {}
\ No newline at end of file
+pub struct MessageHandled {
+    pub sender: [u8; 32],
+    pub message_path_identifier: [u8; 32],
+    pub nonce: u64,
+    pub echoed: bool,
+    pub body: Vec<u8>,
+}
+
+impl MessageHandled {
+    pub fn size(body_len: usize) -> usize {
+        32 + 32 + 8 + 1 + 4 + body_len
+    }
+}
+
+// Records the data returned by the recipient of an acknowledged message
+#[account]
+pub struct AckReceived {
+    pub result: Vec<u8>,
+}
\ No newline at end of file
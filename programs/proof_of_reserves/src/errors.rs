@@ -0,0 +1,39 @@
+use anchor_lang::prelude::*;
+
+#[error_code]
+pub enum ProofOfReservesError {
+    #[msg("Unauthorized function call")]
+    Unauthorized,
+    #[msg("Buffer IO error")]
+    BufferIOError,
+    #[msg("Invalid payload length")]
+    InvalidPayloadLength,
+    #[msg("Invalid payload selector")]
+    InvalidPayloadSelector,
+    #[msg("Payload hash does not match payload")]
+    PayloadHashMismatch,
+    #[msg("Payload amount does not fit in 64 bits")]
+    AmountOverflow,
+    #[msg("Invalid chain ID")]
+    InvalidChainID,
+    #[msg("Invalid token address")]
+    InvalidTokenAddress,
+    #[msg("Reserves attestation is older than the latest one")]
+    StaleAttestation,
+    #[msg("Reserves attestation timestamp is in the future")]
+    AttestationFromFuture,
+    #[msg("Reserves attestation has expired")]
+    AttestationExpired,
+    #[msg("Mint would exceed attested reserves")]
+    ReservesExceeded,
+    #[msg("Missing proof of reserves account")]
+    MissingProofOfReservesAccount,
+    #[msg("Invalid proof of reserves program")]
+    InvalidProofOfReservesProgram,
+}
+
+impl From<std::io::Error> for ProofOfReservesError {
+    fn from(_error: std::io::Error) -> Self {
+        ProofOfReservesError::BufferIOError
+    }
+}
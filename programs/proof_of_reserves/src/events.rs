@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[event]
+pub struct OwnershipTransferInitiated {
+    pub new_admin: Pubkey,
+}
+
+#[event]
+pub struct ConsortiumSet {
+    pub consortium: Pubkey,
+}
+
+#[event]
+pub struct MaxAttestationAgeSet {
+    pub max_attestation_age: u64,
+}
+
+#[event]
+pub struct ReservesAttested {
+    pub mint: Pubkey,
+    pub reserves: u64,
+    pub timestamp: u64,
+    pub payload_hash: [u8; 32],
+}
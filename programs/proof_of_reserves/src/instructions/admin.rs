@@ -0,0 +1,34 @@
+use anchor_lang::prelude::*;
+
+use crate::constants::CONFIG_SEED;
+use crate::errors::ProofOfReservesError;
+use crate::events::{ConsortiumSet, MaxAttestationAgeSet, OwnershipTransferInitiated};
+use crate::state::Config;
+
+#[derive(Accounts)]
+pub struct Admin<'info> {
+    #[account(address = config.admin @ ProofOfReservesError::Unauthorized)]
+    pub admin: Signer<'info>,
+    #[account(mut, seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+}
+
+pub fn transfer_ownership(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
+    ctx.accounts.config.pending_admin = new_admin;
+    emit!(OwnershipTransferInitiated { new_admin });
+    Ok(())
+}
+
+pub fn set_consortium(ctx: Context<Admin>, consortium: Pubkey) -> Result<()> {
+    ctx.accounts.config.consortium = consortium;
+    emit!(ConsortiumSet { consortium });
+    Ok(())
+}
+
+pub fn set_max_attestation_age(ctx: Context<Admin>, max_attestation_age: u64) -> Result<()> {
+    ctx.accounts.config.max_attestation_age = max_attestation_age;
+    emit!(MaxAttestationAgeSet {
+        max_attestation_age
+    });
+    Ok(())
+}
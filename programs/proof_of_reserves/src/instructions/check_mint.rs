@@ -0,0 +1,43 @@
+//! Check called by minting programs before minting, failing when the mint would push the
+//! supply above the attested reserves.
+use anchor_lang::prelude::*;
+use anchor_spl::token_interface::Mint;
+
+use crate::{
+    constants::{CONFIG_SEED, MINT_RESERVES_SEED},
+    errors::ProofOfReservesError,
+    state::{Config, MintReserves},
+};
+
+#[derive(Accounts)]
+pub struct CheckMint<'info> {
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    pub mint: InterfaceAccount<'info, Mint>,
+    #[account(seeds = [MINT_RESERVES_SEED, mint.key().as_ref()], bump)]
+    pub mint_reserves: Account<'info, MintReserves>,
+}
+
+pub fn check_mint(ctx: Context<CheckMint>, amount: u64) -> Result<()> {
+    let max_attestation_age = ctx.accounts.config.max_attestation_age;
+    let mint_reserves = &ctx.accounts.mint_reserves;
+    if max_attestation_age != 0 {
+        let now = Clock::get()?.unix_timestamp as u64;
+        require!(
+            now.saturating_sub(mint_reserves.timestamp) <= max_attestation_age,
+            ProofOfReservesError::AttestationExpired
+        );
+    }
+
+    let supply_after_mint = ctx
+        .accounts
+        .mint
+        .supply
+        .checked_add(amount)
+        .ok_or(ProofOfReservesError::ReservesExceeded)?;
+    require!(
+        supply_after_mint <= mint_reserves.reserves,
+        ProofOfReservesError::ReservesExceeded
+    );
+    Ok(())
+}
@@ -0,0 +1,10 @@
+pub mod accept_ownership;
+pub use accept_ownership::*;
+pub mod admin;
+pub use admin::*;
+pub mod check_mint;
+pub use check_mint::*;
+pub mod initialize;
+pub use initialize::*;
+pub mod submit_reserves;
+pub use submit_reserves::*;
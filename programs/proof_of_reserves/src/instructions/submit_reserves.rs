@@ -0,0 +1,85 @@
+//! Records a consortium-notarized reserves attestation.
+use anchor_lang::prelude::*;
+use anchor_lang::solana_program::hash::hash as sha256;
+use consortium::{constants::VALIDATED_PAYLOAD_SEED, state::ValidatedPayload};
+
+use crate::{
+    constants::{CHAIN_ID, CONFIG_SEED, MINT_RESERVES_SEED},
+    errors::ProofOfReservesError,
+    events::ReservesAttested,
+    state::{Config, MintReserves},
+    utils::reserves_payloads::{ReservesV1, RESERVES_V1_PAYLOAD_LEN},
+};
+
+#[derive(Accounts)]
+#[instruction(reserves_payload: [u8; RESERVES_V1_PAYLOAD_LEN], reserves_payload_hash: [u8; 32])]
+pub struct SubmitReserves<'info> {
+    #[account(mut)]
+    pub payer: Signer<'info>,
+    #[account(seeds = [CONFIG_SEED], bump)]
+    pub config: Account<'info, Config>,
+    /// check that the consortium program has validated the payload
+    #[account(
+        owner = config.consortium,
+        seeds = [VALIDATED_PAYLOAD_SEED, &reserves_payload_hash[..]],
+        seeds::program = config.consortium,
+        bump
+    )]
+    pub consortium_validated_payload: Account<'info, ValidatedPayload>,
+    /// CHECK: The attested token, only its address is used to derive the reserves account.
+    pub mint: UncheckedAccount<'info>,
+    #[account(
+        init_if_needed,
+        payer = payer,
+        space = 8 + MintReserves::INIT_SPACE,
+        seeds = [MINT_RESERVES_SEED, mint.key().as_ref()],
+        bump
+    )]
+    pub mint_reserves: Account<'info, MintReserves>,
+    pub system_program: Program<'info, System>,
+}
+
+pub fn submit_reserves(
+    ctx: Context<SubmitReserves>,
+    reserves_payload: [u8; RESERVES_V1_PAYLOAD_LEN],
+    reserves_payload_hash: [u8; 32],
+) -> Result<()> {
+    require!(
+        reserves_payload_hash == sha256(&reserves_payload).to_bytes(),
+        ProofOfReservesError::PayloadHashMismatch
+    );
+
+    let attestation = ReservesV1::from_session_payload(&reserves_payload)?;
+    require!(
+        attestation.destination_chain_id == CHAIN_ID,
+        ProofOfReservesError::InvalidChainID
+    );
+    let mint = ctx.accounts.mint.key();
+    require!(
+        attestation.token_address == mint.to_bytes(),
+        ProofOfReservesError::InvalidTokenAddress
+    );
+
+    let mint_reserves = &mut ctx.accounts.mint_reserves;
+    // Attestations only move forward, so an older notarized payload can not be replayed.
+    require!(
+        attestation.timestamp > mint_reserves.timestamp,
+        ProofOfReservesError::StaleAttestation
+    );
+    require!(
+        attestation.timestamp <= Clock::get()?.unix_timestamp as u64,
+        ProofOfReservesError::AttestationFromFuture
+    );
+
+    mint_reserves.mint = mint;
+    mint_reserves.reserves = attestation.reserves;
+    mint_reserves.timestamp = attestation.timestamp;
+
+    emit!(ReservesAttested {
+        mint,
+        reserves: attestation.reserves,
+        timestamp: attestation.timestamp,
+        payload_hash: reserves_payload_hash,
+    });
+    Ok(())
+}
@@ -0,0 +1,66 @@
+use anchor_lang::prelude::*;
+
+pub mod constants;
+pub mod errors;
+pub mod events;
+pub mod instructions;
+pub mod security;
+pub mod state;
+pub mod utils;
+
+use instructions::*;
+use utils::reserves_payloads::RESERVES_V1_PAYLOAD_LEN;
+
+#[cfg(feature = "mainnet")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "gastald")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "staging")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(feature = "bft")]
+declare_id!("ToDo111111111111111111111111111111111111111");
+#[cfg(not(any(feature = "mainnet", feature = "gastald", feature = "staging", feature = "bft")))]
+declare_id!("GJc1bKbLK3R7Xs3ZDQqYYegdeNgcLTn6jFnWDaBdpbg3");
+
+#[program]
+pub mod proof_of_reserves {
+
+    use super::*;
+
+    pub fn initialize(
+        ctx: Context<Initialize>,
+        admin: Pubkey,
+        consortium: Pubkey,
+        max_attestation_age: u64,
+    ) -> Result<()> {
+        instructions::initialize(ctx, admin, consortium, max_attestation_age)
+    }
+
+    pub fn transfer_ownership(ctx: Context<Admin>, new_admin: Pubkey) -> Result<()> {
+        instructions::transfer_ownership(ctx, new_admin)
+    }
+
+    pub fn accept_ownership(ctx: Context<AcceptOwnership>) -> Result<()> {
+        instructions::accept_ownership(ctx)
+    }
+
+    pub fn set_consortium(ctx: Context<Admin>, consortium: Pubkey) -> Result<()> {
+        instructions::set_consortium(ctx, consortium)
+    }
+
+    pub fn set_max_attestation_age(ctx: Context<Admin>, max_attestation_age: u64) -> Result<()> {
+        instructions::set_max_attestation_age(ctx, max_attestation_age)
+    }
+
+    pub fn submit_reserves(
+        ctx: Context<SubmitReserves>,
+        reserves_payload: [u8; RESERVES_V1_PAYLOAD_LEN],
+        reserves_payload_hash: [u8; 32],
+    ) -> Result<()> {
+        instructions::submit_reserves(ctx, reserves_payload, reserves_payload_hash)
+    }
+
+    pub fn check_mint(ctx: Context<CheckMint>, amount: u64) -> Result<()> {
+        instructions::check_mint(ctx, amount)
+    }
+}
@@ -0,0 +1,24 @@
+use anchor_lang::prelude::*;
+
+#[account]
+#[derive(InitSpace)]
+pub struct Config {
+    pub admin: Pubkey,
+    pub pending_admin: Pubkey,
+    /// The Lombard security consortium program notarizing reserve attestations.
+    pub consortium: Pubkey,
+    /// Maximum age in seconds of an attestation for mints to be checked against it, 0 disables
+    /// the expiry.
+    pub max_attestation_age: u64,
+}
+
+/// Latest attested reserves backing a mint on this chain.
+#[account]
+#[derive(InitSpace)]
+pub struct MintReserves {
+    pub mint: Pubkey,
+    /// Maximum total supply of the mint allowed by the attested reserves.
+    pub reserves: u64,
+    /// Unix timestamp at which the reserves were attested.
+    pub timestamp: u64,
+}
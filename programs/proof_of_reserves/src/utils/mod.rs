@@ -0,0 +1,4 @@
+// only built for the minting programs, which depend on this crate with the `cpi` feature
+#[cfg(feature = "cpi")]
+pub mod reserves;
+pub(crate) mod reserves_payloads;
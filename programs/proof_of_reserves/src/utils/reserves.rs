@@ -0,0 +1,43 @@
+//! Proof-of-reserves check performed by the minting programs before every mint.
+use anchor_lang::prelude::*;
+
+use crate::{
+    cpi::{accounts::CheckMint, check_mint},
+    errors::ProofOfReservesError,
+};
+
+/// When a proof-of-reserves program is configured, makes a CPI to its check to ensure minting
+/// `amount` keeps the supply of `mint` within the attested reserves.
+pub fn check_reserves<'info>(
+    proof_of_reserves: Option<Pubkey>,
+    reserves_program: Option<&UncheckedAccount<'info>>,
+    reserves_config: Option<&UncheckedAccount<'info>>,
+    mint_reserves: Option<&UncheckedAccount<'info>>,
+    mint: AccountInfo<'info>,
+    amount: u64,
+) -> Result<()> {
+    let Some(proof_of_reserves) = proof_of_reserves else {
+        return Ok(());
+    };
+    let reserves_program =
+        reserves_program.ok_or(ProofOfReservesError::MissingProofOfReservesAccount)?;
+    require_keys_eq!(
+        reserves_program.key(),
+        proof_of_reserves,
+        ProofOfReservesError::InvalidProofOfReservesProgram
+    );
+    let reserves_config =
+        reserves_config.ok_or(ProofOfReservesError::MissingProofOfReservesAccount)?;
+    let mint_reserves = mint_reserves.ok_or(ProofOfReservesError::MissingProofOfReservesAccount)?;
+    check_mint(
+        CpiContext::new(
+            reserves_program.to_account_info(),
+            CheckMint {
+                config: reserves_config.to_account_info(),
+                mint,
+                mint_reserves: mint_reserves.to_account_info(),
+            },
+        ),
+        amount,
+    )
+}
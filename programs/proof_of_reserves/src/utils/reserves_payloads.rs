@@ -0,0 +1,101 @@
+use std::io::{prelude::*, BufReader};
+
+use crate::errors::ProofOfReservesError;
+
+/// First 4 bytes of keccak256("ReservesV1(bytes32,bytes32,uint256,uint256)").
+pub const RESERVES_V1_SELECTOR: [u8; 4] = [0xe3, 0xad, 0x26, 0x5a];
+pub const RESERVES_V1_PAYLOAD_LEN: usize = 4 + 32 * 4;
+
+pub struct ReservesV1 {
+    pub destination_chain_id: [u8; 32],
+    pub token_address: [u8; 32],
+    pub reserves: u64,
+    pub timestamp: u64,
+}
+
+impl ReservesV1 {
+    /// creates a ReservesV1 struct from an ethereum ABI-encoded ReservesV1 payload
+    pub fn from_session_payload(payload: &[u8]) -> Result<Self, ProofOfReservesError> {
+        let mut reader = BufReader::new(payload);
+        if payload.len() != RESERVES_V1_PAYLOAD_LEN {
+            return Err(ProofOfReservesError::InvalidPayloadLength);
+        }
+
+        let mut selector = [0u8; 4];
+        reader.read_exact(&mut selector)?;
+        if selector != RESERVES_V1_SELECTOR {
+            return Err(ProofOfReservesError::InvalidPayloadSelector);
+        }
+
+        let mut reserves_v1 = Self {
+            destination_chain_id: [0u8; 32],
+            token_address: [0u8; 32],
+            reserves: 0,
+            timestamp: 0,
+        };
+        reader.read_exact(&mut reserves_v1.destination_chain_id)?;
+        reader.read_exact(&mut reserves_v1.token_address)?;
+        reserves_v1.reserves = read_u64(&mut reader)?;
+        reserves_v1.timestamp = read_u64(&mut reader)?;
+
+        Ok(reserves_v1)
+    }
+}
+
+/// Reads an ABI-encoded uint256, rejecting values that do not fit in a u64.
+fn read_u64(reader: &mut impl Read) -> Result<u64, ProofOfReservesError> {
+    let mut bytes = [0u8; 32];
+    reader.read_exact(&mut bytes)?;
+    if bytes[..24].iter().any(|b| *b != 0) {
+        return Err(ProofOfReservesError::AmountOverflow);
+    }
+    Ok(u64::from_be_bytes(bytes[24..32].try_into().unwrap()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use anchor_lang::solana_program::keccak::hash as keccak256;
+
+    fn payload(reserves: [u8; 32], timestamp: u64) -> Vec<u8> {
+        let mut payload = RESERVES_V1_SELECTOR.to_vec();
+        payload.extend_from_slice(&[1u8; 32]);
+        payload.extend_from_slice(&[2u8; 32]);
+        payload.extend_from_slice(&reserves);
+        let mut timestamp_bytes = [0u8; 32];
+        timestamp_bytes[24..].copy_from_slice(&timestamp.to_be_bytes());
+        payload.extend_from_slice(&timestamp_bytes);
+        payload
+    }
+
+    #[test]
+    fn test_selector() {
+        let hash = keccak256(b"ReservesV1(bytes32,bytes32,uint256,uint256)").to_bytes();
+        assert_eq!(RESERVES_V1_SELECTOR, hash[..4]);
+    }
+
+    #[test]
+    fn test_decode_reserves_payload() {
+        let mut reserves = [0u8; 32];
+        reserves[24..].copy_from_slice(&21_000_000u64.to_be_bytes());
+        let decoded = ReservesV1::from_session_payload(&payload(reserves, 1_700_000_000)).unwrap();
+        assert_eq!(decoded.destination_chain_id, [1u8; 32]);
+        assert_eq!(decoded.token_address, [2u8; 32]);
+        assert_eq!(decoded.reserves, 21_000_000);
+        assert_eq!(decoded.timestamp, 1_700_000_000);
+    }
+
+    #[test]
+    fn test_decode_rejects_oversized_amount() {
+        let mut reserves = [0u8; 32];
+        reserves[0] = 1;
+        assert!(ReservesV1::from_session_payload(&payload(reserves, 0)).is_err());
+    }
+
+    #[test]
+    fn test_decode_rejects_wrong_selector() {
+        let mut payload = payload([0u8; 32], 0);
+        payload[0] ^= 0xff;
+        assert!(ReservesV1::from_session_payload(&payload).is_err());
+    }
+}
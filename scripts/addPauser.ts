@@ -7,7 +7,7 @@ import { getBase58EncodedTxBytes, getConfigPDA } from "./utils";
 if (process.argv.indexOf("--help") > -1) {
   console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn addPauser <pauser>
 
-    Adds <pauser> to the pauser list.`);
+    Grants the pauser role to <pauser>.`);
   process.exit(0);
 }
 
@@ -40,9 +40,8 @@ const pauser = new PublicKey(process.argv[2]);
     const configPDA = getConfigPDA(programId);
     console.log("Using config PDA:", configPDA.toBase58());
 
-    const tx = await program.methods.addPauser(pauser).accounts({
-      payer,
-      config: configPDA
+    const tx = await program.methods.grantAccountRole(pauser, { pauser: {} }).accounts({
+      payer
     });
 
     if (populate) {
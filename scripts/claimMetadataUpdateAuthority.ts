@@ -0,0 +1,56 @@
+import * as anchor from "@coral-xyz/anchor";
+import { PublicKey } from "@solana/web3.js";
+import { Lbtc } from "../target/types/lbtc";
+import { getBase58EncodedTxBytes, getConfigPDA } from "./utils";
+
+// Provide instructions.
+if (process.argv.indexOf("--help") > -1) {
+  console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn claimMetadataUpdateAuthority
+
+    Moves the update authority of the LBTC Metaplex metadata from the admin wallet to the token authority PDA, so
+    the metadata can be changed with updateTokenMetadata afterwards.`);
+  process.exit(0);
+}
+
+const provider = anchor.AnchorProvider.env();
+anchor.setProvider(provider);
+
+// Check for program ID match.
+if (!process.env.PROGRAM_ID) {
+  console.error("no program Id set");
+  process.exit(1);
+}
+const programId = new PublicKey(process.env.PROGRAM_ID);
+const program = new anchor.Program(require("../target/idl/lbtc.json"), provider) as anchor.Program<Lbtc>;
+
+if (!program.programId.equals(programId)) {
+  console.error("the program id in the idl does not match the program id passed as env variable");
+  process.exit(1);
+}
+
+// If we have a populate flag at the end of the call, we return the bytes.
+let populate = process.argv.at(-1) === "--populate";
+
+(async () => {
+  try {
+    const payer = provider.wallet.publicKey; // Get wallet address
+
+    // Derive PDA for config
+    const configPDA = getConfigPDA(programId);
+    console.log("Using config PDA:", configPDA.toBase58());
+    const cfg = await program.account.config.fetch(configPDA);
+
+    const tx = await program.methods.claimMetadataUpdateAuthority().accounts({
+      payer,
+      mint: cfg.mint
+    });
+
+    if (populate) {
+      console.log("Transaction bytes:", await getBase58EncodedTxBytes(await tx.instruction(), provider.connection));
+    } else {
+      console.log("Transaction Signature:", await tx.rpc());
+    }
+  } catch (err) {
+    console.error("Error claiming metadata update authority:", err);
+  }
+})();
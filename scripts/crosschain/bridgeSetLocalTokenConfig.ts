@@ -5,9 +5,9 @@ import { Bridge } from "../../target/types/bridge";
 
 // Provide instructions.
 if (process.argv.indexOf("--help") > -1) {
-  console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn crosschain_bridgeSetLocalTokenConfig <mint address> [--populate]
+  console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn crosschain_bridgeSetLocalTokenConfig <mint address> [--lock-release] [--populate]
 
-    Sets local token config on the bridge. `);
+    Sets local token config on the bridge, burning and minting the token unless --lock-release is passed. `);
   process.exit(0);
 }
 
@@ -31,13 +31,14 @@ if (!program.programId.equals(programId)) {
 let populate = process.argv.at(-1) === "--populate";
 
 const mint = new PublicKey(process.argv[2]);
+const mode = process.argv.includes("--lock-release") ? { lockRelease: {} } : { burnMint: {} };
 
 (async () => {
   try {
     const deployer = provider.wallet.publicKey; // Get wallet address
 
     const tx = await program.methods
-			.setLocalTokenConfig(mint)
+			.setLocalTokenConfig(mint, mode)
 			.accounts({
         admin: deployer,
 			});
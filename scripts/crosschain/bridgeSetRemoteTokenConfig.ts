@@ -5,7 +5,7 @@ import { Bridge } from "../../target/types/bridge";
 
 // Provide instructions.
 if (process.argv.indexOf("--help") > -1) {
-  console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn crosschain_bridgeSetRemoteTokenConfig <mint address> <remote chain id> <remote token address> <direction bitmask: 1|2|3> [--populate]
+  console.log(`Usage: PROGRAM_ID=<program_id> ANCHOR_PROVIDER_URL=<rpc_url> ANCHOR_WALLET=<wallet_path> yarn crosschain_bridgeSetRemoteTokenConfig <mint address> <remote chain id> <remote token address> <direction bitmask: 1|2|3> <remote token decimals> [--populate]
 
     Sets remote token config on the bridge. `);
   process.exit(0);
@@ -34,13 +34,14 @@ const mint = new PublicKey(process.argv[2]);
 const remoteChainId = Array.from(Uint8Array.from(Buffer.from(process.argv[3], "hex")));
 const remoteBridge = Array.from(Uint8Array.from(Buffer.from(process.argv[4], "hex")));
 const direction = Number(process.argv[5]);
+const decimals = Number(process.argv[6]);
 
 (async () => {
   try {
     const deployer = provider.wallet.publicKey; // Get wallet address
 
     const tx = await program.methods
-			.setRemoteTokenConfig(mint, remoteChainId, remoteBridge, direction)
+			.setRemoteTokenConfig(mint, remoteChainId, remoteBridge, direction, decimals)
 			.accounts({
         admin: deployer,
 			});
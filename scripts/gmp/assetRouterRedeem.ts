@@ -3,7 +3,7 @@ import * as spl from "@solana/spl-token";
 import { PublicKey } from "@solana/web3.js";
 import { AssetRouter } from "../../target/types/asset_router";
 import { getBase58EncodedTxBytes, getConfigPDA } from "../utils";
-import { getAssetRouterConfigPDA, getAssetRouterTokenBtcRoutePDA, getAssetRouterTokenLocalRoutePDA, getMailboxConfigPDA, getMailboxOutboundMessagePDA, getMailboxSenderConfigPDA, getOutboundMessagePathPDA } from "./utils";
+import { getAssetRouterConfigPDA, getAssetRouterTokenBtcRoutePDA, getAssetRouterTokenLocalRoutePDA, getMailboxConfigPDA, getMailboxOutboundMessagePDA, getMailboxOutboundMessageRecordPDA, getMailboxSenderConfigPDA, getOutboundMessagePathPDA } from "./utils";
 import { Mailbox } from "../../target/types/mailbox";
 
 // Provide instructions.
@@ -71,6 +71,7 @@ const ledgerChainID = Buffer.from(process.argv[6], "hex");
     const senderConfigPDA = getMailboxSenderConfigPDA(mailboxProgramId, programId);
     const outboundMessagePDA = getMailboxOutboundMessagePDA(mailboxProgramId, mailboxConfig.globalNonce);
     console.log("Outbound message PDA:", outboundMessagePDA.toBase58());
+    const outboundMessageRecordPDA = getMailboxOutboundMessageRecordPDA(mailboxProgramId, mailboxConfig.globalNonce);
 
     const srcMintAccountInfo = await provider.connection.getAccountInfo(sourceMint);
     if (!srcMintAccountInfo) {
@@ -102,6 +103,7 @@ const ledgerChainID = Buffer.from(process.argv[6], "hex");
       mailboxConfig: mailboxConfigPDA,
       outboundMessagePath: outboundMessagePathPDA,
       outboundMessage: outboundMessagePDA,
+      outboundMessageRecord: outboundMessageRecordPDA,
       senderConfig: senderConfigPDA,
       treasury: assetRouterConfig.treasury
 
@@ -11,6 +11,7 @@ export const MAILBOX_INBOUND_MESSGE_PATH_SEED = Buffer.from("inbound_message_pat
 export const MAILBOX_OUTBOUND_MESSGE_PATH_SEED = Buffer.from("outbound_message_path");
 export const MAILBOX_MESSAGE_HANDLED_SEED = Buffer.from("message_handled");
 export const MAILBOX_OUTBOUND_MESSAGE_SEED = Buffer.from("outbound_message");
+export const MAILBOX_OUTBOUND_MESSAGE_RECORD_SEED = Buffer.from("outbound_message_record");
 export const ORACLE_SEED = Buffer.from("oracle");
 export const BASCULE_GMP_CONFIG_SEED = Buffer.from("bascule_gmp_config");
 export const RATIO_ORACLE_CONFIG_SEED = Buffer.from("ratio_oracle_config");
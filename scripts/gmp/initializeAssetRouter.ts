@@ -58,6 +58,8 @@ const programData = PublicKey.findProgramAddressSync(
       consortium: consortium,
       bascule: null,
       basculeGmp: null,
+      complianceHook: null,
+      proofOfReserves: null,
       ledgerLchainId: Array.from(Uint8Array.from(lendgerChainId)),
       bitcoinLchainId: Array.from(Uint8Array.from(bitcoinChainId))
     };
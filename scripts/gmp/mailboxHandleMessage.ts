@@ -74,8 +74,15 @@ const payloadHash = Array.from(Uint8Array.from(payloadHashBuf));
     console.log("token authority:", tokenAuthority.toBase58());
     console.log("Mint:", mint.toBase58());
 
+    const messageInfoPDA = PublicKey.findProgramAddressSync(
+      [Buffer.from("message"), payloadHashBuf],
+      program.programId
+    )[0];
+    const messageInfo = await program.account.messageV1Info.fetch(messageInfoPDA);
+
     const tx = await program.methods.handleMessage(payloadHash).accounts({
       handler: payer,
+      deliverer: messageInfo.deliverer,
       recipientProgram: msgRecipient,
     })
     .remainingAccounts([
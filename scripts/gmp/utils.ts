@@ -2,7 +2,7 @@ import { Connection, Transaction, PublicKey, TransactionInstruction } from "@sol
 import * as anchor from "@coral-xyz/anchor";
 import { AnchorProvider, Program } from "@coral-xyz/anchor";
 import bs58 from "bs58";
-import { ASSET_ROUTER_CONFIG_SEED, ASSET_ROUTER_TOKEN_CONFIG_SEED, ASSET_ROUTER_TOKEN_ROUTE_SEED, CONSORTIUM_CONFIG_SEED, CONSORTIUM_SESSION_PAYLOAD_SEED, CONSORTIUM_SESSION_SEED, CONSORTIUM_VALIDATED_PAYLOAD_SEED, MAILBOX_CONFIG_SEED, MAILBOX_INBOUND_MESSGE_PATH_SEED, MAILBOX_MESSAGE_HANDLED_SEED, MAILBOX_OUTBOUND_MESSAGE_SEED, MAILBOX_OUTBOUND_MESSGE_PATH_SEED, MAILBOX_SENDER_CONFIG_SEED, ORACLE_SEED } from "./constants";
+import { ASSET_ROUTER_CONFIG_SEED, ASSET_ROUTER_TOKEN_CONFIG_SEED, ASSET_ROUTER_TOKEN_ROUTE_SEED, CONSORTIUM_CONFIG_SEED, CONSORTIUM_SESSION_PAYLOAD_SEED, CONSORTIUM_SESSION_SEED, CONSORTIUM_VALIDATED_PAYLOAD_SEED, MAILBOX_CONFIG_SEED, MAILBOX_INBOUND_MESSGE_PATH_SEED, MAILBOX_MESSAGE_HANDLED_SEED, MAILBOX_OUTBOUND_MESSAGE_RECORD_SEED, MAILBOX_OUTBOUND_MESSAGE_SEED, MAILBOX_OUTBOUND_MESSGE_PATH_SEED, MAILBOX_SENDER_CONFIG_SEED, ORACLE_SEED } from "./constants";
 import { sha256 } from "js-sha256";
 
 const BITCOIN_ADDRESS = Buffer.from("0000000000000000000000000000000000000000000000000000000000000001", "hex"); 
@@ -55,6 +55,10 @@ export function getMailboxOutboundMessagePDA(program: PublicKey, nonce: anchor.B
   return PublicKey.findProgramAddressSync([MAILBOX_OUTBOUND_MESSAGE_SEED, nonce.toArrayLike(Buffer, "be", 8)], program)[0];
 }
 
+export function getMailboxOutboundMessageRecordPDA(program: PublicKey, nonce: anchor.BN) {
+  return PublicKey.findProgramAddressSync([MAILBOX_OUTBOUND_MESSAGE_RECORD_SEED, nonce.toArrayLike(Buffer, "be", 8)], program)[0];
+}
+
 export function getInboundMessagePathPDA(program: PublicKey, fromChainId: Buffer<ArrayBuffer>) {
   return PublicKey.findProgramAddressSync([MAILBOX_INBOUND_MESSGE_PATH_SEED, fromChainId], program)[0];
 }
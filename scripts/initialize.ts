@@ -35,9 +35,9 @@ let populate = process.argv.at(-1) === "--populate";
 const admin = new PublicKey(process.argv[2]);
 const mint = new PublicKey(process.argv[3]);
 const treasuryHolder = new PublicKey(process.argv[4]);
-const burnCommission = new anchor.BN(process.argv[5]);
+const burnCommission = { flat: { amount: new anchor.BN(process.argv[5]) } };
 const dustFeeRate = new anchor.BN(process.argv[6]);
-const mintFee = new anchor.BN(process.argv[7]);
+const mintFee = { flat: { amount: new anchor.BN(process.argv[7]) } };
 
 (async () => {
   try {
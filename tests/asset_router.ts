@@ -1,7 +1,15 @@
 import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { BN, BorshCoder, EventManager, Program } from "@coral-xyz/anchor";
-import { Ed25519Program, Keypair, PublicKey, SystemProgram, TransactionInstruction } from "@solana/web3.js";
+import {
+  Ed25519Program,
+  Keypair,
+  PublicKey,
+  sendAndConfirmTransaction,
+  SystemProgram,
+  Transaction,
+  TransactionInstruction
+} from "@solana/web3.js";
 import * as spl from "@solana/spl-token";
 import { AssetRouter } from "../target/types/asset_router";
 import { sha256 } from "js-sha256";
@@ -243,6 +251,8 @@ describe("Asset Router", () => {
             mailbox: mailboxAddress,
             bascule: bascule,
             basculeGmp: basculeGmp,
+            complianceHook: null,
+            proofOfReserves: null,
             ledgerLchainId: LEDGER_LCHAIN_ID_BZ,
             bitcoinLchainId: BITCOIN_LCHAIN_ID_BZ
           })
@@ -268,6 +278,8 @@ describe("Asset Router", () => {
           mailbox: mailboxAddress,
           bascule: bascule,
           basculeGmp: basculeGmp,
+          complianceHook: null,
+          proofOfReserves: null,
           ledgerLchainId: LEDGER_LCHAIN_ID_BZ,
           bitcoinLchainId: BITCOIN_LCHAIN_ID_BZ
         })
@@ -290,6 +302,18 @@ describe("Asset Router", () => {
       expect(cfg.bitcoinLchainId).to.be.deep.eq(BITCOIN_LCHAIN_ID_BZ);
     });
 
+    it("migrateConfig: rejects when the config is already migrated", async () => {
+      await expect(
+          withBlockhashRetry(async () =>
+            program.methods
+          .migrateConfig()
+          .accounts({ payer: provider.wallet.publicKey })
+          .signers([Keypair.fromSecretKey(provider.wallet.payer.secretKey)])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("ConfigAlreadyMigrated");
+    });
+
     /*    it("changeMintAuth: successful when called by admin", async () => {
           await withBlockhashRetry(async () =>
             program.methods
@@ -1076,7 +1100,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: mailboxUtilities.getSenderConfigPDA(staker1.publicKey),
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: null
           })
           .signers([staker1])
@@ -1104,7 +1129,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: mailboxUtilities.getSenderConfigPDA(staker1.publicKey),
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: null
           })
           .signers([staker1])
@@ -1131,7 +1157,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: mailboxUtilities.getSenderConfigPDA(staker1.publicKey),
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: null
           })
           .signers([staker1])
@@ -1919,6 +1946,7 @@ describe("Asset Router", () => {
         .handleMessage(gmpMessageHashBz)
         .accounts({
           handler: payer.publicKey,
+          deliverer: payer.publicKey,
           recipientProgram: program.programId
         })
         .remainingAccounts([
@@ -2032,6 +2060,7 @@ describe("Asset Router", () => {
         .handleMessage(gmpMessageHashBz)
         .accounts({
           handler: payer.publicKey,
+          deliverer: payer.publicKey,
           recipientProgram: program.programId
         })
         .remainingAccounts([
@@ -2160,6 +2189,7 @@ describe("Asset Router", () => {
             .handleMessage(gmpMessageHashBz)
             .accounts({
               handler: payer.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: program.programId
             })
             .remainingAccounts([
@@ -2256,6 +2286,7 @@ describe("Asset Router", () => {
           .handleMessage(gmpMessageHashBz)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: program.programId
           })
           .remainingAccounts([
@@ -2373,6 +2404,7 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
             // senderConfig: null,
             // treasury: treasury.publicKey
             senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
@@ -2439,7 +2471,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2466,7 +2499,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2494,7 +2528,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2521,7 +2556,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2548,7 +2584,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2575,7 +2612,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2605,7 +2643,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2635,7 +2674,8 @@ describe("Asset Router", () => {
             mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
             outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
             outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-            senderConfig: null,
+            outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+            senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
             treasury: treasury.publicKey
           })
           .signers([staker1])
@@ -2677,6 +2717,7 @@ describe("Asset Router", () => {
           mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
           outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
           outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
+          outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
           senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
           treasury: null
         })
@@ -2776,7 +2817,8 @@ describe("Asset Router", () => {
               mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
               outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
               outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-              senderConfig: mailboxUtilities.getSenderConfigPDA(staker1.publicKey),
+              outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+              senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
               treasury: null
             })
             .signers([staker1])
@@ -2822,6 +2864,7 @@ describe("Asset Router", () => {
           mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
           outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
           outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
+          outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
           senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
           treasury: null
         })
@@ -2918,7 +2961,8 @@ describe("Asset Router", () => {
               mailboxConfig: MailboxUtilities.getMailboxConfigPDA(),
               outboundMessagePath: mailboxUtilities.getOutboundMessagePathPDA(LEDGER_LCHAIN_ID),
               outboundMessage: await MailboxUtilities.getCurrentOutboundMessagePDA(),
-              senderConfig: mailboxUtilities.getSenderConfigPDA(staker1.publicKey),
+              outboundMessageRecord: await MailboxUtilities.getCurrentOutboundMessageRecordPDA(),
+              senderConfig: mailboxUtilities.getSenderConfigPDA(program.programId),
               treasury: null
             })
             .signers([staker1])
@@ -2929,5 +2973,153 @@ describe("Asset Router", () => {
     });
   });
 
+  describe("Token metadata", function () {
+    const metadataMint = Keypair.generate();
+    const newUpdateAuthority = Keypair.generate();
+
+    before(async function () {
+      const mintLen = spl.getMintLen([spl.ExtensionType.MetadataPointer]);
+      const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+      const tx = new Transaction().add(
+        SystemProgram.createAccount({
+          fromPubkey: admin.publicKey,
+          newAccountPubkey: metadataMint.publicKey,
+          space: mintLen,
+          lamports,
+          programId: spl.TOKEN_2022_PROGRAM_ID
+        }),
+        spl.createInitializeMetadataPointerInstruction(
+          metadataMint.publicKey,
+          tokenAuth,
+          metadataMint.publicKey,
+          spl.TOKEN_2022_PROGRAM_ID
+        ),
+        spl.createInitializeMintInstruction(metadataMint.publicKey, 8, tokenAuth, null, spl.TOKEN_2022_PROGRAM_ID)
+      );
+      await sendAndConfirmTransaction(provider.connection, tx, [admin, metadataMint], { commitment: "confirmed" });
+    });
+
+    it("initializeTokenMetadata rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .initializeTokenMetadata("Staked BTC", "SBTC", "https://example.com/sbtc.json")
+          .accounts({ payer: staker1.publicKey, mint: metadataMint.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("initializeTokenMetadata successful by admin", async () => {
+      await program.methods
+        .initializeTokenMetadata("Staked BTC", "SBTC", "https://example.com/sbtc.json")
+        .accounts({ payer: admin.publicKey, mint: metadataMint.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+      const metadata = await spl.getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata.name).to.be.eq("Staked BTC");
+      expect(metadata.symbol).to.be.eq("SBTC");
+      expect(metadata.uri).to.be.eq("https://example.com/sbtc.json");
+      expect(metadata.updateAuthority.toBase58()).to.be.eq(tokenAuth.toBase58());
+    });
+
+    it("updateTokenMetadataField rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .updateTokenMetadataField({ name: {} }, "Renamed BTC")
+          .accounts({ payer: staker1.publicKey, mint: metadataMint.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("updateTokenMetadataField updates a standard field", async () => {
+      await program.methods
+        .updateTokenMetadataField({ name: {} }, "Renamed BTC")
+        .accounts({ payer: admin.publicKey, mint: metadataMint.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+      const metadata = await spl.getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata.name).to.be.eq("Renamed BTC");
+      expect(metadata.symbol).to.be.eq("SBTC");
+    });
+
+    it("updateTokenMetadataField adds a custom field", async () => {
+      await program.methods
+        .updateTokenMetadataField({ key: { 0: "chain" } }, "solana")
+        .accounts({ payer: admin.publicKey, mint: metadataMint.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+      const metadata = await spl.getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata.additionalMetadata).to.be.deep.eq([["chain", "solana"]]);
+    });
+
+    it("setTokenMetadataUpdateAuthority rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .setTokenMetadataUpdateAuthority(staker1.publicKey)
+          .accounts({ payer: staker1.publicKey, mint: metadataMint.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("setTokenMetadataUpdateAuthority hands over the update authority", async () => {
+      await program.methods
+        .setTokenMetadataUpdateAuthority(newUpdateAuthority.publicKey)
+        .accounts({ payer: admin.publicKey, mint: metadataMint.publicKey })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" });
+
+      const metadata = await spl.getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata.updateAuthority.toBase58()).to.be.eq(newUpdateAuthority.publicKey.toBase58());
+    });
+
+    it("updateTokenMetadataField rejects once the update authority was handed over", async () => {
+      await expect(
+        program.methods
+          .updateTokenMetadataField({ uri: {} }, "https://example.com/other.json")
+          .accounts({ payer: admin.publicKey, mint: metadataMint.publicKey })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejected;
+
+      const metadata = await spl.getTokenMetadata(provider.connection, metadataMint.publicKey, "confirmed");
+      expect(metadata.uri).to.be.eq("https://example.com/sbtc.json");
+    });
+
+    it("updateMetadata rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .updateMetadata("Renamed BTC", null, null)
+          .accounts({ payer: staker1.publicKey, mint: stakedMintKeypair.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("setMetadataUpdateAuthority rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .setMetadataUpdateAuthority(staker1.publicKey)
+          .accounts({ payer: staker1.publicKey, mint: stakedMintKeypair.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("claimMetadataUpdateAuthority rejects when called by not admin", async () => {
+      await expect(
+        program.methods
+          .claimMetadataUpdateAuthority()
+          .accounts({ payer: staker1.publicKey, mint: stakedMintKeypair.publicKey })
+          .signers([staker1])
+          .rpc({ commitment: "confirmed" })
+      ).to.be.rejectedWith("Unauthorized");
+    });
+  });
+
   describe("Ratio", function () {});
 });
@@ -30,7 +30,7 @@ class BridgePayload {
   recipient: string;
   amount: string;
 
-  constructor(token: Uint8Array, sender: Uint8Array, recipient: Uint8Array, amount: number, version: number = 1) {
+  constructor(token: Uint8Array, sender: Uint8Array, recipient: Uint8Array, amount: number | bigint, version: number = 1) {
     this.version = ("00" + version.toString(16)).slice(-2);
     this.token = toHexString(token);
     this.sender = toHexString(sender);
@@ -448,7 +448,7 @@ describe("Bridge", () => {
     it("Enable local mint_1", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setLocalTokenConfig(mint)
+          .setLocalTokenConfig(mint, { burnMint: {} })
           .accounts({
             admin: admin.publicKey
           })
@@ -463,7 +463,7 @@ describe("Bridge", () => {
     it("Enable local mint_2", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setLocalTokenConfig(mint2)
+          .setLocalTokenConfig(mint2, { burnMint: {} })
           .accounts({
             admin: admin.publicKey
           })
@@ -479,7 +479,7 @@ describe("Bridge", () => {
       const foreignTokenBytes = Array.from(randomBytes(32));
       await withBlockhashRetry(() =>
         bridge.methods
-          .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3)
+          .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, 8)
           .accounts({
             admin: admin.publicKey
           })
@@ -496,7 +496,7 @@ describe("Bridge", () => {
     it("Change remote token_1 on chain_1", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3)
+          .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, 8)
           .accounts({
             admin: admin.publicKey
           })
@@ -513,7 +513,7 @@ describe("Bridge", () => {
     it("Set remote token_2 on chain_1", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setRemoteTokenConfig(mint2, foreignLchainIdBytes, foreignTokenBytes2, 3)
+          .setRemoteTokenConfig(mint2, foreignLchainIdBytes, foreignTokenBytes2, 3, 8)
           .accounts({
             admin: admin.publicKey
           })
@@ -530,7 +530,7 @@ describe("Bridge", () => {
     it("Set remote token_1 on chain_2", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setRemoteTokenConfig(mint, foreignLchainIdBytes2, foreignTokenBytes, 3)
+          .setRemoteTokenConfig(mint, foreignLchainIdBytes2, foreignTokenBytes, 3, 8)
           .accounts({
             admin: admin.publicKey
           })
@@ -542,7 +542,7 @@ describe("Bridge", () => {
     it("Set remote token_2 on chain_2", async () => {
       await withBlockhashRetry(() =>
         bridge.methods
-          .setRemoteTokenConfig(mint2, foreignLchainIdBytes2, foreignTokenBytes2, 3)
+          .setRemoteTokenConfig(mint2, foreignLchainIdBytes2, foreignTokenBytes2, 3, 8)
           .accounts({
             admin: admin.publicKey
           })
@@ -691,6 +691,7 @@ describe("Bridge", () => {
             .handleMessage(payloadHashBytes)
             .accounts({
               handler: payer.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: bridge.programId
             })
             .remainingAccounts([
@@ -813,6 +814,10 @@ describe("Bridge", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       const amountToSend = 2000;
 
@@ -832,6 +837,7 @@ describe("Bridge", () => {
             mailbox: mailbox.programId,
             mailboxConfig: mailboxConfigPDA,
             outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
             outboundMessagePath: outboundMessagePathPDA,
             mailboxSenderConfig: bridgeSenderConfigPDA,
             treasury: treasury.publicKey
@@ -886,6 +892,10 @@ describe("Bridge", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       const amountToSend = 2000;
 
@@ -905,6 +915,7 @@ describe("Bridge", () => {
             mailbox: mailbox.programId,
             mailboxConfig: mailboxConfigPDA,
             outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
             outboundMessagePath: outboundMessagePathPDA,
             mailboxSenderConfig: bridgeSenderConfigPDA,
             treasury: treasury.publicKey
@@ -1006,6 +1017,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -1024,6 +1039,7 @@ describe("Bridge", () => {
 
               outboundMessagePath: arg.outboundMessagePathPDA(),
               outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
               remoteBridgeConfig: arg.remoteBridgeConfig(),
               localTokenConfig: arg.localTokenConfig(),
               remoteTokenConfig: arg.remoteTokenConfig(),
@@ -1114,6 +1130,10 @@ describe("Bridge", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       const amountToSend = 2000;
 
@@ -1134,6 +1154,7 @@ describe("Bridge", () => {
               mailbox: mailbox.programId,
               mailboxConfig: mailboxConfigPDA,
               outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
               outboundMessagePath: outboundMessagePathPDA,
               mailboxSenderConfig: bridgeSenderConfigPDA,
               treasury: treasury.publicKey
@@ -1174,6 +1195,7 @@ describe("Bridge", () => {
             .handleMessage(payloadHashBytes)
             .accounts({
               handler: payer.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: bridge.programId
             })
             .remainingAccounts([
@@ -1315,6 +1337,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -1335,6 +1361,7 @@ describe("Bridge", () => {
                 mailbox: mailbox.programId,
                 mailboxConfig: mailboxConfigPDA,
                 outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
                 outboundMessagePath: outboundMessagePathPDA,
                 mailboxSenderConfig: bridgeSenderConfigPDA,
                 treasury: treasury.publicKey
@@ -1375,6 +1402,7 @@ describe("Bridge", () => {
               .handleMessage(payloadHashBytes)
               .accounts({
                 handler: payer.publicKey,
+                deliverer: payer.publicKey,
                 recipientProgram: bridge.programId
               })
               .remainingAccounts([
@@ -1494,6 +1522,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -1514,6 +1546,7 @@ describe("Bridge", () => {
                 mailbox: mailbox.programId,
                 mailboxConfig: mailboxConfigPDA,
                 outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
                 outboundMessagePath: outboundMessagePathPDA,
                 mailboxSenderConfig: bridgeSenderConfigPDA,
                 treasury: treasury.publicKey
@@ -1554,6 +1587,7 @@ describe("Bridge", () => {
               .handleMessage(payloadHashBytes)
               .accounts({
                 handler: payer.publicKey,
+                deliverer: payer.publicKey,
                 recipientProgram: bridge.programId
               })
               .remainingAccounts([
@@ -1641,7 +1675,7 @@ describe("Bridge", () => {
       after("Enable local token_1", async function () {
         await withBlockhashRetry(() =>
           bridge.methods
-            .setLocalTokenConfig(mint)
+            .setLocalTokenConfig(mint, { burnMint: {} })
             .accounts({
               admin: admin.publicKey
             })
@@ -1675,6 +1709,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -1695,6 +1733,7 @@ describe("Bridge", () => {
                 mailbox: mailbox.programId,
                 mailboxConfig: mailboxConfigPDA,
                 outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
                 outboundMessagePath: outboundMessagePathPDA,
                 mailboxSenderConfig: bridgeSenderConfigPDA,
                 treasury: treasury.publicKey
@@ -1735,6 +1774,7 @@ describe("Bridge", () => {
               .handleMessage(payloadHashBytes)
               .accounts({
                 handler: payer.publicKey,
+                deliverer: payer.publicKey,
                 recipientProgram: bridge.programId
               })
               .remainingAccounts([
@@ -1822,7 +1862,7 @@ describe("Bridge", () => {
       after("Enable remote token_1 chain_1", async () => {
         await withBlockhashRetry(() =>
           bridge.methods
-            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3)
+            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, 8)
             .accounts({
               admin: admin.publicKey
             })
@@ -1836,7 +1876,7 @@ describe("Bridge", () => {
       before("Disable remote token_1 chain_1", async function () {
         await withBlockhashRetry(() =>
           bridge.methods
-            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 0)
+            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 0, 8)
             .accounts({
               admin: admin.publicKey
             })
@@ -1854,6 +1894,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -1874,6 +1918,7 @@ describe("Bridge", () => {
                 mailbox: mailbox.programId,
                 mailboxConfig: mailboxConfigPDA,
                 outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
                 outboundMessagePath: outboundMessagePathPDA,
                 mailboxSenderConfig: bridgeSenderConfigPDA,
                 treasury: treasury.publicKey
@@ -1914,6 +1959,7 @@ describe("Bridge", () => {
               .handleMessage(payloadHashBytes)
               .accounts({
                 handler: payer.publicKey,
+                deliverer: payer.publicKey,
                 recipientProgram: bridge.programId
               })
               .remainingAccounts([
@@ -2001,7 +2047,7 @@ describe("Bridge", () => {
       after("Enable remote token_1 chain_1", async () => {
         await withBlockhashRetry(() =>
           bridge.methods
-            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3)
+            .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, 8)
             .accounts({
               admin: admin.publicKey
             })
@@ -2035,6 +2081,10 @@ describe("Bridge", () => {
           [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
           mailbox.programId
         )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
 
         const amountToSend = 2000;
 
@@ -2055,6 +2105,7 @@ describe("Bridge", () => {
                 mailbox: mailbox.programId,
                 mailboxConfig: mailboxConfigPDA,
                 outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
                 outboundMessagePath: outboundMessagePathPDA,
                 mailboxSenderConfig: bridgeSenderConfigPDA,
                 treasury: treasury.publicKey
@@ -2256,6 +2307,7 @@ describe("Bridge", () => {
                 .handleMessage(payloadHashBytes)
                 .accounts({
                   handler: payer.publicKey,
+                  deliverer: payer.publicKey,
                   recipientProgram: bridge.programId
                 })
                 .remainingAccounts([
@@ -2416,6 +2468,10 @@ describe("Bridge", () => {
             [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
             mailbox.programId
           )[0];
+          const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+            [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+            mailbox.programId
+          )[0];
 
           const amountToSend = 2000;
 
@@ -2435,6 +2491,7 @@ describe("Bridge", () => {
 
                   outboundMessagePath: arg.outboundMessagePathPDA(),
                   outboundMessage: outboundMessagePDA,
+                  outboundMessageRecord: outboundMessageRecordPDA,
                   remoteBridgeConfig: arg.remoteBridgeConfig(),
                   remoteTokenConfig: arg.remoteTokenConfig(),
                   localTokenConfig: arg.localTokenConfig(),
@@ -2515,6 +2572,7 @@ describe("Bridge", () => {
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: bridge.programId
           })
           .remainingAccounts([
@@ -2643,6 +2701,7 @@ describe("Bridge", () => {
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: bridge.programId
           })
           .remainingAccounts([
@@ -2768,6 +2827,7 @@ describe("Bridge", () => {
             .handleMessage(payloadHashBytes)
             .accounts({
               handler: payer.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: bridge.programId
             })
             .remainingAccounts([
@@ -2881,6 +2941,7 @@ describe("Bridge", () => {
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: bridge.programId
           })
           .remainingAccounts([
@@ -2967,7 +3028,7 @@ describe("Bridge", () => {
       expect(tokenBalanceAfter.amount).to.be.equal(tokenBalanceBefore.amount + BigInt(bridgeCapacity));
     });
 
-    it("rate limit does not affect deposits", async () => {
+    it("inbound rate limit does not affect deposits", async () => {
       const bridgeRemoteTokenConfigBefore = await bridge.account.remoteTokenConfig.fetch(remoteTokenConfigPDA11);
 
       let config = await mailbox.account.config.fetch(mailboxConfigPDA);
@@ -2978,6 +3039,10 @@ describe("Bridge", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       const amountToSend = bridgeCapacity * 10;
 
@@ -2997,6 +3062,7 @@ describe("Bridge", () => {
             mailbox: mailbox.programId,
             mailboxConfig: mailboxConfigPDA,
             outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
             outboundMessagePath: outboundMessagePathPDA,
             mailboxSenderConfig: bridgeSenderConfigPDA,
             treasury: treasury.publicKey
@@ -3043,6 +3109,7 @@ describe("Bridge", () => {
             .handleMessage(payloadHashBytes)
             .accounts({
               handler: payer.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: bridge.programId
             })
             .remainingAccounts([
@@ -3158,6 +3225,7 @@ describe("Bridge", () => {
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: bridge.programId
           })
           .remainingAccounts([
@@ -3311,6 +3379,7 @@ describe("Bridge", () => {
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: bridge.programId
           })
           .remainingAccounts([
@@ -3397,4 +3466,531 @@ describe("Bridge", () => {
       expect(tokenBalanceAfter.amount).to.be.equal(tokenBalanceBefore.amount + BigInt(amountToReceive));
     });
   });
+
+  describe("Outbound rate limit", () => {
+    const capacity = 1000;
+    const disabled = { rate: new BN(0), capacity: new BN(0), enabled: false };
+
+    const deposit = async (amount: number) => {
+      const config = await mailbox.account.config.fetch(mailboxConfigPDA);
+      const outboundMessagePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const recipientBz = Array.from(Buffer.from(sha256("recipient"), "hex"));
+      const senderBz = Array.from(Uint8Array.from(user.publicKey.toBuffer()));
+      return withBlockhashRetry(() =>
+        bridge.methods
+          .deposit(senderBz, recipientBz, foreignCallerBytes, new BN(amount), null)
+          .accountsPartial({
+            sender: sender.publicKey,
+            senderTokenAccount: senderTA,
+            tokenProgram: spl.TOKEN_PROGRAM_ID,
+            config: bridgeConfigPDA,
+            senderConfig: senderConfigPDA,
+            remoteBridgeConfig: remoteBridgeConfigPDA,
+            localTokenConfig: localTokenConfigPDA,
+            remoteTokenConfig: remoteTokenConfigPDA11,
+            mint: mint,
+            mailbox: mailbox.programId,
+            mailboxConfig: mailboxConfigPDA,
+            outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
+            outboundMessagePath: outboundMessagePathPDA,
+            mailboxSenderConfig: bridgeSenderConfigPDA,
+            treasury: treasury.publicKey
+          })
+          .signers([sender])
+          .rpc({ commitment: "confirmed" })
+      );
+    };
+
+    const setOutboundRateLimit = (outbound: { rate: BN; capacity: BN; enabled: boolean }) =>
+      withBlockhashRetry(() =>
+        bridge.methods
+          .setOutboundRateLimit(mint, foreignLchainIdBytes, outbound)
+          .accountsPartial({
+            admin: admin.publicKey,
+            config: bridgeConfigPDA,
+            remoteTokenConfig: remoteTokenConfigPDA11
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    after("disable the outbound rate limit", async () => {
+      await setOutboundRateLimit(disabled);
+    });
+
+    it("setOutboundRateLimit rejects when called by not admin", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          bridge.methods
+            .setOutboundRateLimit(mint, foreignLchainIdBytes, disabled)
+            .accountsPartial({
+              admin: user.publicKey,
+              config: bridgeConfigPDA,
+              remoteTokenConfig: remoteTokenConfigPDA11
+            })
+            .signers([user])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("enable outbound rate limit for token_1 to chain_1", async () => {
+      const inboundBefore = (await bridge.account.remoteTokenConfig.fetch(remoteTokenConfigPDA11)).inboundRateLimit;
+      await setOutboundRateLimit({ rate: new BN(1), capacity: new BN(capacity), enabled: true });
+
+      const remoteTokenConfig = await bridge.account.remoteTokenConfig.fetch(remoteTokenConfigPDA11);
+      // the inbound rate limit is left as is
+      expect(remoteTokenConfig.inboundRateLimit.cfg.enabled).to.be.eq(inboundBefore.cfg.enabled);
+      expect(remoteTokenConfig.inboundRateLimit.cfg.capacity.toNumber()).to.be.eq(
+        inboundBefore.cfg.capacity.toNumber()
+      );
+      expect(remoteTokenConfig.outboundRateLimit.cfg.enabled).to.be.true;
+      expect(remoteTokenConfig.outboundRateLimit.cfg.capacity.toNumber()).to.be.eq(capacity);
+      expect(remoteTokenConfig.outboundRateLimit.tokens.toNumber()).to.be.eq(capacity);
+    });
+
+    it("deposit consumes the outbound rate limit", async () => {
+      await deposit(capacity / 2);
+
+      const remoteTokenConfig = await bridge.account.remoteTokenConfig.fetch(remoteTokenConfigPDA11);
+      expect(remoteTokenConfig.outboundRateLimit.tokens.toNumber()).to.be.closeTo(capacity / 2, 5);
+    });
+
+    it("deposit rejects when outbound rate limit is reached", async () => {
+      await expect(deposit(capacity - 10)).to.be.rejectedWith("RLRateLimitReached");
+    });
+
+    it("deposit rejects when amount exceeds outbound capacity", async () => {
+      await expect(deposit(capacity + 1)).to.be.rejectedWith("RLMaxCapacityExceeded");
+    });
+  });
+
+  describe("Lock and release", () => {
+    const rebalancer = Keypair.generate();
+    const mintKeys3 = Keypair.generate();
+    let mint3: PublicKey;
+    let rebalancerTA3: PublicKey;
+    let senderTA3: PublicKey;
+    let userTA3: PublicKey;
+    let vaultTA3: PublicKey;
+    let tokenVaultPDA: PublicKey;
+    let localTokenConfigPDA3: PublicKey;
+    let remoteTokenConfigPDA31: PublicKey;
+    const foreignTokenBytes3 = Array.from(Uint8Array.from(Buffer.from(sha256("foreign-token-3"), "hex")));
+
+    const liquidity = (method: "addLiquidity" | "removeLiquidity", signer: Keypair, token: PublicKey, amount: number) =>
+      withBlockhashRetry(() =>
+        bridge.methods[method](new BN(amount))
+          .accountsPartial({
+            rebalancer: signer.publicKey,
+            mint: token,
+            rebalancerTokenAccount: spl.getAssociatedTokenAddressSync(token, signer.publicKey),
+            tokenProgram: spl.TOKEN_PROGRAM_ID
+          })
+          .signers([signer])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    const deposit = async (amount: number, withVault: boolean) => {
+      const config = await mailbox.account.config.fetch(mailboxConfigPDA);
+      const outboundMessagePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const recipientBz = Array.from(Buffer.from(sha256("recipient"), "hex"));
+      const senderBz = Array.from(Uint8Array.from(user.publicKey.toBuffer()));
+      return withBlockhashRetry(() =>
+        bridge.methods
+          .deposit(senderBz, recipientBz, foreignCallerBytes, new BN(amount), null)
+          .accountsPartial({
+            sender: sender.publicKey,
+            senderTokenAccount: senderTA3,
+            tokenProgram: spl.TOKEN_PROGRAM_ID,
+            config: bridgeConfigPDA,
+            senderConfig: senderConfigPDA,
+            remoteBridgeConfig: remoteBridgeConfigPDA,
+            localTokenConfig: localTokenConfigPDA3,
+            remoteTokenConfig: remoteTokenConfigPDA31,
+            mint: mint3,
+            mailbox: mailbox.programId,
+            mailboxConfig: mailboxConfigPDA,
+            outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
+            outboundMessagePath: outboundMessagePathPDA,
+            mailboxSenderConfig: bridgeSenderConfigPDA,
+            treasury: treasury.publicKey,
+            tokenVault: withVault ? tokenVaultPDA : null,
+            vaultTokenAccount: withVault ? vaultTA3 : null
+          })
+          .signers([sender])
+          .rpc({ commitment: "confirmed" })
+      );
+    };
+
+    const receive = async (amount: number) => {
+      const bridgePayload = new BridgePayload(mint3.toBytes(), remoteSenderBytes, userTA3.toBytes(), amount);
+      const message = messageV1(
+        inboundMessagePath,
+        nonceForeignChain++,
+        foreignBridgeAddress,
+        bridge.programId.toBuffer(),
+        payer.publicKey.toBuffer(),
+        bridgePayload.bytes()
+      );
+      const { payloadHash, payloadHashBytes } = await mailboxUtilities.deliverMessage(
+        foreignMailboxAddress,
+        foreignLchainId,
+        payer,
+        message
+      );
+      const receiverMessageHandledPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("message_handled"), payloadHash],
+        bridge.programId
+      )[0];
+      const account = (pubkey: PublicKey, isWritable: boolean = false) => ({ pubkey, isWritable, isSigner: false });
+      return withBlockhashRetry(() =>
+        mailbox.methods
+          .handleMessage(payloadHashBytes)
+          .accounts({
+            handler: payer.publicKey,
+            deliverer: payer.publicKey,
+            recipientProgram: bridge.programId
+          })
+          .remainingAccounts([
+            { pubkey: payer.publicKey, isWritable: true, isSigner: true },
+            account(bridgeConfigPDA),
+            account(receiverMessageHandledPDA, true),
+            account(spl.TOKEN_PROGRAM_ID),
+            account(userTA3, true),
+            account(mint3, true),
+            account(multisig),
+            account(tokenAuth),
+            account(remoteBridgeConfigPDA),
+            account(localTokenConfigPDA3),
+            account(remoteTokenConfigPDA31, true),
+            account(inboundMessagePathPDA),
+            account(SystemProgram.programId),
+            // no proof of reserves accounts
+            account(bridge.programId),
+            account(bridge.programId),
+            account(bridge.programId),
+            account(tokenVaultPDA, true),
+            account(vaultTA3, true)
+          ])
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+    };
+
+    before("create a token the bridge cannot mint and enable it in lock and release mode", async () => {
+      await fundWallet(rebalancer, 5 * LAMPORTS_PER_SOL);
+      mint3 = await spl.createMint(
+        provider.connection,
+        admin,
+        admin.publicKey,
+        admin.publicKey,
+        8,
+        mintKeys3,
+        spl.TOKEN_PROGRAM_ID
+      );
+      [localTokenConfigPDA3] = PublicKey.findProgramAddressSync(
+        [Buffer.from("local_token_config"), mint3.toBytes()],
+        bridge.programId
+      );
+      [remoteTokenConfigPDA31] = PublicKey.findProgramAddressSync(
+        [Buffer.from("remote_token_config"), mint3.toBytes(), foreignLchainId],
+        bridge.programId
+      );
+      [tokenVaultPDA] = PublicKey.findProgramAddressSync([Buffer.from("token_vault"), mint3.toBytes()], bridge.programId);
+      vaultTA3 = spl.getAssociatedTokenAddressSync(mint3, tokenVaultPDA, true);
+
+      rebalancerTA3 = await spl.createAssociatedTokenAccount(provider.connection, rebalancer, mint3, rebalancer.publicKey);
+      senderTA3 = await spl.createAssociatedTokenAccount(provider.connection, sender, mint3, sender.publicKey);
+      userTA3 = await spl.createAssociatedTokenAccount(provider.connection, user, mint3, user.publicKey);
+      await spl.mintTo(provider.connection, admin, mint3, rebalancerTA3, admin, 10000);
+      await spl.mintTo(provider.connection, admin, mint3, senderTA3, admin, 10000);
+
+      await withBlockhashRetry(() =>
+        bridge.methods
+          .setLocalTokenConfig(mint3, { lockRelease: {} })
+          .accounts({
+            admin: admin.publicKey
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+      await withBlockhashRetry(() =>
+        bridge.methods
+          .setRemoteTokenConfig(mint3, foreignLchainIdBytes, foreignTokenBytes3, 3, 8)
+          .accounts({
+            admin: admin.publicKey
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+      await withBlockhashRetry(() =>
+        bridge.methods
+          .grantAccountRole(rebalancer.publicKey, { rebalancer: {} })
+          .accounts({
+            admin: admin.publicKey
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+
+      const localTokenConfig = await bridge.account.localTokenConfig.fetch(localTokenConfigPDA3);
+      expect(localTokenConfig.mode).to.be.deep.eq({ lockRelease: {} });
+    });
+
+    it("deposit rejects when the token vault is not passed", async () => {
+      await expect(deposit(100, false)).to.be.rejectedWith("MissingTokenVault");
+    });
+
+    it("addLiquidity rejects when called by not rebalancer", async () => {
+      await spl.getOrCreateAssociatedTokenAccount(provider.connection, payer, mint3, pauser.publicKey);
+      await expect(liquidity("addLiquidity", pauser, mint3, 100)).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("addLiquidity rejects for burn and mint tokens", async () => {
+      await spl.getOrCreateAssociatedTokenAccount(provider.connection, payer, mint, rebalancer.publicKey);
+      await expect(liquidity("addLiquidity", rebalancer, mint, 100)).to.be.rejectedWith("NotLockReleaseToken");
+    });
+
+    it("rebalancer adds liquidity", async () => {
+      await liquidity("addLiquidity", rebalancer, mint3, 1000);
+
+      const tokenVault = await bridge.account.tokenVault.fetch(tokenVaultPDA);
+      expect(tokenVault.mint.toBase58()).to.be.eq(mint3.toBase58());
+      expect(tokenVault.lockedAmount.toNumber()).to.be.eq(1000);
+      expect((await spl.getAccount(provider.connection, vaultTA3)).amount).to.be.eq(BigInt(1000));
+    });
+
+    it("deposit locks tokens into the vault", async () => {
+      const supplyBefore = (await spl.getMint(provider.connection, mint3)).supply;
+      await deposit(500, true);
+
+      const tokenVault = await bridge.account.tokenVault.fetch(tokenVaultPDA);
+      expect(tokenVault.lockedAmount.toNumber()).to.be.eq(1500);
+      expect((await spl.getAccount(provider.connection, vaultTA3)).amount).to.be.eq(BigInt(1500));
+      expect((await spl.getAccount(provider.connection, senderTA3)).amount).to.be.eq(BigInt(9500));
+      expect((await spl.getMint(provider.connection, mint3)).supply).to.be.eq(supplyBefore);
+    });
+
+    it("setLocalTokenConfig rejects a mode change while the vault holds locked tokens", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          bridge.methods
+            .setLocalTokenConfig(mint3, { burnMint: {} })
+            .accounts({
+              admin: admin.publicKey
+            })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("TokenVaultNotEmpty");
+    });
+
+    it("unsetLocalTokenConfig rejects while the vault holds locked tokens", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          bridge.methods
+            .unsetLocalTokenConfig(mint3)
+            .accounts({
+              admin: admin.publicKey
+            })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("TokenVaultNotEmpty");
+    });
+
+    it("receive releases tokens from the vault", async () => {
+      await receive(1200);
+
+      const tokenVault = await bridge.account.tokenVault.fetch(tokenVaultPDA);
+      expect(tokenVault.lockedAmount.toNumber()).to.be.eq(300);
+      expect((await spl.getAccount(provider.connection, userTA3)).amount).to.be.eq(BigInt(1200));
+    });
+
+    it("receive rejects when the vault has insufficient liquidity", async () => {
+      await expect(receive(301)).to.be.rejectedWith("InsufficientLiquidity");
+    });
+
+    it("removeLiquidity rejects when amount exceeds locked amount", async () => {
+      await expect(liquidity("removeLiquidity", rebalancer, mint3, 301)).to.be.rejectedWith("InsufficientLiquidity");
+    });
+
+    it("rebalancer removes liquidity", async () => {
+      await liquidity("removeLiquidity", rebalancer, mint3, 300);
+
+      const tokenVault = await bridge.account.tokenVault.fetch(tokenVaultPDA);
+      expect(tokenVault.lockedAmount.toNumber()).to.be.eq(0);
+      expect((await spl.getAccount(provider.connection, rebalancerTA3)).amount).to.be.eq(BigInt(9300));
+    });
+  });
+
+  describe("Decimal conversion", () => {
+    const setRemoteDecimals = (decimals: number) =>
+      withBlockhashRetry(() =>
+        bridge.methods
+          .setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, decimals)
+          .accounts({
+            admin: admin.publicKey
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    const deposit = async (amount: number) => {
+      const config = await mailbox.account.config.fetch(mailboxConfigPDA);
+      const outboundMessagePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const recipientBz = Array.from(Buffer.from(sha256("recipient"), "hex"));
+      const senderBz = Array.from(Uint8Array.from(user.publicKey.toBuffer()));
+      await withBlockhashRetry(() =>
+        bridge.methods
+          .deposit(senderBz, recipientBz, foreignCallerBytes, new BN(amount), null)
+          .accountsPartial({
+            sender: sender.publicKey,
+            senderTokenAccount: senderTA,
+            tokenProgram: spl.TOKEN_PROGRAM_ID,
+            config: bridgeConfigPDA,
+            senderConfig: senderConfigPDA,
+            remoteBridgeConfig: remoteBridgeConfigPDA,
+            localTokenConfig: localTokenConfigPDA,
+            remoteTokenConfig: remoteTokenConfigPDA11,
+            mint: mint,
+            mailbox: mailbox.programId,
+            mailboxConfig: mailboxConfigPDA,
+            outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
+            outboundMessagePath: outboundMessagePathPDA,
+            mailboxSenderConfig: bridgeSenderConfigPDA,
+            treasury: treasury.publicKey
+          })
+          .signers([sender])
+          .rpc({ commitment: "confirmed" })
+      );
+      return outboundMessagePDA;
+    };
+
+    const receive = async (amount: bigint) => {
+      const bridgePayload = new BridgePayload(mint.toBytes(), remoteSenderBytes, userTA.toBytes(), amount);
+      const message = messageV1(
+        inboundMessagePath,
+        nonceForeignChain++,
+        foreignBridgeAddress,
+        bridge.programId.toBuffer(),
+        payer.publicKey.toBuffer(),
+        bridgePayload.bytes()
+      );
+      const { payloadHash, payloadHashBytes } = await mailboxUtilities.deliverMessage(
+        foreignMailboxAddress,
+        foreignLchainId,
+        payer,
+        message
+      );
+      const receiverMessageHandledPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("message_handled"), payloadHash],
+        bridge.programId
+      )[0];
+      const account = (pubkey: PublicKey, isWritable: boolean = false) => ({ pubkey, isWritable, isSigner: false });
+      return withBlockhashRetry(() =>
+        mailbox.methods
+          .handleMessage(payloadHashBytes)
+          .accounts({
+            handler: payer.publicKey,
+            deliverer: payer.publicKey,
+            recipientProgram: bridge.programId
+          })
+          .remainingAccounts([
+            { pubkey: payer.publicKey, isWritable: true, isSigner: true },
+            account(bridgeConfigPDA),
+            account(receiverMessageHandledPDA, true),
+            account(spl.TOKEN_PROGRAM_ID),
+            account(userTA, true),
+            account(mint, true),
+            account(multisig),
+            account(tokenAuth),
+            account(remoteBridgeConfigPDA),
+            account(localTokenConfigPDA),
+            account(remoteTokenConfigPDA11, true),
+            account(inboundMessagePathPDA),
+            account(SystemProgram.programId)
+          ])
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+    };
+
+    // the 129 bytes body of the bridge message ends with the amount, as an uint256, and is
+    // followed by 31 bytes of ABI padding
+    const sentAmount = async (outboundMessagePDA: PublicKey) => {
+      const info = await provider.connection.getAccountInfo(outboundMessagePDA);
+      const end = info.data.length - 31;
+      return BigInt("0x" + info.data.subarray(end - 32, end).toString("hex"));
+    };
+
+    after("restore remote decimals", async () => {
+      await setRemoteDecimals(8);
+    });
+
+    it("setRemoteTokenConfig records remote decimals", async () => {
+      await setRemoteDecimals(6);
+
+      const remoteTokenConfig = await bridge.account.remoteTokenConfig.fetch(remoteTokenConfigPDA11);
+      expect(remoteTokenConfig.decimals).to.be.eq(6);
+    });
+
+    it("deposit scales down the amount to remote decimals", async () => {
+      const outboundMessagePDA = await deposit(1200);
+      expect(await sentAmount(outboundMessagePDA)).to.be.eq(BigInt(12));
+    });
+
+    it("deposit rejects amount with dust", async () => {
+      await expect(deposit(1201)).to.be.rejectedWith("AmountHasDust");
+    });
+
+    it("receive scales up the amount to local decimals", async () => {
+      const balanceBefore = (await spl.getAccount(provider.connection, userTA)).amount;
+      await receive(BigInt(3));
+      expect((await spl.getAccount(provider.connection, userTA)).amount).to.be.eq(balanceBefore + BigInt(300));
+    });
+
+    it("deposit scales up the amount to remote decimals", async () => {
+      await setRemoteDecimals(18);
+      const outboundMessagePDA = await deposit(1201);
+      expect(await sentAmount(outboundMessagePDA)).to.be.eq(BigInt(1201) * BigInt(10) ** BigInt(10));
+    });
+
+    it("receive truncates dust of remote amount", async () => {
+      const balanceBefore = (await spl.getAccount(provider.connection, userTA)).amount;
+      await receive(BigInt(123456789012));
+      expect((await spl.getAccount(provider.connection, userTA)).amount).to.be.eq(balanceBefore + BigInt(12));
+    });
+
+    it("receive rejects amount exceeding local token range", async () => {
+      await expect(receive(BigInt(2) ** BigInt(64) * BigInt(10) ** BigInt(10))).to.be.rejectedWith(
+        "InvalidAmountConversion"
+      );
+    });
+  });
 });
@@ -415,7 +415,7 @@ describe("CCIP Token Pool", () => {
 			);
 			await withBlockhashRetry(() =>
 			  bridge.methods
-				.setLocalTokenConfig(mint)
+				.setLocalTokenConfig(mint, { burnMint: {} })
 				.accounts({
 				admin: admin.publicKey
 				})
@@ -424,7 +424,7 @@ describe("CCIP Token Pool", () => {
 			);
 			await withBlockhashRetry(() =>
 			  bridge.methods
-				.setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3)
+				.setRemoteTokenConfig(mint, foreignLchainIdBytes, foreignTokenBytes, 3, 8)
 				.accounts({
 				admin: admin.publicKey
 				})
@@ -652,7 +652,7 @@ describe("CCIP Token Pool", () => {
 					},
 					{ // inboundMessagePath
 						pubkey: inboundMessagePathPDA,
-						isWritable: false,
+						isWritable: true,
 						isSigner: false
 					},
 					{ // messageInfo
@@ -665,6 +665,16 @@ describe("CCIP Token Pool", () => {
 						isWritable: true,
 						isSigner: false
 					},
+					{ // deliverer
+						pubkey: payer.publicKey,
+						isWritable: true,
+						isSigner: false
+					},
+					{ // mailboxEventAuthority
+						pubkey: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], mailbox.programId)[0],
+						isWritable: false,
+						isSigner: false
+					},
 				])
 				.signers([payer])
 				.rpc({ commitment: "confirmed" })
@@ -733,6 +743,10 @@ describe("CCIP Token Pool", () => {
 				[Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
 				mailbox.programId
 			)[0];
+			const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+				[Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+				mailbox.programId
+			)[0];
 
 			const tokenBalanceBefore = await spl.getAccount(provider.connection, payerTA);
 
@@ -856,7 +870,7 @@ describe("CCIP Token Pool", () => {
 					},
 					{ // outboundMessagePath
 						pubkey: outboundMessagePathPDA,
-						isWritable: false,
+						isWritable: true,
 						isSigner: false
 					},
 					{ // Treasury
@@ -869,6 +883,16 @@ describe("CCIP Token Pool", () => {
 						isWritable: true,
 						isSigner: false
 					},
+					{ // outboundMessageRecord
+						pubkey: outboundMessageRecordPDA,
+						isWritable: true,
+						isSigner: false
+					},
+					{ // mailboxEventAuthority
+						pubkey: PublicKey.findProgramAddressSync([Buffer.from("__event_authority")], mailbox.programId)[0],
+						isWritable: false,
+						isSigner: false
+					},
 				])
 				.signers([payer])
 				.rpc({ commitment: "confirmed" })
@@ -0,0 +1,266 @@
+import * as anchor from "@coral-xyz/anchor";
+import { Program } from "@coral-xyz/anchor";
+import * as spl from "@solana/spl-token";
+import {
+  Keypair,
+  LAMPORTS_PER_SOL,
+  PublicKey,
+  SystemProgram,
+  Transaction,
+  sendAndConfirmTransaction
+} from "@solana/web3.js";
+import { ComplianceHook } from "../target/types/compliance_hook";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { withBlockhashRetry } from "./utils/utils";
+
+chai.use(chaiAsPromised);
+const expect = chai.expect;
+
+describe("Compliance hook", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ComplianceHook as Program<ComplianceHook>;
+
+  let admin: Keypair;
+  let compliance: Keypair;
+  let sender: Keypair;
+  let receiver: Keypair;
+  let other: Keypair;
+  let configPDA: PublicKey;
+  let mint: Keypair;
+  let senderTA: PublicKey;
+  let receiverTA: PublicKey;
+
+  async function fundWallet(account: Keypair, amount: number) {
+    const tx = await provider.connection.requestAirdrop(account.publicKey, amount * LAMPORTS_PER_SOL);
+    const lastBlockHash = await provider.connection.getLatestBlockhash();
+    await provider.connection.confirmTransaction({
+      blockhash: lastBlockHash.blockhash,
+      lastValidBlockHeight: lastBlockHash.lastValidBlockHeight,
+      signature: tx
+    });
+  }
+
+  function denylistEntryPDA(account: PublicKey): PublicKey {
+    return PublicKey.findProgramAddressSync([Buffer.from("denylist"), account.toBuffer()], program.programId)[0];
+  }
+
+  async function transfer(amount: number) {
+    const ix = await spl.createTransferCheckedWithTransferHookInstruction(
+      provider.connection,
+      senderTA,
+      mint.publicKey,
+      receiverTA,
+      sender.publicKey,
+      BigInt(amount),
+      8,
+      [],
+      "confirmed",
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    return sendAndConfirmTransaction(provider.connection, new Transaction().add(ix), [sender], {
+      commitment: "confirmed"
+    });
+  }
+
+  before(async () => {
+    admin = Keypair.generate();
+    compliance = Keypair.generate();
+    sender = Keypair.generate();
+    receiver = Keypair.generate();
+    other = Keypair.generate();
+    mint = Keypair.generate();
+
+    await fundWallet(admin, 25);
+    await fundWallet(compliance, 25);
+    await fundWallet(sender, 25);
+    await fundWallet(other, 25);
+
+    [configPDA] = PublicKey.findProgramAddressSync([Buffer.from("compliance_hook_config")], program.programId);
+
+    // Token-2022 mint with the compliance hook as its transfer hook
+    const mintLen = spl.getMintLen([spl.ExtensionType.TransferHook]);
+    const lamports = await provider.connection.getMinimumBalanceForRentExemption(mintLen);
+    const tx = new Transaction().add(
+      SystemProgram.createAccount({
+        fromPubkey: admin.publicKey,
+        newAccountPubkey: mint.publicKey,
+        space: mintLen,
+        lamports,
+        programId: spl.TOKEN_2022_PROGRAM_ID
+      }),
+      spl.createInitializeTransferHookInstruction(
+        mint.publicKey,
+        admin.publicKey,
+        program.programId,
+        spl.TOKEN_2022_PROGRAM_ID
+      ),
+      spl.createInitializeMintInstruction(mint.publicKey, 8, admin.publicKey, null, spl.TOKEN_2022_PROGRAM_ID)
+    );
+    await sendAndConfirmTransaction(provider.connection, tx, [admin, mint], { commitment: "confirmed" });
+
+    senderTA = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      admin,
+      mint.publicKey,
+      sender.publicKey,
+      { commitment: "confirmed" },
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    receiverTA = await spl.createAssociatedTokenAccount(
+      provider.connection,
+      admin,
+      mint.publicKey,
+      receiver.publicKey,
+      { commitment: "confirmed" },
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+    await spl.mintTo(
+      provider.connection,
+      admin,
+      mint.publicKey,
+      senderTA,
+      admin,
+      1000,
+      [],
+      { commitment: "confirmed" },
+      spl.TOKEN_2022_PROGRAM_ID
+    );
+  });
+
+  describe("Initialize and admin", () => {
+    it("initialize: fails when payer is not deployer", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          program.methods
+            .initialize(admin.publicKey)
+            .accounts({ deployer: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejected;
+    });
+
+    it("initialize: successful", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .initialize(admin.publicKey)
+          .accounts({ deployer: provider.wallet.publicKey })
+          .signers([Keypair.fromSecretKey(provider.wallet.payer.secretKey)])
+          .rpc({ commitment: "confirmed" })
+      );
+
+      const cfg = await program.account.config.fetch(configPDA);
+      expect(cfg.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+    });
+
+    it("initializeExtraAccountMetas: failure from unauthorized party", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          program.methods
+            .initializeExtraAccountMetas()
+            .accounts({ admin: other.publicKey, mint: mint.publicKey, tokenProgram: spl.TOKEN_2022_PROGRAM_ID })
+            .signers([other])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("initializeExtraAccountMetas: successful", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .initializeExtraAccountMetas()
+          .accounts({ admin: admin.publicKey, mint: mint.publicKey, tokenProgram: spl.TOKEN_2022_PROGRAM_ID })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+    });
+
+    it("grantAccountRole: compliance role", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .grantAccountRole(compliance.publicKey, { compliance: {} })
+          .accounts({ admin: admin.publicKey })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+    });
+  });
+
+  describe("Denylist", () => {
+    it("transfer: successful when neither side is denylisted", async () => {
+      await transfer(100);
+      const account = await spl.getAccount(provider.connection, receiverTA, "confirmed", spl.TOKEN_2022_PROGRAM_ID);
+      expect(Number(account.amount)).to.equal(100);
+    });
+
+    it("addToDenylist: failure from unauthorized party", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          program.methods
+            .addToDenylist(receiver.publicKey)
+            .accounts({ authority: other.publicKey, accountRoles: null })
+            .signers([other])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("addToDenylist: successful by compliance role", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .addToDenylist(receiver.publicKey)
+          .accounts({ authority: compliance.publicKey })
+          .signers([compliance])
+          .rpc({ commitment: "confirmed" })
+      );
+      const entry = await program.account.denylistEntry.fetch(denylistEntryPDA(receiver.publicKey));
+      expect(entry.account.toBase58()).to.equal(receiver.publicKey.toBase58());
+    });
+
+    it("transfer: rejects denylisted receiver", async () => {
+      await expect(transfer(100)).to.be.rejectedWith(/0x1773|ReceiverDenylisted/);
+    });
+
+    it("addToDenylist: successful by admin", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .addToDenylist(sender.publicKey)
+          .accounts({ authority: admin.publicKey, accountRoles: null })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+    });
+
+    it("removeFromDenylist: successful", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .removeFromDenylist(receiver.publicKey)
+          .accounts({ authority: compliance.publicKey })
+          .signers([compliance])
+          .rpc({ commitment: "confirmed" })
+      );
+      const info = await provider.connection.getAccountInfo(denylistEntryPDA(receiver.publicKey), "confirmed");
+      expect(info).to.be.null;
+    });
+
+    it("transfer: rejects denylisted sender", async () => {
+      await expect(transfer(100)).to.be.rejectedWith(/0x1772|SenderDenylisted/);
+    });
+
+    it("transfer: successful after removal from denylist", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .removeFromDenylist(sender.publicKey)
+          .accounts({ authority: admin.publicKey, accountRoles: null })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+      await transfer(100);
+      const account = await spl.getAccount(provider.connection, receiverTA, "confirmed", spl.TOKEN_2022_PROGRAM_ID);
+      expect(Number(account.amount)).to.equal(200);
+    });
+  });
+});
@@ -124,6 +124,8 @@ describe("LBTC", () => {
     [Buffer.from("token_authority")],
     program.programId
   )[0] as PublicKey;
+  const accountRolesPDA = (account: PublicKey) =>
+    PublicKey.findProgramAddressSync([Buffer.from("account_roles"), account.toBuffer()], program.programId)[0];
   let metadata_seed = new Uint8Array(32);
   for (let i = 0; i < metadata_seed.length; i++) {
     metadata_seed[i] = 1;
@@ -182,9 +184,9 @@ describe("LBTC", () => {
 
   describe("Initialize and set roles", function () {
     it("initialize: fails when payer is not deployer", async () => {
-      const burnCommission = new BN(1);
+      const burnCommission = { flat: { amount: new BN(1) } };
       const dustFeeRate = new BN(1000);
-      const mintFee = new BN(1);
+      const mintFee = { flat: { amount: new BN(1) } };
       const programData = PublicKey.findProgramAddressSync(
         [program.programId.toBuffer()],
         new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
@@ -208,9 +210,9 @@ describe("LBTC", () => {
     });
 
     it("initialize: successful", async () => {
-      const burnCommission = new BN(1);
+      const burnCommission = { flat: { amount: new BN(1) } };
       const dustFeeRate = new BN(1000);
-      const mintFee = new BN(1);
+      const mintFee = { flat: { amount: new BN(1) } };
       const programData = PublicKey.findProgramAddressSync(
         [program.programId.toBuffer()],
         new PublicKey("BPFLoaderUpgradeab1e11111111111111111111111")
@@ -263,57 +265,57 @@ describe("LBTC", () => {
     });
 
     //Claimer is a role which can perform autoclaim
-    it("addClaimer: successful by admin", async () => {
+    it("grantAccountRole: claimer successful by admin", async () => {
       await withBlockhashRetry(() =>
         program.methods
-        .addClaimer(claimer.publicKey)
-        .accounts({ payer: admin.publicKey, config: configPDA })
+        .grantAccountRole(claimer.publicKey, { claimer: {} })
+        .accounts({ payer: admin.publicKey })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
       );
 
-      const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.claimers[0].toBase58() == claimer.publicKey.toBase58());
+      const roles = await program.account.accountRoles.fetch(accountRolesPDA(claimer.publicKey));
+      expect(roles.roles).to.deep.eq([{ claimer: {} }]);
     });
 
     //Claimer is a role which can perform autoclaim
-    it("addClaimer: adding twice should not increase claimers", async () => {
+    it("grantAccountRole: granting claimer twice should fail", async () => {
       await expect(
           withBlockhashRetry(() =>
             program.methods
-          .addClaimer(claimer.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
+          .grantAccountRole(claimer.publicKey, { claimer: {} })
+          .accounts({ payer: admin.publicKey })
           .signers([admin])
           .rpc({ commitment: "confirmed" })
           )
-        ).to.be.rejectedWith("ClaimerExists");
+        ).to.be.rejectedWith("AccountRoleAlreadyGranted");
     });
 
     //Pauser is a role that can only set contracts on pause
-    it("addPauser: successful by admin", async () => {
+    it("grantAccountRole: pauser successful by admin", async () => {
       await withBlockhashRetry(() =>
         program.methods
-        .addPauser(pauser.publicKey)
-        .accounts({ payer: admin.publicKey, config: configPDA })
+        .grantAccountRole(pauser.publicKey, { pauser: {} })
+        .accounts({ payer: admin.publicKey })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
       );
 
-      const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.pausers[0].toBase58() == pauser.publicKey.toBase58());
+      const roles = await program.account.accountRoles.fetch(accountRolesPDA(pauser.publicKey));
+      expect(roles.roles).to.deep.eq([{ pauser: {} }]);
     });
 
     //Pauser is a role that can only set contracts on pause
-    it("addPauser: adding twice should not increase pausers", async () => {
+    it("grantAccountRole: granting pauser twice should fail", async () => {
       await expect(
           withBlockhashRetry(() =>
             program.methods
-          .addPauser(pauser.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
+          .grantAccountRole(pauser.publicKey, { pauser: {} })
+          .accounts({ payer: admin.publicKey })
           .signers([admin])
           .rpc({ commitment: "confirmed" })
           )
-        ).to.be.rejectedWith("PauserExists");
+        ).to.be.rejectedWith("AccountRoleAlreadyGranted");
     });
 
     //MintFee is a fee that charged for autoclaim and transfered to treasury
@@ -321,14 +323,51 @@ describe("LBTC", () => {
       const mintFee = new BN(10);
       const tx2 = await withBlockhashRetry(() =>
         program.methods
-        .setMintFee(mintFee)
+        .setMintFee({ flat: { amount: mintFee } })
         .accounts({ payer: operator.publicKey, config: configPDA })
         .signers([operator])
         .rpc({ commitment: "confirmed" })
       );
 
       let cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.mintFee.toBigInt()).to.be.eq(mintFee.toBigInt());
+      expect(cfg.mintFee.flat.amount.toBigInt()).to.be.eq(mintFee.toBigInt());
+    });
+
+    it("setMintFee: rejects basis points above the bounds", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            program.methods
+          .setMintFee({ basisPoints: { bps: 101 } })
+          .accounts({ payer: operator.publicKey, config: configPDA })
+          .signers([operator])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("FeeTooHigh");
+    });
+
+    it("setFeeBounds: successful by admin", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+        .setFeeBounds({ maxFlatFee: new BN(100_000), maxFeeBps: 200 })
+        .accounts({ payer: admin.publicKey, config: configPDA })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      const cfg = await program.account.config.fetch(configPDA);
+      expect(cfg.feeBounds.maxFeeBps).to.be.eq(200);
+    });
+
+    it("setFeeBounds: rejects bounds below the configured fees", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            program.methods
+          .setFeeBounds({ maxFlatFee: new BN(5), maxFeeBps: 200 })
+          .accounts({ payer: admin.publicKey, config: configPDA })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("FeeTooHigh");
     });
 
     //BurnCommission is a fee that charged at redeem
@@ -336,14 +375,14 @@ describe("LBTC", () => {
       const burnCommission = new BN(10);
       await withBlockhashRetry(() =>
         program.methods
-        .setBurnCommission(burnCommission)
+        .setBurnCommission({ flat: { amount: burnCommission } })
         .accounts({ payer: admin.publicKey, config: configPDA })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
       );
 
       const cfg = await program.account.config.fetch(configPDA);
-      expect(cfg.burnCommission.eq(burnCommission));
+      expect(cfg.burnCommission.flat.amount.eq(burnCommission));
     });
 
     it("setDustFeeRate: successful by admin", async () => {
@@ -476,7 +515,7 @@ describe("LBTC", () => {
             .signers([payer])
             .rpc({ commitment: "confirmed" })
             )
-          ).to.be.rejectedWith("Unauthorized function call");
+          ).to.be.rejectedWith("AccountNotInitialized");
       });
 
       it("pause: successful by pauser", async () => {
@@ -528,9 +567,7 @@ describe("LBTC", () => {
             .signers([admin])
             .rpc({ commitment: "confirmed" })
             )
-          ).to.be.rejectedWith(
-          "Error Code: Unauthorized. Error Number: 6000. Error Message: Unauthorized function call."
-        );
+          ).to.be.rejectedWith("AccountNotInitialized");
       });
 
       it("unpause: successful by pauser", async () => {
@@ -687,7 +724,7 @@ describe("LBTC", () => {
         await expect(
             withBlockhashRetry(() =>
               program.methods
-            .setMintFee(new BN(10))
+            .setMintFee({ flat: { amount: new BN(10) } })
             .accounts({ payer: payer.publicKey, config: configPDA })
             .signers([payer])
             .rpc({ commitment: "confirmed" })
@@ -699,7 +736,7 @@ describe("LBTC", () => {
         await expect(
             withBlockhashRetry(() =>
               program.methods
-            .setBurnCommission(new BN(10))
+            .setBurnCommission({ flat: { amount: new BN(10) } })
             .accounts({ payer: payer.publicKey, config: configPDA })
             .signers([payer])
             .rpc({ commitment: "confirmed" })
@@ -731,24 +768,12 @@ describe("LBTC", () => {
           ).to.be.rejectedWith("An address constraint was violated");
       });
 
-      it("addClaimer: rejects when called by not admin", async () => {
-        await expect(
-            withBlockhashRetry(() =>
-              program.methods
-            .addClaimer(payer.publicKey)
-            .accounts({ payer: payer.publicKey, config: configPDA })
-            .signers([payer])
-            .rpc({ commitment: "confirmed" })
-            )
-          ).to.be.rejectedWith("An address constraint was violated");
-      });
-
-      it("addPauser: rejects when called by not admin", async () => {
+      it("grantAccountRole: rejects when called by not admin", async () => {
         await expect(
             withBlockhashRetry(() =>
               program.methods
-            .addPauser(payer.publicKey)
-            .accounts({ payer: payer.publicKey, config: configPDA })
+            .grantAccountRole(payer.publicKey, { pauser: {} })
+            .accounts({ payer: payer.publicKey })
             .signers([payer])
             .rpc({ commitment: "confirmed" })
             )
@@ -773,83 +798,144 @@ describe("LBTC", () => {
             )
           ).to.be.rejectedWith("An address constraint was violated");
       });
-    });
 
-    describe("Remove roles", function () {
-      let newClaimer, newPauser;
+      it("updateMetadata: rejects when called by not admin", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .updateMetadata("Renamed LBTC", null, null)
+            .accounts({ payer: payer.publicKey, mint })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("Unauthorized");
+      });
 
-      before(async function () {
-        newClaimer = Keypair.generate();
-        await withBlockhashRetry(() =>
-          program.methods
-          .addClaimer(newClaimer.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
-          .signers([admin])
-          .rpc({ commitment: "confirmed" })
-        );
+      it("setMetadataUpdateAuthority: rejects when called by not admin", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .setMetadataUpdateAuthority(payer.publicKey)
+            .accounts({ payer: payer.publicKey, mint })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("Unauthorized");
+      });
 
-        newPauser = Keypair.generate();
-        await withBlockhashRetry(() =>
-          program.methods
-          .addPauser(newPauser.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
-          .signers([admin])
-          .rpc({ commitment: "confirmed" })
-        );
+      it("claimMetadataUpdateAuthority: rejects when called by not admin", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .claimMetadataUpdateAuthority()
+            .accounts({ payer: payer.publicKey, mint })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("Unauthorized");
+      });
 
-        const cfg = await program.account.config.fetch(configPDA);
-        expect(cfg.claimers[1].toBase58() == newClaimer.publicKey.toBase58());
-        expect(cfg.pausers[1].toBase58() == newPauser.publicKey.toBase58());
+      it("initializeTokenMetadata: rejects when called by not admin", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .initializeTokenMetadata("Lombard Staked BTC", "LBTC", "https://example.com/lbtc.json")
+            .accounts({ payer: payer.publicKey, mint })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("Unauthorized");
       });
 
-      it("removeClaimer: rejects when called by not admin", async () => {
+      it("updateTokenMetadataField: rejects when called by not admin", async () => {
         await expect(
             withBlockhashRetry(() =>
               program.methods
-            .removeClaimer(newClaimer.publicKey)
-            .accounts({ payer: payer.publicKey, config: configPDA })
+            .updateTokenMetadataField({ name: {} }, "Renamed LBTC")
+            .accounts({ payer: payer.publicKey, mint })
             .signers([payer])
             .rpc({ commitment: "confirmed" })
             )
-          ).to.be.rejectedWith("An address constraint was violated");
+          ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("setTokenMetadataUpdateAuthority: rejects when called by not admin", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .setTokenMetadataUpdateAuthority(payer.publicKey)
+            .accounts({ payer: payer.publicKey, mint })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("initializeTokenMetadata: rejects when the mint is not a Token-2022 mint", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .initializeTokenMetadata("Lombard Staked BTC", "LBTC", "https://example.com/lbtc.json")
+            .accounts({ payer: admin.publicKey, mint })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("ConstraintMintTokenProgram");
       });
+    });
+
+    describe("Remove roles", function () {
+      let newPauser;
 
-      it("removeClaimer: successfully by admin", async () => {
+      before(async function () {
+        newPauser = Keypair.generate();
         await withBlockhashRetry(() =>
           program.methods
-          .removeClaimer(newClaimer.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
+          .grantAccountRole(newPauser.publicKey, { pauser: {} })
+          .accounts({ payer: admin.publicKey })
           .signers([admin])
           .rpc({ commitment: "confirmed" })
         );
 
-        const cfg = await program.account.config.fetch(configPDA);
-        expect(cfg.claimers).to.has.length(1);
+        const roles = await program.account.accountRoles.fetch(accountRolesPDA(newPauser.publicKey));
+        expect(roles.roles).to.deep.eq([{ pauser: {} }]);
       });
 
-      it("removePauser: rejects when called by not admin", async () => {
+      it("revokeAccountRoles: rejects when called by not admin", async () => {
         await expect(
             withBlockhashRetry(() =>
               program.methods
-            .removePauser(newPauser.publicKey)
-            .accounts({ payer: payer.publicKey, config: configPDA })
+            .revokeAccountRoles(newPauser.publicKey)
+            .accounts({ payer: payer.publicKey })
             .signers([payer])
             .rpc({ commitment: "confirmed" })
             )
           ).to.be.rejectedWith("An address constraint was violated");
       });
 
-      it("removePauser: successfully by admin", async () => {
+      it("revokeAccountRoles: successfully by admin", async () => {
         await withBlockhashRetry(() =>
           program.methods
-          .removePauser(newPauser.publicKey)
-          .accounts({ payer: admin.publicKey, config: configPDA })
+          .revokeAccountRoles(newPauser.publicKey)
+          .accounts({ payer: admin.publicKey })
           .signers([admin])
           .rpc({ commitment: "confirmed" })
         );
 
-        const cfg = await program.account.config.fetch(configPDA);
-        expect(cfg.pausers).to.has.length(1);
+        const info = await provider.connection.getAccountInfo(accountRolesPDA(newPauser.publicKey));
+        expect(info).to.be.null;
+      });
+
+      it("pause: rejects after roles were revoked", async () => {
+        await expect(
+            withBlockhashRetry(() =>
+              program.methods
+            .pause()
+            .accounts({ payer: newPauser.publicKey, config: configPDA })
+            .signers([newPauser])
+            .rpc({ commitment: "confirmed" })
+            )
+          ).to.be.rejectedWith("AccountNotInitialized");
       });
     });
   });
@@ -1996,7 +2082,7 @@ describe("LBTC", () => {
       it("mintWithFee: rejects when amount < fee", async () => {
         await withBlockhashRetry(() =>
           program.methods
-          .setMintFee(new BN(100_000))
+          .setMintFee({ flat: { amount: new BN(100_000) } })
           .accounts({ payer: operator.publicKey, config: configPDA })
           .signers([operator])
           .rpc({ commitment: "confirmed" })
@@ -2116,7 +2202,7 @@ describe("LBTC", () => {
       it("mintWithFee: successful", async () => {
         await withBlockhashRetry(() =>
           program.methods
-          .setMintFee(new BN(10))
+          .setMintFee({ flat: { amount: new BN(10) } })
           .accounts({ payer: operator.publicKey, config: configPDA })
           .signers([operator])
           .rpc({ commitment: "confirmed" })
@@ -2155,7 +2241,9 @@ describe("LBTC", () => {
         let cfg = await program.account.config.fetch(configPDA);
         // Going to take the least fee value from permit and config
         const finalFees =
-          cfg.mintFee.toBigInt() < feePermit.maxFeesBigInt() ? cfg.mintFee.toBigInt() : feePermit.maxFeesBigInt();
+          cfg.mintFee.flat.amount.toBigInt() < feePermit.maxFeesBigInt()
+            ? cfg.mintFee.flat.amount.toBigInt()
+            : feePermit.maxFeesBigInt();
         expect(recipientBalanceAfter.amount - recipientBalanceBefore.amount).to.be.eq(
           mintPayload2.amountBigInt() - finalFees
         );
@@ -2460,7 +2548,7 @@ describe("LBTC", () => {
         const cfg = await program.account.config.fetch(configPDA);
 
         expect(userBalanceBefore.amount - userBalanceAfter.amount).to.be.eq(amount.toBigInt());
-        expect(treasuryBalanceAfter.amount - treasuryBalanceBefore.amount).to.be.eq(cfg.burnCommission.toBigInt());
+        expect(treasuryBalanceAfter.amount - treasuryBalanceBefore.amount).to.be.eq(cfg.burnCommission.flat.amount.toBigInt());
         expect(cfg.unstakeCounter.toBigInt()).to.be.eq(1n);
 
         const unstakeInfoPDA = PublicKey.findProgramAddressSync(
@@ -2470,7 +2558,7 @@ describe("LBTC", () => {
         const unstakeInfo = await program.account.unstakeInfo.fetch(unstakeInfoPDA);
         expect(unstakeInfo.from == recipient.publicKey);
         expect(unstakeInfo.scriptPubkey == scriptPubkey);
-        expect(unstakeInfo.amount == amount - cfg.burnCommission);
+        expect(unstakeInfo.amount == amount - cfg.burnCommission.flat.amount);
       });
     });
   });
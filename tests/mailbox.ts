@@ -1,7 +1,8 @@
 import "dotenv/config";
 import * as anchor from "@coral-xyz/anchor";
 import { BN, BorshCoder, EventManager, Program } from "@coral-xyz/anchor";
-import { Keypair, PublicKey, SystemProgram } from "@solana/web3.js";
+import { ComputeBudgetProgram, Keypair, PublicKey, sendAndConfirmTransaction, SystemProgram, Transaction } from "@solana/web3.js";
+import * as spl from "@solana/spl-token";
 import { Consortium } from "../target/types/consortium";
 import { Mailbox } from "../target/types/mailbox";
 import { sha256 } from "js-sha256";
@@ -10,7 +11,16 @@ import chaiAsPromised from "chai-as-promised";
 import { ConsortiumUtility, randomNumber } from "./utils/consortium_utilities";
 import { keccak256 } from "ethers";
 import { MailboxReceiver } from "../target/types/mailbox_receiver";
-import { MESSAGE_V1_SELECTOR, MessageV1 } from "./utils/mailbox_utilities";
+import {
+  ACK_REQUESTED,
+  getCpiEvents,
+  MESSAGE_V1_SELECTOR,
+  MESSAGE_V2_SELECTOR,
+  MESSAGE_V3_SELECTOR,
+  MessageV1,
+  MessageV2,
+  SEQUENCED
+} from "./utils/mailbox_utilities";
 import {
   BITCOIN_LCHAIN_ID,
   fundWallet,
@@ -412,6 +422,62 @@ describe("Mailbox", () => {
       expect(OutboundMsgPathEvents[0].enabled).to.be.true;
     });
 
+    it("migrateConfig rejects when the config is already migrated", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .migrateConfig()
+          .accounts({
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("AccountAlreadyMigrated");
+    });
+
+    it("migrateOutboundMessagePath rejects when called by not admin", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .migrateOutboundMessagePath(LEDGER_LCHAIN_ID_BZ)
+          .accounts({
+            admin: payer.publicKey,
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("migrateOutboundMessagePath rejects when the path is already migrated", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .migrateOutboundMessagePath(LEDGER_LCHAIN_ID_BZ)
+          .accounts({
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("AccountAlreadyMigrated");
+    });
+
+    it("migrateInboundMessagePath rejects when the path is already migrated", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .migrateInboundMessagePath(LEDGER_LCHAIN_ID_BZ)
+          .accounts({
+            admin: admin.publicKey,
+          })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("AccountAlreadyMigrated");
+    });
+
     it("disableInboundMessagePath rejects when called by not admin", async () => {
       await expect(
           withBlockhashRetry(() =>
@@ -484,7 +550,6 @@ describe("Mailbox", () => {
 
   describe("Deliver and handle message success flow", () => {
     const MsgDeliveredEvents = [];
-    const MsgHandledEvents = [];
     const listeners: number[] = [];
     const msgSender = Keypair.generate();
 
@@ -495,12 +560,6 @@ describe("Mailbox", () => {
           MsgDeliveredEvents.push(e);
         })
       );
-      listeners.push(
-        programEventManager.addEventListener("messageHandled", e => {
-          console.log(JSON.stringify(e));
-          MsgHandledEvents.push(e);
-        })
-      );
 
       await withBlockhashRetry(() =>
         mailbox.methods
@@ -525,7 +584,6 @@ describe("Mailbox", () => {
 
     afterEach(async function () {
       MsgDeliveredEvents.length = 0;
-      MsgHandledEvents.length = 0;
     });
 
     after(async function () {
@@ -612,6 +670,7 @@ describe("Mailbox", () => {
         expect(messageInfo.message.destinationCaller).to.be.deep.eq(dCaller.expectedDCallerAddress);
         expect(messageInfo.message.body).to.be.deep.eq(message.body);
         expect(messageInfo.status).to.deep.eq({ delivered: {} });
+        expect(messageInfo.deliverer.toBase58()).to.be.eq(payer.publicKey.toBase58());
 
         //Event
         expect(MsgDeliveredEvents[0]).to.be.not.undefined;
@@ -621,11 +680,15 @@ describe("Mailbox", () => {
       });
 
       it(`handleMessage when dCaller is ${dCaller.name}`, async () => {
-        await withBlockhashRetry(() =>
+        const messageInfoSizeBefore = (await provider.connection.getAccountInfo(messageInfoPDA)).data.length;
+        const delivererBalanceBefore = await provider.connection.getBalance(payer.publicKey);
+
+        const signature = await withBlockhashRetry(() =>
           mailbox.methods
           .handleMessage(payloadHashBytes)
           .accounts({
             handler: user.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: mailboxReceiver.programId
           })
           .remainingAccounts([
@@ -654,20 +717,29 @@ describe("Mailbox", () => {
           .rpc({ commitment: "confirmed" })
         );
 
-        const messageInfo = await mailbox.account.messageV1Info.fetch(messageInfoPDA);
-        expect(messageInfo.message.messagePathIdentifier).to.be.deep.eq(inboundMessagePathBytes);
-        expect(messageInfo.message.nonce.toNumber()).to.be.eq(message.nonce);
-        expect(messageInfo.message.sender).to.be.deep.eq(Array.from(message.sender));
-        expect(messageInfo.message.recipient).to.be.deep.eq(Array.from(message.recipient));
-        expect(messageInfo.message.destinationCaller).to.be.deep.eq(dCaller.expectedDCallerAddress);
-        expect(messageInfo.message.body).to.be.deep.eq(message.body);
-        expect(messageInfo.status).to.deep.eq({ handled: {} });
-
-        expect(await mailboxReceiver.account.messageHandled.fetch(receiverMessageHandledPDA)).to.be.deep.eq({});
-
-        //Event
-        expect(MsgHandledEvents[0]).to.be.not.undefined;
-        expect(MsgHandledEvents[0].payloadHash).to.be.deep.eq(payloadHashBytes);
+        // the message info is compacted to the status and payload hash
+        const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(messageInfoPDA);
+        expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+        expect(handledMessageInfo.payloadHash).to.be.deep.eq(payloadHashBytes);
+        const messageInfoAccount = await provider.connection.getAccountInfo(messageInfoPDA);
+        expect(messageInfoAccount.data.length).to.be.eq(8 + 1 + 32);
+        expect(messageInfoAccount.data.length).to.be.lt(messageInfoSizeBefore);
+
+        // the freed rent went back to the deliverer
+        expect(await provider.connection.getBalance(payer.publicKey)).to.be.gt(delivererBalanceBefore);
+
+        // the receiver stored the message
+        const messageHandled = await mailboxReceiver.account.messageHandled.fetch(receiverMessageHandledPDA);
+        expect(messageHandled.sender).to.be.deep.eq(Array.from(msgSender.publicKey.toBuffer()));
+        expect(messageHandled.body).to.be.deep.eq(Buffer.from("test"));
+        expect(messageHandled.echoed).to.be.false;
+
+        //Event, emitted through self-CPI
+        const handled = (await getCpiEvents(mailbox, signature)).find(e => e.name === "messageHandled");
+        expect(handled).to.be.not.undefined;
+        expect(handled.data.payloadHash).to.be.deep.eq(payloadHashBytes);
+        expect(handled.data.handler.toBase58()).to.be.eq(user.publicKey.toBase58());
+        expect(handled.data.recipient).to.be.deep.eq(Array.from(mailboxReceiver.programId.toBuffer()));
       });
 
       it("handleMessage rejects when already handled", async () => {
@@ -677,6 +749,7 @@ describe("Mailbox", () => {
             .handleMessage(payloadHashBytes)
             .accounts({
               handler: user.publicKey,
+              deliverer: payer.publicKey,
               recipientProgram: mailboxReceiver.programId
             })
             .remainingAccounts([
@@ -706,6 +779,27 @@ describe("Mailbox", () => {
             )
           ).to.be.rejectedWith("InvalidPayloadState");
       });
+
+      it("deliverMessage rejects when already handled", async () => {
+        const validatedPayloadPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("validated_payload"), payloadHash],
+          consortium.programId
+        )[0];
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .deliverMessage(payloadHashBytes)
+              .accounts({
+                deliverer: payer.publicKey,
+                inboundMessagePath: inboundMessagePathPDA,
+                consortiumPayload: sessionPayloadPDA,
+                consortiumValidatedPayload: validatedPayloadPDA
+              })
+              .signers([payer])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("already in use");
+      });
     });
   });
 
@@ -1068,6 +1162,7 @@ describe("Mailbox", () => {
           .handleMessage(message.toHashBytes())
           .accounts({
             handler: user.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: mailboxReceiver.programId
           })
           .remainingAccounts([
@@ -1139,6 +1234,7 @@ describe("Mailbox", () => {
           .handleMessage(message.toHashBytes())
           .accounts({
             handler: payer.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: assetRouter.programId
           })
           .remainingAccounts([
@@ -1212,6 +1308,7 @@ describe("Mailbox", () => {
           .handleMessage(message.toHashBytes())
           .accounts({
             handler: user.publicKey,
+            deliverer: payer.publicKey,
             recipientProgram: mailboxReceiver.programId
           })
           .remainingAccounts([
@@ -1243,249 +1340,2037 @@ describe("Mailbox", () => {
     });
   });
 
-  describe("Sender config", () => {
-    const sender = Keypair.generate();
-    const senderConfigPDA = PublicKey.findProgramAddressSync(
-      [Buffer.from("sender_config"), sender.publicKey.toBuffer()],
-      mailbox.programId
-    )[0];
-
-    it("setSenderConfig rejects when called by not admin", async () => {
-      const maxPayloadSize = randomNumber(3);
-
-      await expect(
-          withBlockhashRetry(() =>
-            mailbox.methods
-          .setSenderConfig(sender.publicKey, maxPayloadSize, true)
-          .accounts({
-            admin: payer.publicKey
-          })
-          .signers([payer])
-          .rpc({ commitment: "confirmed" })
-          )
-        ).to.be.rejectedWith("Unauthorized");
-    });
-
-    it("setSenderConfig successful by admin", async () => {
-      const maxPayloadSize = randomNumber(3);
+  describe("Ordered inbound path", () => {
+    let first: MessageV2;
+    let second: MessageV2;
+    let third: MessageV2;
+    let unsequenced: MessageV1;
 
+    const deliver = async (message: MessageV1) => {
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
       await withBlockhashRetry(() =>
-        mailbox.methods
-        .setSenderConfig(sender.publicKey, maxPayloadSize, true)
+        consortium.methods
+        .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
         .accounts({
-          admin: admin.publicKey
+          payer: payer.publicKey,
+          sessionPayload: message.sessionPayloadPDA(payer)
         })
-        .signers([admin])
+        .signers([payer])
         .rpc({ commitment: "confirmed" })
       );
-
-      const senderConfigAccount = await mailbox.account.senderConfig.fetch(senderConfigPDA);
-      expect(senderConfigAccount.maxPayloadSize).to.be.eq(maxPayloadSize);
-      expect(senderConfigAccount.feeDisabled).to.be.eq(true);
-    });
-
-    it("setSenderConfig update successful by admin", async () => {
-      const maxPayloadSize = randomNumber(3);
-
       await withBlockhashRetry(() =>
         mailbox.methods
-        .setSenderConfig(sender.publicKey, maxPayloadSize, false)
+        .deliverMessage(message.toHashBytes())
         .accounts({
-          admin: admin.publicKey
+          deliverer: payer.publicKey,
+          inboundMessagePath: inboundMessagePathPDA,
+          consortiumPayload: message.sessionPayloadPDA(payer),
+          consortiumValidatedPayload: validatedPayloadPDA
         })
-        .signers([admin])
+        .signers([payer])
         .rpc({ commitment: "confirmed" })
       );
+    };
 
-      const senderConfigAccount = await mailbox.account.senderConfig.fetch(senderConfigPDA);
-      expect(senderConfigAccount.maxPayloadSize).to.be.eq(maxPayloadSize);
-      expect(senderConfigAccount.feeDisabled).to.be.eq(false);
-    });
-
-    it("setSenderConfig rejects when called by not admin", async () => {
-      await expect(
-          withBlockhashRetry(() =>
-            mailbox.methods
-          .unsetSenderConfig(sender.publicKey)
-          .accounts({
-            admin: payer.publicKey
-          })
-          .signers([payer])
-          .rpc({ commitment: "confirmed" })
-          )
-        ).to.be.rejectedWith("Unauthorized");
-    });
-
-    it("unsetSenderConfig successful by admin", async () => {
-      await withBlockhashRetry(() =>
+    const handle = (message: MessageV1, path: PublicKey | null) =>
+      withBlockhashRetry(() =>
         mailbox.methods
-        .unsetSenderConfig(sender.publicKey)
-        .accounts({
-          admin: admin.publicKey
+        .handleMessage(message.toHashBytes())
+        .accountsPartial({
+          handler: user.publicKey,
+          deliverer: payer.publicKey,
+          recipientProgram: mailboxReceiver.programId,
+          inboundMessagePath: path
         })
-        .signers([admin])
+        .remainingAccounts([
+          {
+            pubkey: user.publicKey,
+            isWritable: true,
+            isSigner: true
+          },
+          {
+            pubkey: message.receiverConfigPDA(),
+            isWritable: false,
+            isSigner: false
+          },
+          {
+            pubkey: message.receiverMessageHandledPDA(),
+            isWritable: true,
+            isSigner: false
+          },
+          {
+            pubkey: SystemProgram.programId,
+            isWritable: false,
+            isSigner: false
+          }
+        ])
+        .signers([user])
         .rpc({ commitment: "confirmed" })
       );
 
-      expect(await provider.connection.getAccountInfo(senderConfigPDA)).to.be.null;
-    });
-  });
-
-  describe("Send message", () => {
-    const customMaxPayloadSize = defaultMaxPayloadSize + 10;
-    const MsgSentEvents = [];
-    const listeners: number[] = [];
-
-    before("Enable outbound message path", async () => {
-      listeners.push(
-        programEventManager.addEventListener("messageSent", e => {
-          console.log(JSON.stringify(e));
-          MsgSentEvents.push(e);
-        })
-      );
-
-      await withBlockhashRetry(() =>
+    const setOrdering = (signer: Keypair, ordered: boolean, nextSequence: number) =>
+      withBlockhashRetry(() =>
         mailbox.methods
-        .enableOutboundMessagePath(LEDGER_LCHAIN_ID_BZ)
+        .setInboundPathOrdering(LEDGER_LCHAIN_ID_BZ, ordered, new BN(nextSequence))
         .accounts({
-          admin: admin.publicKey,
+          admin: signer.publicKey
         })
-        .signers([admin])
+        .signers([signer])
         .rpc({ commitment: "confirmed" })
       );
 
-      await withBlockhashRetry(() =>
+    const cancel = (message: MessageV1) =>
+      withBlockhashRetry(() =>
         mailbox.methods
-        .setSenderConfig(payerFeeExempt.publicKey, customMaxPayloadSize, true)
+        .cancelMessage(message.toHashBytes(), "skipped")
         .accounts({
-          admin: admin.publicKey
+          canceller: admin.publicKey,
+          deliverer: payer.publicKey,
+          inboundMessagePath: inboundMessagePathPDA
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
       );
+
+    const nextSequence = async () =>
+      (await mailbox.account.inboundMessagePath.fetch(inboundMessagePathPDA)).nextSequence.toNumber();
+
+    const sequenced = (body: string, sequence: number) =>
+      new MessageV2(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from(body),
+        ZERO_BUFFER32,
+        SEQUENCED,
+        0,
+        0,
+        ZERO_BUFFER32,
+        sequence
+      );
+
+    before(async () => {
+      first = sequenced("first", 0);
+      second = sequenced("second", 1);
+      third = sequenced("third", 2);
+      unsequenced = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("unsequenced"),
+        ZERO_BUFFER32,
+        MESSAGE_V1_SELECTOR
+      );
     });
 
-    afterEach(async function () {
-      MsgSentEvents.length = 0;
+    after(async () => {
+      await setOrdering(admin, false, 0);
     });
 
-    after(async function () {
-      for (const l of listeners) {
-        await programEventManager.removeEventListener(l);
-      }
+    it("setInboundPathOrdering rejects when called by not admin", async () => {
+      await expect(setOrdering(t, true, 0)).to.be.rejectedWith("Unauthorized");
     });
 
-    it("send message by paying GMP fee", async () => {
-      let config = await mailbox.account.config.fetch(configPDA);
-      let body = Buffer.from("some body to send", "utf8");
-      let recipient = Buffer.from(sha256("recipient"), "hex");
-      let recipientBz = Array.from(Uint8Array.from(recipient));
-      let destinationCaller = Buffer.from(sha256("destinationCaller"), "hex");
-      let destinationCallerBz = Array.from(Uint8Array.from(destinationCaller));
-      const outboundMessagePDA = PublicKey.findProgramAddressSync(
-        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
-        mailbox.programId
-      )[0];
-      console.log("global nonce:", config.globalNonce.toNumber());
+    it("setInboundPathOrdering successful by admin", async () => {
+      await setOrdering(admin, true, 0);
 
-      const balanceBefore = await provider.connection.getBalance(payer.publicKey);
-      const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+      const path = await mailbox.account.inboundMessagePath.fetch(inboundMessagePathPDA);
+      expect(path.ordered).to.be.true;
+      expect(path.nextSequence.toNumber()).to.be.eq(0);
+    });
 
-      await withBlockhashRetry(() =>
-        mailbox.methods
-        .sendMessage(body, recipientBz, destinationCallerBz, new BN(0))
-        .accountsPartial({
-          feePayer: payer.publicKey,
-          senderAuthority: payer.publicKey,
-          outboundMessage: outboundMessagePDA,
-          outboundMessagePath: outboundMessagePathPDA,
-          treasury: treasury.publicKey,
-          senderConfig: null
-        })
-        .signers([payer])
-        .rpc({ commitment: "confirmed" })
-      );
+    it("deliverMessage records the ordering of sequenced messages", async () => {
+      await deliver(third);
+      await deliver(second);
+      await deliver(first);
+      await deliver(unsequenced);
 
-      const outboundMessageAccount = await provider.connection.getAccountInfo(outboundMessagePDA);
-      expect(outboundMessageAccount).to.be.not.null;
+      expect((await mailbox.account.messageV1Info.fetch(second.messageInfoPDA())).ordered).to.be.true;
+      expect((await mailbox.account.messageV1Info.fetch(unsequenced.messageInfoPDA())).ordered).to.be.false;
+    });
 
-      const configAfter = await mailbox.account.config.fetch(configPDA);
-      expect(configAfter.globalNonce.toNumber()).to.eq(config.globalNonce.toNumber() + 1);
+    it("handleMessage rejects when inbound message path is missing", async () => {
+      await expect(handle(first, null)).to.be.rejectedWith("MissingInboundMessagePath");
+    });
 
-      // 260 is the size of the gmp message in bytes assuming body is less than 32 bytes
-      const fee = feePerByte.muln(260);
+    it("handleMessage rejects a message ahead of the next sequence", async () => {
+      // anyone can handle a message without destination caller, yet not ahead of the ones before it
+      await expect(handle(second, inboundMessagePathPDA)).to.be.rejectedWith("MessageOutOfOrder");
+    });
 
-      const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
-      expect(treasuryBalanceAfter).to.be.eq(treasuryBalanceBefore + fee.toNumber(), "treasury balance mismatch");
+    it("handleMessage successful in order", async () => {
+      await handle(first, inboundMessagePathPDA);
 
-      const balanceAfter = await provider.connection.getBalance(payer.publicKey);
-      // gmp fee + instruction fee should have been deducted, and assume gmp fee is greater than gas + instr fee
-      expect(balanceAfter).to.be.lt(balanceBefore - fee.toNumber(), "fee not paid");
+      expect(await nextSequence()).to.be.eq(1);
     });
 
-    it("send message exempt from GMP fee", async () => {
-      let config = await mailbox.account.config.fetch(configPDA);
-      let body = Buffer.from("some body to send", "utf8");
-      let recipient = Buffer.from(sha256("recipient"), "hex");
-      let recipientBz = Array.from(Uint8Array.from(recipient));
-      let destinationCaller = Buffer.from(sha256("destinationCaller"), "hex");
-      let destinationCallerBz = Array.from(Uint8Array.from(destinationCaller));
-      const outboundMessagePDA = PublicKey.findProgramAddressSync(
-        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
-        mailbox.programId
-      )[0];
-      console.log("global nonce:", config.globalNonce.toNumber());
+    it("cancelMessage rejects a message ahead of the next sequence", async () => {
+      await expect(cancel(third)).to.be.rejectedWith("MessageOutOfOrder");
+    });
 
-      // we use system program just for ease of testing
-      await withBlockhashRetry(() =>
-        mailbox.methods
-        .setSenderConfig(payerFeeExempt.publicKey, customMaxPayloadSize, true)
-        .accounts({
-          admin: admin.publicKey
+    it("cancelMessage of the next message moves the path past it", async () => {
+      await cancel(second);
+
+      expect(await nextSequence()).to.be.eq(2);
+    });
+
+    it("handleMessage successful after a cancelled message", async () => {
+      await handle(third, inboundMessagePathPDA);
+
+      expect(await nextSequence()).to.be.eq(3);
+    });
+
+    it("handleMessage of an unsequenced message keeps the next sequence", async () => {
+      await handle(unsequenced, null);
+
+      expect(await nextSequence()).to.be.eq(3);
+    });
+  });
+
+  describe("Deliver and handle in one instruction", () => {
+    const newMessage = (destinationCaller: Buffer) =>
+      new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("atomic"),
+        destinationCaller,
+        MESSAGE_V1_SELECTOR
+      );
+
+    const deliverAndHandle = async (message: MessageV1) => {
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+      await withBlockhashRetry(() =>
+        consortium.methods
+        .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+        .accounts({
+          payer: payer.publicKey,
+          sessionPayload: message.sessionPayloadPDA(payer)
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" })
+      );
+      return withBlockhashRetry(() =>
+        mailbox.methods
+        .deliverAndHandleMessage(message.toHashBytes())
+        .accounts({
+          deliverer: payer.publicKey,
+          recipientProgram: mailboxReceiver.programId,
+          inboundMessagePath: inboundMessagePathPDA,
+          consortiumPayload: message.sessionPayloadPDA(payer),
+          consortiumValidatedPayload: validatedPayloadPDA
+        })
+        .remainingAccounts([
+          {
+            pubkey: payer.publicKey,
+            isWritable: true,
+            isSigner: true
+          },
+          {
+            pubkey: message.receiverConfigPDA(),
+            isWritable: false,
+            isSigner: false
+          },
+          {
+            pubkey: message.receiverMessageHandledPDA(),
+            isWritable: true,
+            isSigner: false
+          },
+          {
+            pubkey: SystemProgram.programId,
+            isWritable: false,
+            isSigner: false
+          }
+        ])
+        .signers([payer])
+        .rpc({ commitment: "confirmed" })
+      );
+    };
+
+    it("deliverAndHandleMessage successful", async () => {
+      const message = newMessage(payer.publicKey.toBuffer());
+      await deliverAndHandle(message);
+
+      const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+      expect(handledMessageInfo.payloadHash).to.be.deep.eq(message.toHashBytes());
+      const messageHandled = await mailboxReceiver.account.messageHandled.fetch(message.receiverMessageHandledPDA());
+      expect(messageHandled.body).to.be.deep.eq(Buffer.from("atomic"));
+
+      const validatedPayloadPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("validated_payload"), message.toHash()],
+        consortium.programId
+      )[0];
+      await expect(
+        withBlockhashRetry(() =>
+          mailbox.methods
+          .deliverMessage(message.toHashBytes())
+          .accounts({
+            deliverer: payer.publicKey,
+            inboundMessagePath: inboundMessagePathPDA,
+            consortiumPayload: message.sessionPayloadPDA(payer),
+            consortiumValidatedPayload: validatedPayloadPDA
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("already in use");
+    });
+
+    it("deliverAndHandleMessage successful when the message info account is prefunded", async () => {
+      const message = newMessage(payer.publicKey.toBuffer());
+      // anyone can send lamports to the predictable message info PDA ahead of the delivery
+      await sendAndConfirmTransaction(
+        provider.connection,
+        new Transaction().add(
+          SystemProgram.transfer({
+            fromPubkey: user.publicKey,
+            toPubkey: message.messageInfoPDA(),
+            lamports: 1_000_000
+          })
+        ),
+        [user],
+        { commitment: "confirmed" }
+      );
+      await deliverAndHandle(message);
+
+      const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+    });
+
+    it("deliverAndHandleMessage rejects when dCaller is invalid", async () => {
+      await expect(deliverAndHandle(newMessage(user.publicKey.toBuffer()))).to.be.rejectedWith(
+        "InvalidDestinationCaller"
+      );
+    });
+  });
+
+  describe("Executor allowlists", () => {
+    const executorAllowlistPDA = PublicKey.findProgramAddressSync(
+      [Buffer.from("executor_allowlist"), mailboxReceiver.programId.toBuffer()],
+      mailbox.programId
+    )[0];
+
+    const setAllowlist = (executors: PublicKey[], fallbackDelay: number) =>
+      withBlockhashRetry(() =>
+        mailboxReceiver.methods
+          .setExecutorAllowlist(executors, new BN(fallbackDelay))
+          .accounts({
+            deployer: provider.wallet.publicKey,
+            mailbox: mailbox.programId,
+            executorAllowlist: executorAllowlistPDA
+          })
+          .signers([Keypair.fromSecretKey(provider.wallet.payer.secretKey)])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    // a message designating the allowlist of the recipient as destination caller, by default
+    const deliverDesignated = async (destinationCaller: Buffer = executorAllowlistPDA.toBuffer()) => {
+      const message = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("designated"),
+        destinationCaller
+      );
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+      await withBlockhashRetry(() =>
+        consortium.methods
+          .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+          .accounts({
+            payer: payer.publicKey,
+            sessionPayload: message.sessionPayloadPDA(payer)
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+      await withBlockhashRetry(() =>
+        mailbox.methods
+          .deliverMessage(message.toHashBytes())
+          .accounts({
+            deliverer: payer.publicKey,
+            inboundMessagePath: inboundMessagePathPDA,
+            consortiumPayload: message.sessionPayloadPDA(payer),
+            consortiumValidatedPayload: validatedPayloadPDA
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+      return message;
+    };
+
+    const handle = (message: MessageV1, handler: Keypair) =>
+      withBlockhashRetry(() =>
+        mailbox.methods
+          .handleMessage(message.toHashBytes())
+          .accounts({
+            handler: handler.publicKey,
+            deliverer: payer.publicKey,
+            recipientProgram: mailboxReceiver.programId,
+            executorAllowlist: executorAllowlistPDA
+          })
+          .remainingAccounts([
+            { pubkey: handler.publicKey, isWritable: true, isSigner: true },
+            { pubkey: message.receiverConfigPDA(), isWritable: false, isSigner: false },
+            { pubkey: message.receiverMessageHandledPDA(), isWritable: true, isSigner: false },
+            { pubkey: SystemProgram.programId, isWritable: false, isSigner: false }
+          ])
+          .signers([handler])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    it("setExecutorAllowlist rejects an authority not acting for the recipient", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          mailbox.methods
+            .setExecutorAllowlist(mailboxReceiver.programId, [user.publicKey], new BN(0))
+            .accounts({ payer: user.publicKey, recipientAuthority: user.publicKey })
+            .signers([user])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("setExecutorAllowlist registers the executors of the recipient", async () => {
+      await setAllowlist([user.publicKey], 0);
+
+      const allowlist = await mailbox.account.executorAllowlist.fetch(executorAllowlistPDA);
+      expect(allowlist.recipient.toBase58()).to.be.eq(mailboxReceiver.programId.toBase58());
+      expect(allowlist.executors.map(e => e.toBase58())).to.be.deep.eq([user.publicKey.toBase58()]);
+      expect(allowlist.fallbackDelay.toNumber()).to.be.eq(0);
+    });
+
+    it("handleMessage rejects an executor not in the allowlist", async () => {
+      const message = await deliverDesignated();
+      await expect(handle(message, t)).to.be.rejectedWith("InvalidDestinationCaller");
+    });
+
+    it("handleMessage accepts an executor in the allowlist", async () => {
+      const message = await deliverDesignated();
+      await handle(message, user);
+      const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+    });
+
+    it("handleMessage accepts anyone once the fallback delay has passed", async () => {
+      await setAllowlist([user.publicKey], 1);
+      const message = await deliverDesignated();
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await handle(message, t);
+      const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+    });
+
+    it("handleMessage accepts anyone once the fallback delay has passed for a single designated caller", async () => {
+      const message = await deliverDesignated(Keypair.generate().publicKey.toBuffer());
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await handle(message, t);
+      const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+    });
+  });
+
+  describe("Cancel message", () => {
+    const deliver = async (recipient: Buffer = mailboxReceiver.programId.toBuffer()) => {
+      const message = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        recipient,
+        Buffer.from("stuck")
+      );
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+      await withBlockhashRetry(() =>
+        consortium.methods
+          .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+          .accounts({
+            payer: payer.publicKey,
+            sessionPayload: message.sessionPayloadPDA(payer)
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+      await withBlockhashRetry(() =>
+        mailbox.methods
+          .deliverMessage(message.toHashBytes())
+          .accounts({
+            deliverer: payer.publicKey,
+            inboundMessagePath: inboundMessagePathPDA,
+            consortiumPayload: message.sessionPayloadPDA(payer),
+            consortiumValidatedPayload: validatedPayloadPDA
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+      return message;
+    };
+
+    const cancel = (message: MessageV1, canceller: Keypair) =>
+      withBlockhashRetry(() =>
+        mailbox.methods
+          .cancelMessage(message.toHashBytes(), "recipient deprecated")
+          .accounts({
+            canceller: canceller.publicKey,
+            deliverer: payer.publicKey
+          })
+          .signers([canceller])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    const setExpiry = (expiry: number) =>
+      withBlockhashRetry(() =>
+        mailbox.methods
+          .setDeliveredMessageExpiry(new BN(expiry))
+          .accounts({ admin: admin.publicKey })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+
+    after(async () => {
+      await setExpiry(0);
+    });
+
+    it("cancelMessage rejects when called by not admin before expiry", async () => {
+      const message = await deliver();
+      await expect(cancel(message, t)).to.be.rejectedWith("MessageNotCancellable");
+    });
+
+    it("cancelMessage successful by admin", async () => {
+      const message = await deliver();
+      const delivererBalanceBefore = await provider.connection.getBalance(payer.publicKey);
+      await cancel(message, admin);
+
+      const cancelledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(cancelledMessageInfo.status).to.deep.eq({ cancelled: {} });
+      expect(await provider.connection.getBalance(payer.publicKey)).to.be.gt(delivererBalanceBefore);
+
+      await expect(cancel(message, admin)).to.be.rejectedWith("InvalidPayloadState");
+      const status = await mailbox.methods.getMessageStatus(message.toHashBytes()).view({ commitment: "confirmed" });
+      expect(status).to.be.deep.eq({ cancelled: {} });
+    });
+
+    it("cancelMessage successful by a wallet recipient", async () => {
+      const message = await deliver(t.publicKey.toBuffer());
+      await cancel(message, t);
+
+      const cancelledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(cancelledMessageInfo.status).to.deep.eq({ cancelled: {} });
+    });
+
+    it("setDeliveredMessageExpiry rejects when called by not admin", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          mailbox.methods
+            .setDeliveredMessageExpiry(new BN(1))
+            .accounts({ admin: t.publicKey })
+            .signers([t])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("cancelMessage successful by anyone once expired", async () => {
+      await setExpiry(1);
+      const message = await deliver();
+      await new Promise(resolve => setTimeout(resolve, 3000));
+      await cancel(message, t);
+
+      const cancelledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+      expect(cancelledMessageInfo.status).to.deep.eq({ cancelled: {} });
+    });
+  });
+
+  describe("Inbound path verification", () => {
+    const secondaryVerifier = assetRouter.programId;
+
+    const setPolicy = (signer: Keypair, policy: any) =>
+      withBlockhashRetry(() =>
+        mailbox.methods
+        .setInboundPathVerification(LEDGER_LCHAIN_ID_BZ, policy)
+        .accounts({
+          admin: signer.publicKey
+        })
+        .signers([signer])
+        .rpc({ commitment: "confirmed" })
+      );
+
+    after(async () => {
+      await setPolicy(admin, { consortium: {} });
+    });
+
+    it("setInboundPathVerification rejects when called by not admin", async () => {
+      await expect(setPolicy(t, { consortium: {} })).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("setInboundPathVerification rejects unreachable threshold", async () => {
+      await expect(
+        setPolicy(admin, { threshold: { verifiers: [consortium.programId], threshold: 2 } })
+      ).to.be.rejectedWith("InvalidVerificationPolicy");
+    });
+
+    it("deliverMessage rejects without secondary attestation", async () => {
+      await setPolicy(admin, { consortiumAndSecondary: { verifier: secondaryVerifier } });
+      const path = await mailbox.account.inboundMessagePath.fetch(inboundMessagePathPDA);
+      expect(path.verificationPolicy.consortiumAndSecondary.verifier.toBase58()).to.be.eq(
+        secondaryVerifier.toBase58()
+      );
+
+      const message = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("verified"),
+        ZERO_BUFFER32,
+        MESSAGE_V1_SELECTOR
+      );
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+      await withBlockhashRetry(() =>
+        consortium.methods
+        .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+        .accounts({
+          payer: payer.publicKey,
+          sessionPayload: message.sessionPayloadPDA(payer)
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" })
+      );
+      const attestationPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("validated_payload"), message.toHash()],
+        secondaryVerifier
+      )[0];
+
+      await expect(
+        withBlockhashRetry(() =>
+          mailbox.methods
+          .deliverMessage(message.toHashBytes())
+          .accounts({
+            deliverer: payer.publicKey,
+            inboundMessagePath: inboundMessagePathPDA,
+            consortiumPayload: message.sessionPayloadPDA(payer),
+            consortiumValidatedPayload: validatedPayloadPDA
+          })
+          .remainingAccounts([
+            {
+              pubkey: attestationPDA,
+              isWritable: false,
+              isSigner: false
+            }
+          ])
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("PayloadNotVerified");
+    });
+  });
+
+  describe("Sender config", () => {
+    const sender = Keypair.generate();
+    const senderConfigPDA = PublicKey.findProgramAddressSync(
+      [Buffer.from("sender_config"), sender.publicKey.toBuffer()],
+      mailbox.programId
+    )[0];
+
+    it("setSenderConfig rejects when called by not admin", async () => {
+      const maxPayloadSize = randomNumber(3);
+
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .setSenderConfig(sender.publicKey, maxPayloadSize, true)
+          .accounts({
+            admin: payer.publicKey
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("setSenderConfig successful by admin", async () => {
+      const maxPayloadSize = randomNumber(3);
+
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .setSenderConfig(sender.publicKey, maxPayloadSize, true)
+        .accounts({
+          admin: admin.publicKey
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      const senderConfigAccount = await mailbox.account.senderConfig.fetch(senderConfigPDA);
+      expect(senderConfigAccount.maxPayloadSize).to.be.eq(maxPayloadSize);
+      expect(senderConfigAccount.feeDisabled).to.be.eq(true);
+    });
+
+    it("setSenderConfig update successful by admin", async () => {
+      const maxPayloadSize = randomNumber(3);
+
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .setSenderConfig(sender.publicKey, maxPayloadSize, false)
+        .accounts({
+          admin: admin.publicKey
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      const senderConfigAccount = await mailbox.account.senderConfig.fetch(senderConfigPDA);
+      expect(senderConfigAccount.maxPayloadSize).to.be.eq(maxPayloadSize);
+      expect(senderConfigAccount.feeDisabled).to.be.eq(false);
+    });
+
+    it("setSenderConfig rejects when called by not admin", async () => {
+      await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+          .unsetSenderConfig(sender.publicKey)
+          .accounts({
+            admin: payer.publicKey
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+    });
+
+    it("unsetSenderConfig successful by admin", async () => {
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .unsetSenderConfig(sender.publicKey)
+        .accounts({
+          admin: admin.publicKey
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      expect(await provider.connection.getAccountInfo(senderConfigPDA)).to.be.null;
+    });
+  });
+
+  describe("Send message", () => {
+    const customMaxPayloadSize = defaultMaxPayloadSize + 10;
+
+    before("Enable outbound message path", async () => {
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .enableOutboundMessagePath(LEDGER_LCHAIN_ID_BZ)
+        .accounts({
+          admin: admin.publicKey,
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .setSenderConfig(payerFeeExempt.publicKey, customMaxPayloadSize, true)
+        .accounts({
+          admin: admin.publicKey
+        })
+        .signers([admin])
+        .rpc({ commitment: "confirmed" })
+      );
+    });
+
+
+    it("send message by paying GMP fee", async () => {
+      let config = await mailbox.account.config.fetch(configPDA);
+      let body = Buffer.from("some body to send", "utf8");
+      let recipient = Buffer.from(sha256("recipient"), "hex");
+      let recipientBz = Array.from(Uint8Array.from(recipient));
+      let destinationCaller = Buffer.from(sha256("destinationCaller"), "hex");
+      let destinationCallerBz = Array.from(Uint8Array.from(destinationCaller));
+      const outboundMessagePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      console.log("global nonce:", config.globalNonce.toNumber());
+
+      const balanceBefore = await provider.connection.getBalance(payer.publicKey);
+      const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+
+      const signature = await withBlockhashRetry(() =>
+        mailbox.methods
+        .sendMessage(body, recipientBz, destinationCallerBz, new BN(0), null, false)
+        .accountsPartial({
+          feePayer: payer.publicKey,
+          senderAuthority: payer.publicKey,
+          outboundMessage: outboundMessagePDA,
+          outboundMessageRecord: outboundMessageRecordPDA,
+          outboundMessagePath: outboundMessagePathPDA,
+          treasury: treasury.publicKey,
+          senderConfig: PublicKey.findProgramAddressSync(
+            [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+            mailbox.programId)[0]
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      const outboundMessageAccount = await provider.connection.getAccountInfo(outboundMessagePDA);
+      expect(outboundMessageAccount).to.be.not.null;
+
+      const configAfter = await mailbox.account.config.fetch(configPDA);
+      expect(configAfter.globalNonce.toNumber()).to.eq(config.globalNonce.toNumber() + 1);
+
+      // 260 is the size of the gmp message in bytes assuming body is less than 32 bytes
+      const fee = feePerByte.muln(260);
+
+      const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
+      expect(treasuryBalanceAfter).to.be.eq(treasuryBalanceBefore + fee.toNumber(), "treasury balance mismatch");
+
+      const balanceAfter = await provider.connection.getBalance(payer.publicKey);
+      // gmp fee + instruction fee should have been deducted, and assume gmp fee is greater than gas + instr fee
+      expect(balanceAfter).to.be.lt(balanceBefore - fee.toNumber(), "fee not paid");
+
+      const sent = (await getCpiEvents(mailbox, signature)).find(e => e.name === "messageSent");
+      expect(sent.data.fee.toNumber()).to.be.eq(fee.toNumber());
+      expect(sent.data.feeMint).to.be.null;
+    });
+
+    it("send message exempt from GMP fee", async () => {
+      let config = await mailbox.account.config.fetch(configPDA);
+      let body = Buffer.from("some body to send", "utf8");
+      let recipient = Buffer.from(sha256("recipient"), "hex");
+      let recipientBz = Array.from(Uint8Array.from(recipient));
+      let destinationCaller = Buffer.from(sha256("destinationCaller"), "hex");
+      let destinationCallerBz = Array.from(Uint8Array.from(destinationCaller));
+      const outboundMessagePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      console.log("global nonce:", config.globalNonce.toNumber());
+
+      // we use system program just for ease of testing
+      await withBlockhashRetry(() =>
+        mailbox.methods
+        .setSenderConfig(payerFeeExempt.publicKey, customMaxPayloadSize, true)
+        .accounts({
+          admin: admin.publicKey
         })
         .signers([admin])
         .rpc({ commitment: "confirmed" })
       );
 
-      const balanceBefore = await provider.connection.getBalance(payerFeeExempt.publicKey);
-      const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+      const balanceBefore = await provider.connection.getBalance(payerFeeExempt.publicKey);
+      const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+
+      const signature = await withBlockhashRetry(() =>
+        mailbox.methods
+        .sendMessage(body, recipientBz, destinationCallerBz, new BN(0), null, false)
+        .accountsPartial({
+          feePayer: payerFeeExempt.publicKey,
+          senderAuthority: payerFeeExempt.publicKey,
+          outboundMessage: outboundMessagePDA,
+          outboundMessageRecord: outboundMessageRecordPDA,
+          outboundMessagePath: outboundMessagePathPDA,
+          senderConfig: PublicKey.findProgramAddressSync(
+            [Buffer.from("sender_config"), payerFeeExempt.publicKey.toBuffer()],
+            mailbox.programId)[0],
+          treasury: null
+        })
+        .signers([payerFeeExempt])
+        .rpc({ commitment: "confirmed" })
+      );
+
+      const outboundMessageAccount = await provider.connection.getAccountInfo(outboundMessagePDA);
+      expect(outboundMessageAccount).to.be.not.null;
+
+      const configAfter = await mailbox.account.config.fetch(configPDA);
+      expect(configAfter.globalNonce.toNumber()).to.eq(config.globalNonce.toNumber() + 1);
+
+      // 260 is the size of the gmp message in bytes assuming body is less than 32 bytes
+      const fee = feePerByte.muln(260);
+
+      // treasury balance didnt change
+      const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
+      expect(treasuryBalanceAfter).to.be.eq(treasuryBalanceBefore);
+
+      const balanceAfter = await provider.connection.getBalance(payerFeeExempt.publicKey);
+      expect(balanceAfter).to.be.gt(balanceBefore - fee.toNumber());
+
+      //Event, emitted through self-CPI
+      const sent = (await getCpiEvents(mailbox, signature)).find(e => e.name === "messageSent");
+      expect(sent).to.be.not.undefined;
+      expect(sent.data.nonce.toNumber()).to.be.deep.eq(config.globalNonce.toNumber());
+      expect(sent.data.destinationChainId).to.be.deep.eq(LEDGER_LCHAIN_ID_BZ);
+      expect(sent.data.recipient).to.be.deep.eq(recipientBz);
+      expect(sent.data.bodyLength.toNumber()).to.be.eq(body.length);
+      expect(sent.data.fee.toNumber()).to.be.eq(0);
+      expect(sent.data.feeMint).to.be.null;
+    });
+
+    describe("Send options", () => {
+      const send = async (options: { deadline: BN; executionHint: BN; refundAddress: number[]; sequenced: boolean }) => {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(Buffer.from("some body to send", "utf8"), Array.from(ZERO_BUFFER32), null, new BN(0), options, false)
+            .accountsPartial({
+              feePayer: payerFeeExempt.publicKey,
+              senderAuthority: payerFeeExempt.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              senderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), payerFeeExempt.publicKey.toBuffer()],
+                mailbox.programId)[0],
+              treasury: null
+            })
+            .signers([payerFeeExempt])
+            .rpc({ commitment: "confirmed" })
+        );
+        return outboundMessagePDA;
+      };
+
+      it("sendMessage sends a MessageV2 carrying the options", async () => {
+        const deadline = Math.floor(Date.now() / 1000) + 3600;
+        const refundAddress = Buffer.from(sha256("refund"), "hex");
+        const outboundMessagePDA = await send({
+          deadline: new BN(deadline),
+          executionHint: new BN(200_000),
+          refundAddress: Array.from(refundAddress),
+          sequenced: false
+        });
+
+        const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+        expect(data.subarray(0, 4).toString("hex")).to.be.eq(MESSAGE_V2_SELECTOR);
+        // the options follow the destination caller
+        const word = (i: number) => data.subarray(4 + 32 * i, 4 + 32 * (i + 1));
+        expect(new BN(word(5)).toNumber()).to.be.eq(deadline);
+        expect(new BN(word(6)).toNumber()).to.be.eq(200_000);
+        expect(word(7)).to.be.deep.eq(refundAddress);
+      });
+
+      it("sendMessage rejects when the deadline has passed", async () => {
+        await expect(
+          send({ deadline: new BN(1), executionHint: new BN(0), refundAddress: Array.from(ZERO_BUFFER32), sequenced: false })
+        ).to.be.rejectedWith("MessageExpired");
+      });
+
+      it("sendMessage sends sequenced messages as MessageV3 numbered by the path", async () => {
+        const options = { deadline: new BN(0), executionHint: new BN(0), refundAddress: Array.from(ZERO_BUFFER32), sequenced: true };
+        const { nextSequence } = await mailbox.account.outboundMessagePath.fetch(outboundMessagePathPDA);
+        const firstPDA = await send(options);
+        const secondPDA = await send(options);
+
+        // the flags and the sequence follow the deadline, execution hint and refund address
+        const sequence = async (pda: PublicKey) => {
+          const data = (await provider.connection.getAccountInfo(pda)).data;
+          expect(data.subarray(0, 4).toString("hex")).to.be.eq(MESSAGE_V3_SELECTOR);
+          expect(data[4 + 32 * 9 - 1]).to.be.eq(SEQUENCED);
+          return new BN(data.subarray(4 + 32 * 9, 4 + 32 * 10)).toNumber();
+        };
+        expect(await sequence(firstPDA)).to.be.eq(nextSequence.toNumber());
+        expect(await sequence(secondPDA)).to.be.eq(nextSequence.toNumber() + 1);
+        const path = await mailbox.account.outboundMessagePath.fetch(outboundMessagePathPDA);
+        expect(path.nextSequence.toNumber()).to.be.eq(nextSequence.toNumber() + 2);
+      });
+    });
+
+    describe("Fee schedules", () => {
+      const baseFee = new BN(5000000);
+      const senderFeePerByte = new BN(1000);
+      const senderFeeSchedulePDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("sender_fee_schedule"), LEDGER_LCHAIN_ID, payer.publicKey.toBuffer()],
+        mailbox.programId
+      )[0];
+
+      async function quoteFee(bodyLength: number): Promise<BN> {
+        return mailbox.methods
+          .quoteFee(LEDGER_LCHAIN_ID_BZ, payer.publicKey, bodyLength, new BN(0))
+          .accounts({ senderConfig: null })
+          .view({ commitment: "confirmed" });
+      }
+
+      async function sendAndGetTreasuryDelta(): Promise<number> {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const treasuryBalanceBefore = await provider.connection.getBalance(treasury.publicKey);
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(Buffer.from("some body to send", "utf8"), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: payer.publicKey,
+              senderAuthority: payer.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey,
+              senderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+                mailbox.programId)[0]
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+        const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
+        return treasuryBalanceAfter - treasuryBalanceBefore;
+      }
+
+      after("restore default path fees", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundPathFees(LEDGER_LCHAIN_ID_BZ, new BN(0), feePerByte)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+      });
+
+      it("new path uses default fee per byte", async () => {
+        const path = await mailbox.account.outboundMessagePath.fetch(outboundMessagePathPDA);
+        expect(path.feeSchedule.baseFee.toNumber()).to.be.eq(0);
+        expect(path.feeSchedule.feePerByte.toBigInt()).to.be.eq(feePerByte.toBigInt());
+        expect((await quoteFee(17)).toBigInt()).to.be.eq(feePerByte.muln(260).toBigInt());
+      });
+
+      it("setOutboundPathFees rejects when called by not admin", async () => {
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .setOutboundPathFees(LEDGER_LCHAIN_ID_BZ, baseFee, feePerByte)
+              .accounts({ admin: user.publicKey })
+              .signers([user])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("setOutboundPathFees successful by admin and charged on send", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundPathFees(LEDGER_LCHAIN_ID_BZ, baseFee, feePerByte)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const expectedFee = baseFee.add(feePerByte.muln(260));
+        expect((await quoteFee(17)).toBigInt()).to.be.eq(expectedFee.toBigInt());
+        // a 33 bytes body takes one more abi slot
+        expect((await quoteFee(33)).toBigInt()).to.be.eq(baseFee.add(feePerByte.muln(292)).toBigInt());
+        expect(await sendAndGetTreasuryDelta()).to.be.eq(expectedFee.toNumber());
+      });
+
+      it("setSenderPathFees overrides path fees for the sender", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setSenderPathFees(LEDGER_LCHAIN_ID_BZ, payer.publicKey, new BN(0), senderFeePerByte)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const expectedFee = senderFeePerByte.muln(260);
+        expect((await quoteFee(17)).toBigInt()).to.be.eq(expectedFee.toBigInt());
+        expect(await sendAndGetTreasuryDelta()).to.be.eq(expectedFee.toNumber());
+      });
+
+      it("unsetSenderPathFees falls back to path fees", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .unsetSenderPathFees(LEDGER_LCHAIN_ID_BZ, payer.publicKey)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+        expect(await provider.connection.getAccountInfo(senderFeeSchedulePDA)).to.be.null;
+        expect((await quoteFee(17)).toBigInt()).to.be.eq(baseFee.add(feePerByte.muln(260)).toBigInt());
+      });
+    });
+
+    describe("Fee tokens", () => {
+      // token base units per SOL of fee
+      const rate = new BN(1000);
+      let feeMint: PublicKey;
+      let feeTokenPDA: PublicKey;
+      let payerTokenAccount: PublicKey;
+      let treasuryTokenAccount: PublicKey;
+
+      before("create fee token", async () => {
+        feeMint = await spl.createMint(provider.connection, admin, admin.publicKey, null, 8, undefined, {
+          commitment: "confirmed"
+        });
+        feeTokenPDA = PublicKey.findProgramAddressSync([Buffer.from("fee_token"), feeMint.toBuffer()], mailbox.programId)[0];
+        payerTokenAccount = await spl.createAssociatedTokenAccount(
+          provider.connection,
+          admin,
+          feeMint,
+          payer.publicKey,
+          { commitment: "confirmed" }
+        );
+        treasuryTokenAccount = await spl.createAssociatedTokenAccount(
+          provider.connection,
+          admin,
+          feeMint,
+          treasury.publicKey,
+          { commitment: "confirmed" }
+        );
+        await spl.mintTo(provider.connection, admin, feeMint, payerTokenAccount, admin, 10000, [], {
+          commitment: "confirmed"
+        });
+      });
+
+      function sendWithTokenFee(treasuryToken: PublicKey) {
+        return mailbox.account.config.fetch(configPDA).then(config =>
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .sendMessage(Buffer.from("some body to send", "utf8"), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+              .accountsPartial({
+                feePayer: payer.publicKey,
+                senderAuthority: payer.publicKey,
+                outboundMessage: PublicKey.findProgramAddressSync(
+                  [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+                  mailbox.programId
+                )[0],
+                outboundMessageRecord: PublicKey.findProgramAddressSync(
+                  [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+                  mailbox.programId
+                )[0],
+                outboundMessagePath: outboundMessagePathPDA,
+                treasury: null,
+                senderConfig: PublicKey.findProgramAddressSync(
+                  [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+                  mailbox.programId)[0],
+                feeToken: feeTokenPDA,
+                feeMint,
+                feePayerTokenAccount: payerTokenAccount,
+                treasuryTokenAccount: treasuryToken,
+                feeTokenProgram: spl.TOKEN_PROGRAM_ID
+              })
+              .signers([payer])
+              .rpc({ commitment: "confirmed" })
+          )
+        );
+      }
+
+      it("setFeeToken rejects when called by not admin", async () => {
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .setFeeToken(rate)
+              .accounts({ admin: user.publicKey, mint: feeMint, treasuryTokenAccount })
+              .signers([user])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("setFeeToken successful by admin", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setFeeToken(rate)
+            .accounts({ admin: admin.publicKey, mint: feeMint, treasuryTokenAccount })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const quote: BN = await mailbox.methods
+          .quoteFee(LEDGER_LCHAIN_ID_BZ, payer.publicKey, 17, new BN(0))
+          .accounts({ senderConfig: null, senderFeeSchedule: null, feeToken: feeTokenPDA })
+          .view({ commitment: "confirmed" });
+        // 260 bytes * 0.001 SOL per byte = 0.26 SOL at 1000 units per SOL
+        expect(quote.toNumber()).to.be.eq(260);
+      });
+
+      it("sendMessage rejects token fee paid to another account", async () => {
+        await expect(sendWithTokenFee(payerTokenAccount)).to.be.rejectedWith("TreasuryMismatch");
+      });
+
+      it("sendMessage pays fee in token", async () => {
+        const treasuryLamportsBefore = await provider.connection.getBalance(treasury.publicKey);
+        await sendWithTokenFee(treasuryTokenAccount);
+
+        const treasuryAccount = await spl.getAccount(provider.connection, treasuryTokenAccount, "confirmed");
+        expect(Number(treasuryAccount.amount)).to.be.eq(260);
+        expect(await provider.connection.getBalance(treasury.publicKey)).to.be.eq(treasuryLamportsBefore);
+      });
+
+      it("removeFeeToken disables token fees", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .removeFeeToken(feeMint)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+        await expect(sendWithTokenFee(treasuryTokenAccount)).to.be.rejectedWith("AccountNotInitialized");
+      });
+    });
+
+    describe("Rate limits", () => {
+      const disabled = { enabled: false, capacity: new BN(0), rate: new BN(0) };
+      const body = Buffer.from("some body to send", "utf8");
+
+      async function send(sender: Keypair, senderConfigOf: PublicKey = sender.publicKey) {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        return withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(body, Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: sender.publicKey,
+              senderAuthority: sender.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey,
+              senderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), senderConfigOf.toBuffer()],
+                mailbox.programId)[0]
+            })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+      }
+
+      after("disable rate limits", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundPathRateLimits(LEDGER_LCHAIN_ID_BZ, disabled, disabled)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setSenderRateLimits(payerFeeExempt.publicKey, disabled, disabled)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+      });
+
+      it("setOutboundPathRateLimits rejects when called by not admin", async () => {
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .setOutboundPathRateLimits(LEDGER_LCHAIN_ID_BZ, disabled, disabled)
+              .accounts({ admin: t.publicKey })
+              .signers([t])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("sendMessage rejects when path bytes rate limit is reached", async () => {
+        const bytesLimit = { enabled: true, capacity: new BN(body.length + 3), rate: new BN(1) };
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundPathRateLimits(LEDGER_LCHAIN_ID_BZ, disabled, bytesLimit)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const path = await mailbox.account.outboundMessagePath.fetch(outboundMessagePathPDA);
+        expect(path.bytesRateLimit.tokens.toNumber()).to.be.eq(body.length + 3);
+
+        await send(payer);
+        await expect(send(payer)).to.be.rejectedWith("RLRateLimitReached");
+      });
+
+      it("sendMessage rejects when sender bytes capacity is exceeded", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundPathRateLimits(LEDGER_LCHAIN_ID_BZ, disabled, disabled)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+        const bytesLimit = { enabled: true, capacity: new BN(body.length - 1), rate: new BN(1) };
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setSenderRateLimits(payerFeeExempt.publicKey, disabled, bytesLimit)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        await expect(send(payerFeeExempt)).to.be.rejectedWith("RLMaxCapacityExceeded");
+      });
+
+      it("sendMessage rejects the sender config of another sender", async () => {
+        // the sender config can't be swapped for one without limits to skip them
+        await expect(send(payerFeeExempt, payer.publicKey)).to.be.rejectedWith("ConstraintSeeds");
+      });
+    });
+
+    describe("Sender allowlists", () => {
+      const body = Buffer.from("some allowlisted body", "utf8");
+      const otherChainId = Array.from(Buffer.alloc(32, 7));
+      const recipient = Array.from(Buffer.alloc(32, 1));
+      const senderConfigPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("sender_config"), payerFeeExempt.publicKey.toBuffer()],
+        mailbox.programId
+      )[0];
+
+      async function send(recipient: number[], senderConfig: PublicKey = senderConfigPDA) {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        return withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(body, recipient, null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: payerFeeExempt.publicKey,
+              senderAuthority: payerFeeExempt.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey,
+              senderConfig
+            })
+            .signers([payerFeeExempt])
+            .rpc({ commitment: "confirmed" })
+        );
+      }
+
+      async function setAllowlists(destinationChainIds: number[][], recipients: number[][]) {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setSenderAllowlists(payerFeeExempt.publicKey, destinationChainIds, recipients)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+      }
+
+      after("clear allowlists", async () => {
+        await setAllowlists([], []);
+      });
+
+      it("setSenderAllowlists rejects when called by not admin", async () => {
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .setSenderAllowlists(payerFeeExempt.publicKey, [], [])
+              .accounts({ admin: t.publicKey })
+              .signers([t])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("setSenderAllowlists rejects too many destinations", async () => {
+        const destinations = Array.from({ length: 9 }, (_, i) => Array.from(Buffer.alloc(32, i)));
+        await expect(setAllowlists(destinations, [])).to.be.rejectedWith("InvalidSenderAllowlist");
+      });
+
+      it("sendMessage rejects when destination is not allowed", async () => {
+        await setAllowlists([otherChainId], []);
+        const senderConfig = await mailbox.account.senderConfig.fetch(senderConfigPDA);
+        expect(senderConfig.allowedDestinationChainIds).to.be.deep.eq([otherChainId]);
+
+        await expect(send(recipient)).to.be.rejectedWith("DestinationNotAllowed");
+      });
+
+      it("sendMessage rejects when recipient is not allowed", async () => {
+        await setAllowlists([Array.from(LEDGER_LCHAIN_ID_BZ)], [recipient]);
+        await expect(send(Array.from(ZERO_BUFFER32))).to.be.rejectedWith("RecipientNotAllowed");
+      });
+
+      it("sendMessage successful to allowed destination and recipient", async () => {
+        await send(recipient);
+      });
+
+      it("sendMessage rejects the sender config of an unrestricted sender", async () => {
+        const unrestrictedSenderConfigPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+          mailbox.programId
+        )[0];
+        await expect(send(Array.from(ZERO_BUFFER32), unrestrictedSenderConfigPDA)).to.be.rejectedWith(
+          "ConstraintSeeds"
+        );
+      });
+    });
+
+    describe("Message buffers", () => {
+      const sender = Keypair.generate();
+      const bufferId = new BN(1);
+      const messageBufferPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("message_buffer"), sender.publicKey.toBuffer(), bufferId.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
+      const senderConfigPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("sender_config"), sender.publicKey.toBuffer()],
+        mailbox.programId
+      )[0];
+      // larger than what fits in a single transaction
+      const body = Buffer.alloc(1500, 7);
+
+      before(async () => {
+        await fundWallet(sender, 5);
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setSenderConfig(sender.publicKey, 16 * 1024, false)
+            .accounts({ admin: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        );
+      });
 
-      await withBlockhashRetry(() =>
-        mailbox.methods
-        .sendMessage(body, recipientBz, destinationCallerBz, new BN(0))
-        .accountsPartial({
-          feePayer: payerFeeExempt.publicKey,
-          senderAuthority: payerFeeExempt.publicKey,
-          outboundMessage: outboundMessagePDA,
-          outboundMessagePath: outboundMessagePathPDA,
-          senderConfig: PublicKey.findProgramAddressSync(
-            [Buffer.from("sender_config"), payerFeeExempt.publicKey.toBuffer()],
-            mailbox.programId)[0],
-          treasury: null
-        })
-        .signers([payerFeeExempt])
-        .rpc({ commitment: "confirmed" })
+      it("writeMessageBuffer stages the body in chunks", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .initMessageBuffer(bufferId)
+            .accounts({ payer: sender.publicKey, owner: sender.publicKey })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+        for (let i = 0; i < body.length; i += 500) {
+          await withBlockhashRetry(() =>
+            mailbox.methods
+              .writeMessageBuffer(bufferId, body.subarray(i, i + 500))
+              .accounts({ payer: sender.publicKey, owner: sender.publicKey })
+              .signers([sender])
+              .rpc({ commitment: "confirmed" })
+          );
+        }
+
+        const bufferAccount = await provider.connection.getAccountInfo(messageBufferPDA);
+        expect(bufferAccount.data.subarray(8 + 1 + 32)).to.be.deep.eq(body);
+      });
+
+      it("sendMessage rejects a body along with a buffer", async () => {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .sendMessage(Buffer.from("body"), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+              .accountsPartial({
+                feePayer: sender.publicKey,
+                senderAuthority: sender.publicKey,
+                messageBuffer: messageBufferPDA,
+                senderConfig: senderConfigPDA,
+                outboundMessage: outboundMessagePDA,
+                outboundMessageRecord: outboundMessageRecordPDA,
+                outboundMessagePath: outboundMessagePathPDA,
+                treasury: treasury.publicKey
+              })
+              .signers([sender])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("InvalidMessageBuffer");
+      });
+
+      it("sendMessage sends the staged body and closes the buffer", async () => {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(Buffer.alloc(0), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: sender.publicKey,
+              senderAuthority: sender.publicKey,
+              messageBuffer: messageBufferPDA,
+              senderConfig: senderConfigPDA,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey
+            })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const outboundMessage = await provider.connection.getAccountInfo(outboundMessagePDA);
+        // the body follows the selector, the header and the body offset and length words
+        expect(outboundMessage.data.subarray(4 + 32 * 7, 4 + 32 * 7 + body.length)).to.be.deep.eq(body);
+        expect(await provider.connection.getAccountInfo(messageBufferPDA)).to.be.null;
+      });
+
+      it("sendMessage sends a body over 10 KiB into a reserved outbound message", async () => {
+        const largeBufferId = new BN(3);
+        const largeBufferPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("message_buffer"), sender.publicKey.toBuffer(), largeBufferId.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const largeBody = Buffer.alloc(12 * 1024, 9);
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .initMessageBuffer(largeBufferId)
+            .accounts({ payer: sender.publicKey, owner: sender.publicKey })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+        for (let i = 0; i < largeBody.length; i += 900) {
+          await withBlockhashRetry(() =>
+            mailbox.methods
+              .writeMessageBuffer(largeBufferId, largeBody.subarray(i, i + 900))
+              .accounts({ payer: sender.publicKey, owner: sender.publicKey })
+              .signers([sender])
+              .rpc({ commitment: "confirmed" })
+          );
+        }
+
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const send = () =>
+          mailbox.methods
+            .sendMessage(Buffer.alloc(0), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: sender.publicKey,
+              senderAuthority: sender.publicKey,
+              messageBuffer: largeBufferPDA,
+              senderConfig: senderConfigPDA,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey
+            })
+            .signers([sender]);
+
+        // an instruction can't allocate more than 10 KiB
+        await expect(withBlockhashRetry(() => send().rpc({ commitment: "confirmed" }))).to.be.rejectedWith(
+          "OutboundMessageNotReserved"
+        );
+
+        const payloadLength = 4 + 32 * 7 + largeBody.length;
+        const reserve = await mailbox.methods
+          .reserveOutboundMessage(new BN(payloadLength))
+          .accountsPartial({ payer: sender.publicKey, outboundMessage: outboundMessagePDA })
+          .instruction();
+        await withBlockhashRetry(() =>
+          send()
+            .preInstructions([ComputeBudgetProgram.setComputeUnitLimit({ units: 400_000 }), reserve])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const outboundMessage = await provider.connection.getAccountInfo(outboundMessagePDA);
+        expect(outboundMessage.data.length).to.be.eq(payloadLength);
+        expect(outboundMessage.data.subarray(4 + 32 * 7)).to.be.deep.eq(largeBody);
+        expect(await provider.connection.getAccountInfo(largeBufferPDA)).to.be.null;
+      });
+
+      it("closeMessageBuffer discards a staged body", async () => {
+        const otherBufferId = new BN(2);
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .initMessageBuffer(otherBufferId)
+            .accounts({ payer: sender.publicKey, owner: sender.publicKey })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .closeMessageBuffer(otherBufferId)
+            .accounts({ owner: sender.publicKey, receiver: sender.publicKey })
+            .signers([sender])
+            .rpc({ commitment: "confirmed" })
+        );
+        const otherBufferPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("message_buffer"), sender.publicKey.toBuffer(), otherBufferId.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        expect(await provider.connection.getAccountInfo(otherBufferPDA)).to.be.null;
+      });
+    });
+
+    describe("Acknowledgements", () => {
+      const ackCallbackPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("ack_callback"), user.publicKey.toBuffer()],
+        mailbox.programId
+      )[0];
+      const receiverConfigPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("mailbox_receiver_config")],
+        mailboxReceiver.programId
+      )[0];
+
+      const currentOutboundMessage = async () => {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        return { nonce: config.globalNonce.toNumber(), outboundMessagePDA, outboundMessageRecordPDA };
+      };
+
+      const deliver = async (message: MessageV1) => {
+        const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+        await withBlockhashRetry(() =>
+          consortium.methods
+            .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+            .accounts({
+              payer: payer.publicKey,
+              sessionPayload: message.sessionPayloadPDA(payer)
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .deliverMessage(message.toHashBytes())
+            .accounts({
+              deliverer: payer.publicKey,
+              inboundMessagePath: inboundMessagePathPDA,
+              consortiumPayload: message.sessionPayloadPDA(payer),
+              consortiumValidatedPayload: validatedPayloadPDA
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+      };
+
+      it("sendMessage flags a message requesting an acknowledgement", async () => {
+        const { outboundMessagePDA, outboundMessageRecordPDA } = await currentOutboundMessage();
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(Buffer.from("ping"), Array.from(ZERO_BUFFER32), null, new BN(0), null, true)
+            .accountsPartial({
+              feePayer: payer.publicKey,
+              senderAuthority: payer.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey,
+              senderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+                mailbox.programId)[0]
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+
+        const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+        expect(data.subarray(0, 4).toString("hex")).to.be.eq(MESSAGE_V3_SELECTOR);
+        // the flags follow the deadline, execution hint and refund address
+        expect(data[4 + 32 * 9 - 1]).to.be.eq(ACK_REQUESTED);
+      });
+
+      it("setAckCallback rejects an authority not acting for the sender", async () => {
+        await expect(
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .setAckCallback(user.publicKey, mailboxReceiver.programId)
+              .accounts({ payer: payer.publicKey, senderAuthority: payer.publicKey })
+              .signers([payer])
+              .rpc({ commitment: "confirmed" })
+          )
+        ).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("setAckCallback registers the callback of the sender", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .setAckCallback(user.publicKey, mailboxReceiver.programId)
+            .accounts({ payer: user.publicKey, senderAuthority: user.publicKey })
+            .signers([user])
+            .rpc({ commitment: "confirmed" })
+        );
+        const ackCallback = await mailbox.account.ackCallback.fetch(ackCallbackPDA);
+        expect(ackCallback.callbackProgram.toBase58()).to.be.eq(mailboxReceiver.programId.toBase58());
+      });
+
+      describe("on the destination chain", () => {
+        const remoteSender = Keypair.generate().publicKey.toBuffer();
+        const message = new MessageV2(
+          inboundMessagePath,
+          ++globalNonce,
+          remoteSender,
+          mailboxReceiver.programId.toBuffer(),
+          Buffer.from("ping"),
+          ZERO_BUFFER32,
+          ACK_REQUESTED
+        );
+
+        const handle = (ackAccounts: object) =>
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .handleMessage(message.toHashBytes())
+              .accounts({
+                handler: payer.publicKey,
+                deliverer: payer.publicKey,
+                recipientProgram: mailboxReceiver.programId,
+                inboundMessagePath: inboundMessagePathPDA,
+                ...ackAccounts
+              })
+              .remainingAccounts([
+                { pubkey: payer.publicKey, isWritable: true, isSigner: true },
+                { pubkey: receiverConfigPDA, isWritable: false, isSigner: false },
+                { pubkey: message.receiverMessageHandledPDA(), isWritable: true, isSigner: false },
+                { pubkey: SystemProgram.programId, isWritable: false, isSigner: false }
+              ])
+              .signers([payer])
+              .rpc({ commitment: "confirmed" })
+          );
+
+        before(async () => {
+          await deliver(message);
+        });
+
+        it("handleMessage rejects without the accounts to send the acknowledgement", async () => {
+          await expect(handle({})).to.be.rejectedWith("MissingAckAccounts");
+        });
+
+        it("handleMessage sends back the acknowledgement", async () => {
+          const { nonce, outboundMessagePDA, outboundMessageRecordPDA } = await currentOutboundMessage();
+          await handle({
+            ackOutboundMessagePath: outboundMessagePathPDA,
+            ackOutboundMessage: outboundMessagePDA,
+            ackOutboundMessageRecord: outboundMessageRecordPDA
+          });
+
+          // the acknowledgement goes to the source mailbox, carrying the acknowledged payload hash,
+          // its sender and the data returned by the recipient, the hash of the body it received
+          const ack = new MessageV1(
+            outboundMessagePath,
+            nonce,
+            mailbox.programId.toBuffer(),
+            LEDGER_MAILBOX_ADDRESS,
+            Buffer.concat([message.toHash(), remoteSender, Buffer.from(sha256("ping"), "hex")])
+          );
+          const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+          expect(data.subarray(0, ack.toBuffer().length)).to.be.deep.eq(ack.toBuffer());
+
+          const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(message.messageInfoPDA());
+          expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+        });
+      });
+
+      describe("on the source chain", () => {
+        const ackedPayloadHash = Buffer.from(sha256("acked payload"), "hex");
+        const receiverAckReceivedPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("ack_received"), ackedPayloadHash],
+          mailboxReceiver.programId
+        )[0];
+
+        const handleAck = (ack: MessageV1) =>
+          withBlockhashRetry(() =>
+            mailbox.methods
+              .handleMessage(ack.toHashBytes())
+              .accounts({
+                handler: payer.publicKey,
+                deliverer: payer.publicKey,
+                recipientProgram: mailbox.programId,
+                inboundMessagePath: inboundMessagePathPDA,
+                ackCallback: ackCallbackPDA
+              })
+              .remainingAccounts([
+                // the callback program, followed by the accounts of its gmp_ack instruction
+                { pubkey: mailboxReceiver.programId, isWritable: false, isSigner: false },
+                { pubkey: payer.publicKey, isWritable: true, isSigner: true },
+                { pubkey: receiverConfigPDA, isWritable: false, isSigner: false },
+                { pubkey: receiverAckReceivedPDA, isWritable: true, isSigner: false },
+                { pubkey: SystemProgram.programId, isWritable: false, isSigner: false }
+              ])
+              .signers([payer])
+              .rpc({ commitment: "confirmed" })
+          );
+
+        it("handleMessage rejects an acknowledgement not sent by the remote mailbox", async () => {
+          const ack = new MessageV1(
+            inboundMessagePath,
+            ++globalNonce,
+            Keypair.generate().publicKey.toBuffer(),
+            mailbox.programId.toBuffer(),
+            Buffer.concat([ackedPayloadHash, user.publicKey.toBuffer(), Buffer.from("pong")])
+          );
+          await deliver(ack);
+          await expect(handleAck(ack)).to.be.rejectedWith("InvalidAck");
+        });
+
+        it("handleMessage of an acknowledgement calls the callback of the sender", async () => {
+          const ack = new MessageV1(
+            inboundMessagePath,
+            ++globalNonce,
+            LEDGER_MAILBOX_ADDRESS,
+            mailbox.programId.toBuffer(),
+            Buffer.concat([ackedPayloadHash, user.publicKey.toBuffer(), Buffer.from("pong")])
+          );
+          await deliver(ack);
+          await handleAck(ack);
+
+          const ackReceived = await mailboxReceiver.account.ackReceived.fetch(receiverAckReceivedPDA);
+          expect(ackReceived.result).to.be.deep.eq(Buffer.from("pong"));
+          const handledMessageInfo = await mailbox.account.handledMessageInfo.fetch(ack.messageInfoPDA());
+          expect(handledMessageInfo.status).to.deep.eq({ handled: {} });
+        });
+      });
+
+      it("unsetAckCallback removes the callback of the sender", async () => {
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .unsetAckCallback(user.publicKey)
+            .accounts({ senderAuthority: user.publicKey })
+            .signers([user])
+            .rpc({ commitment: "confirmed" })
+        );
+        expect(await provider.connection.getAccountInfo(ackCallbackPDA)).to.be.null;
+      });
+    });
+
+    describe("Echo replies", () => {
+      const remoteSender = Keypair.generate().publicKey.toBuffer();
+      const message = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        remoteSender,
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("echo me")
       );
+      const receiverConfigPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("mailbox_receiver_config")],
+        mailboxReceiver.programId
+      )[0];
 
-      const outboundMessageAccount = await provider.connection.getAccountInfo(outboundMessagePDA);
-      expect(outboundMessageAccount).to.be.not.null;
+      const echo = async () => {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        await withBlockhashRetry(() =>
+          mailboxReceiver.methods
+            .echo(message.toHashBytes())
+            .accountsPartial({
+              payer: payer.publicKey,
+              messageHandled: message.receiverMessageHandledPDA(),
+              mailbox: mailbox.programId,
+              mailboxConfig: configPDA,
+              inboundMessagePath: inboundMessagePathPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              treasury: treasury.publicKey,
+              mailboxSenderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), mailboxReceiver.programId.toBuffer()],
+                mailbox.programId)[0]
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+        return { nonce: config.globalNonce.toNumber(), outboundMessagePDA };
+      };
 
-      const configAfter = await mailbox.account.config.fetch(configPDA);
-      expect(configAfter.globalNonce.toNumber()).to.eq(config.globalNonce.toNumber() + 1);
+      before("deliver and handle a message to the receiver", async () => {
+        const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+        await withBlockhashRetry(() =>
+          consortium.methods
+            .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+            .accounts({
+              payer: payer.publicKey,
+              sessionPayload: message.sessionPayloadPDA(payer)
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .deliverAndHandleMessage(message.toHashBytes())
+            .accounts({
+              deliverer: payer.publicKey,
+              recipientProgram: mailboxReceiver.programId,
+              inboundMessagePath: inboundMessagePathPDA,
+              consortiumPayload: message.sessionPayloadPDA(payer),
+              consortiumValidatedPayload: validatedPayloadPDA
+            })
+            .remainingAccounts([
+              { pubkey: payer.publicKey, isWritable: true, isSigner: true },
+              { pubkey: receiverConfigPDA, isWritable: false, isSigner: false },
+              { pubkey: message.receiverMessageHandledPDA(), isWritable: true, isSigner: false },
+              { pubkey: SystemProgram.programId, isWritable: false, isSigner: false }
+            ])
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+      });
 
-      // 260 is the size of the gmp message in bytes assuming body is less than 32 bytes
-      const fee = feePerByte.muln(260);
+      it("echo sends the body back to the sender on the source chain", async () => {
+        const { nonce, outboundMessagePDA } = await echo();
 
-      // treasury balance didnt change
-      const treasuryBalanceAfter = await provider.connection.getBalance(treasury.publicKey);
-      expect(treasuryBalanceAfter).to.be.eq(treasuryBalanceBefore);
+        // the receiver program is the sender of the reply
+        const reply = new MessageV1(
+          outboundMessagePath,
+          nonce,
+          mailboxReceiver.programId.toBuffer(),
+          remoteSender,
+          Buffer.from("echo me")
+        );
+        const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+        expect(data.subarray(0, reply.toBuffer().length)).to.be.deep.eq(reply.toBuffer());
 
-      const balanceAfter = await provider.connection.getBalance(payerFeeExempt.publicKey);
-      expect(balanceAfter).to.be.gt(balanceBefore - fee.toNumber());
+        const messageHandled = await mailboxReceiver.account.messageHandled.fetch(message.receiverMessageHandledPDA());
+        expect(messageHandled.echoed).to.be.true;
+      });
 
-      //Event
-      expect(MsgSentEvents[0]).to.be.not.undefined;
-      expect(MsgSentEvents[0].nonce.toNumber()).to.be.deep.eq(config.globalNonce.toNumber());
+      it("echo rejects when already echoed", async () => {
+        await expect(echo()).to.be.rejectedWith("AlreadyEchoed");
+      });
+    });
+
+    describe("Close outbound message", () => {
+      async function send(): Promise<{ nonce: BN; outboundMessagePDA: PublicKey; outboundMessageRecordPDA: PublicKey }> {
+        const config = await mailbox.account.config.fetch(configPDA);
+        const outboundMessagePDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+          [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+          mailbox.programId
+        )[0];
+        await withBlockhashRetry(() =>
+          mailbox.methods
+            .sendMessage(Buffer.from("some body to send", "utf8"), Array.from(ZERO_BUFFER32), null, new BN(0), null, false)
+            .accountsPartial({
+              feePayer: payer.publicKey,
+              senderAuthority: payer.publicKey,
+              outboundMessage: outboundMessagePDA,
+              outboundMessageRecord: outboundMessageRecordPDA,
+              outboundMessagePath: outboundMessagePathPDA,
+              treasury: treasury.publicKey,
+              senderConfig: PublicKey.findProgramAddressSync(
+                [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+                mailbox.programId)[0]
+            })
+            .signers([payer])
+            .rpc({ commitment: "confirmed" })
+        );
+        return { nonce: config.globalNonce, outboundMessagePDA, outboundMessageRecordPDA };
+      }
+
+      function close(nonce: BN, payerKey: PublicKey, validatedPayload: PublicKey | null) {
+        return withBlockhashRetry(() =>
+          mailbox.methods
+            .closeOutboundMessage(nonce)
+            .accounts({ payer: payerKey, consortiumValidatedPayload: validatedPayload })
+            .rpc({ commitment: "confirmed" })
+        );
+      }
+
+      function setRetention(retention: number, signer: Keypair) {
+        return withBlockhashRetry(() =>
+          mailbox.methods
+            .setOutboundMessageRetention(new BN(retention))
+            .accounts({ admin: signer.publicKey })
+            .signers([signer])
+            .rpc({ commitment: "confirmed" })
+        );
+      }
+
+      it("outbound message record holds the payer", async () => {
+        const { outboundMessagePDA, outboundMessageRecordPDA } = await send();
+        const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+        expect(data.length).to.be.eq(260);
+
+        const record = await mailbox.account.outboundMessageRecord.fetch(outboundMessageRecordPDA);
+        expect(record.payer.toBase58()).to.be.eq(payer.publicKey.toBase58());
+      });
+
+      it("closeOutboundMessage rejects when not notarized and retention disabled", async () => {
+        const { nonce } = await send();
+        await expect(close(nonce, payer.publicKey, null)).to.be.rejectedWith("OutboundMessageNotClosable");
+      });
+
+      it("closeOutboundMessage rejects when payer is not the recorded one", async () => {
+        const { nonce } = await send();
+        await expect(close(nonce, user.publicKey, null)).to.be.rejectedWith("InvalidPayer");
+      });
+
+      it("closeOutboundMessage successful once notarized", async () => {
+        const { nonce, outboundMessagePDA, outboundMessageRecordPDA } = await send();
+        const data = (await provider.connection.getAccountInfo(outboundMessagePDA)).data;
+        const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, data);
+
+        const balanceBefore = await provider.connection.getBalance(payer.publicKey);
+        await close(nonce, payer.publicKey, validatedPayloadPDA);
+
+        expect(await provider.connection.getAccountInfo(outboundMessagePDA)).to.be.null;
+        expect(await provider.connection.getAccountInfo(outboundMessageRecordPDA)).to.be.null;
+        expect(await provider.connection.getBalance(payer.publicKey)).to.be.gt(balanceBefore);
+      });
+
+      it("setOutboundMessageRetention rejects when called by not admin", async () => {
+        await expect(setRetention(1, user)).to.be.rejectedWith("Unauthorized");
+      });
+
+      it("closeOutboundMessage successful after retention", async () => {
+        await setRetention(1, admin);
+        const { nonce, outboundMessagePDA } = await send();
+        await new Promise(resolve => setTimeout(resolve, 2000));
+
+        await close(nonce, payer.publicKey, null);
+        expect(await provider.connection.getAccountInfo(outboundMessagePDA)).to.be.null;
+
+        await setRetention(0, admin);
+      });
     });
 
     it("sendMessage rejects when body is greater than max", async () => {
@@ -1499,18 +3384,25 @@ describe("Mailbox", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       await expect(
           withBlockhashRetry(() =>
             mailbox.methods
-          .sendMessage(body, recipientBz, destinationCallerBz, new BN(0))
+          .sendMessage(body, recipientBz, destinationCallerBz, new BN(0), null, false)
           .accountsPartial({
             feePayer: payer.publicKey,
             senderAuthority: payer.publicKey,
             outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
             outboundMessagePath: outboundMessagePathPDA,
             treasury: treasury.publicKey,
-            senderConfig: null
+            senderConfig: PublicKey.findProgramAddressSync(
+              [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+              mailbox.programId)[0]
           })
           .signers([payer])
           .rpc({ commitment: "confirmed" })
@@ -1519,6 +3411,75 @@ describe("Mailbox", () => {
     });
   });
 
+  describe("Views", () => {
+    it("getMessageStatus returns unknown for a message not delivered", async () => {
+      const status = await mailbox.methods
+        .getMessageStatus(Array.from(Buffer.from(sha256("not delivered"), "hex")))
+        .view({ commitment: "confirmed" });
+      expect(status).to.be.deep.eq({ unknown: {} });
+    });
+
+    it("getMessageStatus returns delivered for a delivered message", async () => {
+      const message = new MessageV1(
+        inboundMessagePath,
+        ++globalNonce,
+        user.publicKey.toBuffer(),
+        mailboxReceiver.programId.toBuffer(),
+        Buffer.from("view")
+      );
+      const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, message.toBuffer());
+      await withBlockhashRetry(() =>
+        consortium.methods
+          .postSessionPayload(message.toHashBytes(), message.toBuffer(), message.toBuffer().length)
+          .accounts({
+            payer: payer.publicKey,
+            sessionPayload: message.sessionPayloadPDA(payer)
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+      await withBlockhashRetry(() =>
+        mailbox.methods
+          .deliverMessage(message.toHashBytes())
+          .accounts({
+            deliverer: payer.publicKey,
+            inboundMessagePath: inboundMessagePathPDA,
+            consortiumPayload: message.sessionPayloadPDA(payer),
+            consortiumValidatedPayload: validatedPayloadPDA
+          })
+          .signers([payer])
+          .rpc({ commitment: "confirmed" })
+      );
+
+      const status = await mailbox.methods.getMessageStatus(message.toHashBytes()).view({ commitment: "confirmed" });
+      expect(status).to.be.deep.eq({ delivered: {} });
+    });
+
+    it("getOutboundNonce returns the nonce of the next outbound message", async () => {
+      const config = await mailbox.account.config.fetch(configPDA);
+      const nonce = await mailbox.methods.getOutboundNonce().view({ commitment: "confirmed" });
+      expect(nonce.toNumber()).to.be.eq(config.globalNonce.toNumber());
+    });
+
+    it("getOutboundPath returns the path configuration", async () => {
+      const path = await mailbox.methods.getOutboundPath(LEDGER_LCHAIN_ID_BZ).view({ commitment: "confirmed" });
+      expect(path.enabled).to.be.true;
+      expect(path.identifier).to.be.deep.eq(outboundMessagePathBytes);
+
+      const unknownChainId = Array.from(Buffer.from(sha256("unknown-chain-id"), "hex"));
+      const unknownPath = await mailbox.methods.getOutboundPath(unknownChainId).view({ commitment: "confirmed" });
+      expect(unknownPath.enabled).to.be.false;
+    });
+
+    it("getInboundPath returns the path configuration", async () => {
+      const path = await mailbox.methods.getInboundPath(LEDGER_LCHAIN_ID_BZ).view({ commitment: "confirmed" });
+      expect(path.enabled).to.be.true;
+      expect(path.identifier).to.be.deep.eq(inboundMessagePathBytes);
+      expect(path.sourceMailboxAddress).to.be.deep.eq(LEDGER_MAILBOX_ADDRESS_BZ);
+      expect(path.verificationPolicy).to.be.deep.eq({ consortium: {} });
+    });
+  });
+
   describe("Pause", function () {
     const PauseEvents = [];
     const listeners: number[] = [];
@@ -1632,18 +3593,25 @@ describe("Mailbox", () => {
         [Buffer.from("outbound_message"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
         mailbox.programId
       )[0];
+      const outboundMessageRecordPDA = PublicKey.findProgramAddressSync(
+        [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+        mailbox.programId
+      )[0];
 
       await expect(
           withBlockhashRetry(() =>
             mailbox.methods
-          .sendMessage(body, recipientBz, destinationCallerBz, new BN(0))
+          .sendMessage(body, recipientBz, destinationCallerBz, new BN(0), null, false)
           .accountsPartial({
             feePayer: payer.publicKey,
             senderAuthority: payer.publicKey,
             outboundMessage: outboundMessagePDA,
+            outboundMessageRecord: outboundMessageRecordPDA,
             outboundMessagePath: outboundMessagePathPDA,
             treasury: treasury.publicKey,
-            senderConfig: null
+            senderConfig: PublicKey.findProgramAddressSync(
+              [Buffer.from("sender_config"), payer.publicKey.toBuffer()],
+              mailbox.programId)[0]
           })
           .signers([payer])
           .rpc({ commitment: "confirmed" })
@@ -0,0 +1,180 @@
+import * as anchor from "@coral-xyz/anchor";
+import { BN, Program } from "@coral-xyz/anchor";
+import * as spl from "@solana/spl-token";
+import { Keypair, PublicKey } from "@solana/web3.js";
+import { sha256 } from "js-sha256";
+import { ProofOfReserves } from "../target/types/proof_of_reserves";
+import { Consortium } from "../target/types/consortium";
+import chai from "chai";
+import chaiAsPromised from "chai-as-promised";
+import { ConsortiumUtility } from "./utils/consortium_utilities";
+import { fundWallet, LCHAIN_ID } from "./utils/asset_router_utilities";
+import { withBlockhashRetry } from "./utils/utils";
+
+chai.use(chaiAsPromised);
+const expect = chai.expect;
+
+const RESERVES_V1_SELECTOR = Buffer.from([0xe3, 0xad, 0x26, 0x5a]);
+
+function u256(value: number): Buffer {
+  const buf = Buffer.alloc(32);
+  buf.writeBigUInt64BE(BigInt(value), 24);
+  return buf;
+}
+
+function reservesPayload(chainId: Buffer, mint: PublicKey, reserves: number, timestamp: number): Buffer {
+  return Buffer.concat([RESERVES_V1_SELECTOR, chainId, mint.toBuffer(), u256(reserves), u256(timestamp)]);
+}
+
+describe("Proof of reserves", () => {
+  const provider = anchor.AnchorProvider.env();
+  anchor.setProvider(provider);
+
+  const program = anchor.workspace.ProofOfReserves as Program<ProofOfReserves>;
+  const consortium = anchor.workspace.Consortium as Program<Consortium>;
+  const consortiumUtility = new ConsortiumUtility(consortium);
+
+  let admin: Keypair;
+  let payer: Keypair;
+  let other: Keypair;
+  let configPDA: PublicKey;
+  let mint: PublicKey;
+  let mintReservesPDA: PublicKey;
+  const now = Math.floor(Date.now() / 1000);
+
+  async function submit(payload: Buffer) {
+    const { validatedPayloadPDA } = await consortiumUtility.createAndFinalizeSession(payer, payload);
+    const payloadHash = Array.from(Uint8Array.from(Buffer.from(sha256(payload), "hex")));
+    return withBlockhashRetry(() =>
+      program.methods
+        .submitReserves(Array.from(Uint8Array.from(payload)), payloadHash)
+        .accounts({
+          payer: payer.publicKey,
+          consortiumValidatedPayload: validatedPayloadPDA,
+          mint
+        })
+        .signers([payer])
+        .rpc({ commitment: "confirmed" })
+    );
+  }
+
+  function checkMint(amount: number) {
+    return withBlockhashRetry(() =>
+      program.methods.checkMint(new BN(amount)).accounts({ mint }).rpc({ commitment: "confirmed" })
+    );
+  }
+
+  before(async () => {
+    admin = Keypair.generate();
+    payer = Keypair.generate();
+    other = Keypair.generate();
+    await fundWallet(admin, 25);
+    await fundWallet(payer, 25);
+    await fundWallet(other, 25);
+
+    consortiumUtility.generateAndAddKeypairs(3);
+    await consortiumUtility.initializeConsortiumProgram(admin);
+
+    [configPDA] = PublicKey.findProgramAddressSync([Buffer.from("proof_of_reserves_config")], program.programId);
+
+    mint = await spl.createMint(provider.connection, admin, admin.publicKey, null, 8, undefined, {
+      commitment: "confirmed"
+    });
+    [mintReservesPDA] = PublicKey.findProgramAddressSync(
+      [Buffer.from("mint_reserves"), mint.toBuffer()],
+      program.programId
+    );
+    const holder = await spl.createAssociatedTokenAccount(provider.connection, admin, mint, admin.publicKey, {
+      commitment: "confirmed"
+    });
+    await spl.mintTo(provider.connection, admin, mint, holder, admin, 1000, [], { commitment: "confirmed" });
+  });
+
+  describe("Initialize and admin", () => {
+    it("initialize: fails when payer is not deployer", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          program.methods
+            .initialize(admin.publicKey, consortium.programId, new BN(0))
+            .accounts({ deployer: admin.publicKey })
+            .signers([admin])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejected;
+    });
+
+    it("initialize: successful", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .initialize(admin.publicKey, consortium.programId, new BN(0))
+          .accounts({ deployer: provider.wallet.publicKey })
+          .signers([Keypair.fromSecretKey(provider.wallet.payer.secretKey)])
+          .rpc({ commitment: "confirmed" })
+      );
+
+      const cfg = await program.account.config.fetch(configPDA);
+      expect(cfg.admin.toBase58()).to.equal(admin.publicKey.toBase58());
+      expect(cfg.consortium.toBase58()).to.equal(consortium.programId.toBase58());
+    });
+
+    it("setMaxAttestationAge: failure from unauthorized party", async () => {
+      await expect(
+        withBlockhashRetry(() =>
+          program.methods
+            .setMaxAttestationAge(new BN(3600))
+            .accounts({ admin: other.publicKey })
+            .signers([other])
+            .rpc({ commitment: "confirmed" })
+        )
+      ).to.be.rejectedWith("Unauthorized");
+    });
+  });
+
+  describe("Reserves", () => {
+    it("checkMint: fails without attestation", async () => {
+      await expect(checkMint(1)).to.be.rejectedWith("AccountNotInitialized");
+    });
+
+    it("submitReserves: rejects payload for another chain", async () => {
+      const payload = reservesPayload(Buffer.alloc(32, 7), mint, 5000, now - 600);
+      await expect(submit(payload)).to.be.rejectedWith("InvalidChainID");
+    });
+
+    it("submitReserves: successful", async () => {
+      await submit(reservesPayload(LCHAIN_ID, mint, 5000, now - 600));
+      const reserves = await program.account.mintReserves.fetch(mintReservesPDA);
+      expect(reserves.reserves.toNumber()).to.equal(5000);
+      expect(reserves.timestamp.toNumber()).to.equal(now - 600);
+    });
+
+    it("submitReserves: rejects older attestation", async () => {
+      await expect(submit(reservesPayload(LCHAIN_ID, mint, 9000, now - 1200))).to.be.rejectedWith(
+        "StaleAttestation"
+      );
+    });
+
+    it("checkMint: successful within reserves", async () => {
+      await checkMint(4000);
+    });
+
+    it("checkMint: fails above reserves", async () => {
+      await expect(checkMint(4001)).to.be.rejectedWith("ReservesExceeded");
+    });
+
+    it("checkMint: fails with expired attestation", async () => {
+      await withBlockhashRetry(() =>
+        program.methods
+          .setMaxAttestationAge(new BN(60))
+          .accounts({ admin: admin.publicKey })
+          .signers([admin])
+          .rpc({ commitment: "confirmed" })
+      );
+      await expect(checkMint(1)).to.be.rejectedWith("AttestationExpired");
+    });
+
+    it("submitReserves: newer attestation refreshes reserves", async () => {
+      await submit(reservesPayload(LCHAIN_ID, mint, 8000, now));
+      await checkMint(7000);
+    });
+  });
+});
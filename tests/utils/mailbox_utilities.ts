@@ -12,6 +12,11 @@ const consortium = anchor.workspace.Consortium as Program<Consortium>;
 const mailbox = anchor.workspace.Mailbox as Program<Mailbox>;
 
 export const MESSAGE_V1_SELECTOR = "e288fb4a";
+export const MESSAGE_V2_SELECTOR = "a60fb72e";
+export const MESSAGE_V3_SELECTOR = "ac300840";
+// MessageV3 flag asking the destination mailbox to send back an acknowledgement
+export const ACK_REQUESTED = 1;
+export const SEQUENCED = 2;
 
 export class MailboxUtilities {
   consortiumUtility: ConsortiumUtility;
@@ -153,6 +158,35 @@ export class MailboxUtilities {
       mailbox.programId
     )[0];
   }
+
+  static async getCurrentOutboundMessageRecordPDA() {
+    const config = await mailbox.account.config.fetch(MailboxUtilities.getMailboxConfigPDA());
+    return PublicKey.findProgramAddressSync(
+      [Buffer.from("outbound_message_record"), config.globalNonce.toArrayLike(Buffer, "be", 8)],
+      mailbox.programId
+    )[0];
+  }
+}
+
+// Tag of the self-CPI instructions anchor emits events with, see `emit_cpi!`
+const EVENT_IX_TAG = Buffer.from("e445a52e51cb9a1d", "hex");
+
+/**
+ * Decodes the events a program emitted through self-CPI in a confirmed transaction.
+ */
+export async function getCpiEvents(program: Program<any>, signature: string) {
+  const tx = await program.provider.connection.getTransaction(signature, {
+    commitment: "confirmed",
+    maxSupportedTransactionVersion: 0
+  });
+  const accountKeys = tx.transaction.message.getAccountKeys({ accountKeysFromLookups: tx.meta.loadedAddresses });
+  return tx.meta.innerInstructions
+    .flatMap(inner => inner.instructions)
+    .filter(ix => accountKeys.get(ix.programIdIndex).equals(program.programId))
+    .map(ix => Buffer.from(anchor.utils.bytes.bs58.decode(ix.data)))
+    .filter(data => data.subarray(0, 8).equals(EVENT_IX_TAG))
+    .map(data => program.coder.events.decode(data.subarray(8).toString("base64")))
+    .filter(event => event !== null);
 }
 
 export function messageV1(
@@ -256,3 +290,65 @@ export class MessageV1 {
     )[0];
   }
 }
+
+// Encoded as a MessageV3 when it carries flags, as a MessageV2 otherwise
+export class MessageV2 extends MessageV1 {
+  deadline: number;
+  executionHint: number;
+  refundAddress: Buffer;
+  flags: number;
+  sequence: number;
+
+  constructor(
+    pathIdentifier: Buffer,
+    nonce: Number,
+    sender: Buffer,
+    recipient: Buffer,
+    body: Buffer,
+    destinationCaller: Buffer = ZERO_BUFFER32,
+    flags: number = 0,
+    deadline: number = 0,
+    executionHint: number = 0,
+    refundAddress: Buffer = ZERO_BUFFER32,
+    sequence: number = 0
+  ) {
+    super(
+      pathIdentifier,
+      nonce,
+      sender,
+      recipient,
+      body,
+      destinationCaller,
+      flags != 0 ? MESSAGE_V3_SELECTOR : MESSAGE_V2_SELECTOR
+    );
+    this.deadline = deadline;
+    this.executionHint = executionHint;
+    this.refundAddress = refundAddress;
+    this.flags = flags;
+    this.sequence = sequence;
+  }
+
+  toBuffer(): Buffer {
+    const types = ["bytes32", "uint256", "bytes32", "bytes32", "bytes32", "uint256", "uint256", "bytes32"];
+    const values: any[] = [
+      this.pathIdentifier,
+      this.nonce,
+      this.sender,
+      this.recipient,
+      this.destinationCaller,
+      this.deadline,
+      this.executionHint,
+      this.refundAddress
+    ];
+    if (this.flags != 0) {
+      types.push("uint256", "uint256");
+      values.push(this.flags, this.sequence);
+    }
+    types.push("bytes");
+    values.push(this.body);
+    return Buffer.concat([
+      Buffer.from(this.selector, "hex"),
+      Buffer.from(ethers.AbiCoder.defaultAbiCoder().encode(types, values).slice(2), "hex")
+    ]);
+  }
+}